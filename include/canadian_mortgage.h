@@ -0,0 +1,70 @@
+#ifndef CANADIAN_MORTGAGE_H
+#define CANADIAN_MORTGAGE_H
+
+/* C interface to the canadian_mortgage library, built with the ffi feature. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum {
+    CM_OK = 0,
+    CM_NULL_POINTER = 1,
+    CM_INVALID_FREQUENCY = 2,
+    CM_INVALID_RATE = 3,
+    CM_INVALID_AMORTIZATION = 4,
+    CM_INVALID_TERM = 5,
+    CM_INVALID_PRINCIPAL = 6,
+    CM_INVALID_PROPERTY_VALUE = 7,
+    CM_UNINSURABLE = 8,
+    CM_PAYMENT_BELOW_INTEREST = 9,
+    CM_NUMERIC_OVERFLOW = 10,
+    CM_CONVERSION_FAILURE = 11,
+    CM_INDEX_OUT_OF_RANGE = 12,
+    CM_OTHER = 99
+} CmStatus;
+
+/* Payment frequencies */
+#define CM_MONTHLY 0
+#define CM_SEMI_MONTHLY 1
+#define CM_BIWEEKLY 2
+#define CM_ACCELERATED_BIWEEKLY 3
+#define CM_WEEKLY 4
+#define CM_ACCELERATED_WEEKLY 5
+
+typedef struct {
+    uint64_t payment_number;
+    double payment;
+    double interest;
+    double principal;
+    double prepayment;
+    double balance;
+} CmScheduleRow;
+
+typedef struct CmSchedule CmSchedule;
+
+/* Any status value is accepted; unknown values are described as "error". */
+const char *cm_status_message(uint32_t status);
+
+/* rate is the annual interest rate as a percentage, compounded semi-annually. */
+CmStatus cm_payment(double principal, double rate, uint32_t amortization, uint32_t frequency,
+                    double *out_payment);
+
+CmStatus cm_insurance_premium(double loan, double property_value, uint32_t amortization,
+                              double *out_premium);
+
+/* The schedule must be released with cm_schedule_free. */
+CmStatus cm_schedule_new(double principal, double rate, uint32_t amortization, uint32_t frequency,
+                         CmSchedule **out_schedule);
+size_t cm_schedule_len(const CmSchedule *schedule);
+CmStatus cm_schedule_row(const CmSchedule *schedule, size_t index, CmScheduleRow *out_row);
+void cm_schedule_free(CmSchedule *schedule);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,71 @@
+use crate::{CanadianMortgage, PaymentFrequency, Result};
+use rust_decimal::prelude::*;
+
+impl PaymentFrequency {
+    // Accelerated payments are the monthly payment split in two or four, which adds up to an extra
+    // monthly payment every year. The non-accelerated counterpart spreads twelve monthly payments
+    // over the same number of payments instead.
+    pub fn is_accelerated(&self) -> bool {
+        matches!(
+            self,
+            PaymentFrequency::AcceleratedBiWeekly | PaymentFrequency::AcceleratedWeekly
+        )
+    }
+
+    pub fn non_accelerated(&self) -> PaymentFrequency {
+        match self {
+            PaymentFrequency::AcceleratedBiWeekly => PaymentFrequency::BiWeekly,
+            PaymentFrequency::AcceleratedWeekly => PaymentFrequency::Weekly,
+            frequency => *frequency,
+        }
+    }
+}
+
+impl CanadianMortgage {
+    // The number of years it actually takes to pay off the mortgage, which is shorter than
+    // the amortization period for accelerated frequencies.
+    pub fn effective_amortization(&self) -> Result<Decimal> {
+        let payments = crate::decimal_from_u64(self.amortization_schedule()?.len() as u64)?;
+        let payments_per_year =
+            crate::decimal_from_u64(self.payment_frequency.payments_per_year())?;
+        Ok(payments / payments_per_year)
+    }
+
+    // The lifetime interest saved by paying at an accelerated frequency instead of its
+    // non-accelerated counterpart. Zero for frequencies that are not accelerated.
+    pub fn interest_saved_by_acceleration(&self) -> Result<Decimal> {
+        let mut non_accelerated = self.clone();
+        non_accelerated.payment_frequency = self.payment_frequency.non_accelerated();
+
+        Ok(non_accelerated.summary()?.total_interest - self.summary()?.total_interest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::*;
+
+    fn mortgage(frequency: PaymentFrequency) -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(4.59), 25, frequency).unwrap()
+    }
+
+    #[test]
+    fn accelerated_payments_shorten_amortization() {
+        let monthly = mortgage(PaymentFrequency::Monthly);
+        assert_eq!(monthly.effective_amortization().unwrap(), dec!(25));
+        assert_eq!(monthly.interest_saved_by_acceleration().unwrap(), dec!(0));
+
+        let accelerated = mortgage(PaymentFrequency::AcceleratedBiWeekly);
+        let years = accelerated.effective_amortization().unwrap();
+        assert!(
+            years > dec!(21) && years < dec!(23),
+            "about 22 years, got {}",
+            years
+        );
+        assert!(accelerated.interest_saved_by_acceleration().unwrap() > dec!(0));
+
+        let accelerated = mortgage(PaymentFrequency::AcceleratedWeekly);
+        assert!(accelerated.effective_amortization().unwrap() < dec!(23));
+    }
+}
@@ -0,0 +1,213 @@
+// Lenders limit housing costs to a share of gross income with two ratios:
+// Gross Debt Service (GDS) covers the mortgage payment, property tax, heating and half of any condo fees.
+// Total Debt Service (TDS) adds every other debt payment on top of that.
+// https://www.canada.ca/en/financial-consumer-agency/services/mortgages/calculate-gds-tds.html
+use crate::{CanadianMortgage, Compounding, MortgageError, PaymentFrequency, Result};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// All costs are monthly amounts.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Household {
+    pub gross_annual_income: Decimal,
+    pub heating: Decimal,
+    pub property_tax: Decimal,
+    pub condo_fees: Decimal,
+    pub other_debts: Decimal,
+}
+
+// Maximum ratios as percentages of gross monthly income.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DebtServiceLimits {
+    pub gds: Decimal,
+    pub tds: Decimal,
+}
+
+impl Default for DebtServiceLimits {
+    fn default() -> Self {
+        DebtServiceLimits {
+            gds: dec!(39),
+            tds: dec!(44),
+        }
+    }
+}
+
+// Ratios are percentages of gross monthly income.
+// Headroom is how much more could be spent each month before reaching the limit, negative when over it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DebtService {
+    pub gds: Decimal,
+    pub tds: Decimal,
+    pub gds_headroom: Decimal,
+    pub tds_headroom: Decimal,
+    pub passes: bool,
+}
+
+// Only half of condo fees count towards housing costs.
+const CONDO_FEE_SHARE: Decimal = dec!(0.5);
+
+pub fn debt_service(
+    monthly_payment: Decimal,
+    household: &Household,
+    limits: &DebtServiceLimits,
+) -> Result<DebtService> {
+    if household.gross_annual_income <= dec!(0) {
+        return Err(MortgageError::InvalidIncome(household.gross_annual_income));
+    }
+
+    let monthly_income = household.gross_annual_income / dec!(12);
+    let housing_costs = monthly_payment
+        + household.property_tax
+        + household.heating
+        + household.condo_fees * CONDO_FEE_SHARE;
+    let total_debts = housing_costs + household.other_debts;
+
+    let gds_headroom = monthly_income * limits.gds / dec!(100) - housing_costs;
+    let tds_headroom = monthly_income * limits.tds / dec!(100) - total_debts;
+
+    Ok(DebtService {
+        gds: housing_costs / monthly_income * dec!(100),
+        tds: total_debts / monthly_income * dec!(100),
+        gds_headroom,
+        tds_headroom,
+        passes: gds_headroom >= dec!(0) && tds_headroom >= dec!(0),
+    })
+}
+
+// The largest principal that a regular payment at payment_frequency pays off over
+// amortization_period years. interest_rate is the annual rate as a percentage, compounded semi-annually.
+pub fn max_principal(
+    payment: Decimal,
+    interest_rate: Decimal,
+    amortization_period: u64,
+    payment_frequency: PaymentFrequency,
+) -> Result<Decimal> {
+    if payment <= dec!(0) {
+        return Err(MortgageError::InvalidPayment(payment));
+    }
+    if amortization_period == 0 {
+        return Err(MortgageError::InvalidAmortization(amortization_period));
+    }
+
+    crate::affordability(
+        payment_frequency.monthly_equivalent(payment),
+        crate::rates::monthly_compounded_rate(interest_rate, Compounding::SemiAnnual)? / dec!(12),
+        amortization_period
+            .checked_mul(12)
+            .ok_or(MortgageError::NumericOverflow("number of payments"))?,
+    )
+}
+
+impl CanadianMortgage {
+    // Debt service ratios using the monthly payment at the stress-tested qualifying rate,
+    // which is what lenders use to approve a mortgage.
+    pub fn debt_service(
+        &self,
+        household: &Household,
+        limits: &DebtServiceLimits,
+    ) -> Result<DebtService> {
+        let mut monthly = self.clone();
+        monthly.payment_frequency = PaymentFrequency::Monthly;
+
+        debt_service(monthly.qualifying_payment()?, household, limits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn household() -> Household {
+        Household {
+            gross_annual_income: dec!(120000),
+            heating: dec!(100),
+            property_tax: dec!(400),
+            condo_fees: dec!(400),
+            other_debts: dec!(500),
+        }
+    }
+
+    #[test]
+    fn ratios_and_headroom() {
+        let result = debt_service(dec!(2500), &household(), &DebtServiceLimits::default()).unwrap();
+
+        // housing costs: 2500 + 400 + 100 + 200 = 3200 of 10000 monthly income
+        assert_eq!(result.gds, dec!(32));
+        assert_eq!(result.tds, dec!(37));
+        assert_eq!(result.gds_headroom, dec!(700));
+        assert_eq!(result.tds_headroom, dec!(700));
+        assert!(result.passes);
+
+        let strict = DebtServiceLimits {
+            gds: dec!(30),
+            tds: dec!(40),
+        };
+        let result = debt_service(dec!(2500), &household(), &strict).unwrap();
+        assert_eq!(result.gds_headroom, dec!(-200));
+        assert!(!result.passes);
+    }
+
+    #[test]
+    fn max_principal_inverts_the_payment() {
+        for payment_frequency in PaymentFrequency::ALL.iter() {
+            let mortgage =
+                CanadianMortgage::new(dec!(430000), dec!(4.59), 25, *payment_frequency).unwrap();
+            assert_eq!(
+                max_principal(
+                    mortgage.payment().unwrap(),
+                    dec!(4.59),
+                    25,
+                    *payment_frequency
+                )
+                .unwrap()
+                .round_dp(10),
+                dec!(430000),
+                "{:?}",
+                payment_frequency
+            );
+        }
+
+        assert_eq!(
+            max_principal(dec!(0), dec!(4.59), 25, PaymentFrequency::Monthly),
+            Err(MortgageError::InvalidPayment(dec!(0)))
+        );
+        assert!(max_principal(dec!(2500), dec!(101), 25, PaymentFrequency::Monthly).is_err());
+    }
+
+    #[test]
+    fn mortgage_qualifies_at_stress_tested_rate() {
+        let mortgage = CanadianMortgage::new(
+            dec!(430000),
+            dec!(4.59),
+            25,
+            PaymentFrequency::AcceleratedBiWeekly,
+        )
+        .unwrap();
+        let result = mortgage
+            .debt_service(&household(), &DebtServiceLimits::default())
+            .unwrap();
+
+        let monthly_qualifying_payment =
+            CanadianMortgage::new(dec!(430000), dec!(6.59), 25, PaymentFrequency::Monthly)
+                .unwrap()
+                .payment()
+                .unwrap();
+        assert_eq!(
+            result.gds,
+            (monthly_qualifying_payment + dec!(700)) / dec!(10000) * dec!(100)
+        );
+    }
+
+    #[test]
+    fn income_is_required() {
+        let mut household = household();
+        household.gross_annual_income = dec!(0);
+        assert_eq!(
+            debt_service(dec!(2500), &household, &DebtServiceLimits::default()),
+            Err(MortgageError::InvalidIncome(dec!(0)))
+        );
+    }
+}
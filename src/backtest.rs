@@ -0,0 +1,280 @@
+// What choosing fixed or variable would actually have cost, over rolling start dates in the past.
+// From each start, the fixed mortgage takes the fixed rate offered then and renews at the end of
+// every term at the rate offered on the renewal date. The variable mortgage floats at prime as it
+// was, plus its spread, as in VariableRateMortgage::replay. Both are compared over the same number
+// of payments.
+use crate::{CanadianMortgage, MortgageError, RateHistory, Result, VariableRateMortgage};
+use alloc::vec::Vec;
+use chrono::{Months, NaiveDate};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// Start dates from first_start to last_start, every step_months months, each held for
+// horizon_years. A step_months of 0 only starts on first_start.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Backtest {
+    pub first_start: NaiveDate,
+    pub last_start: NaiveDate,
+    pub step_months: u32,
+    pub horizon_years: u64,
+}
+
+// start is the date of the first payment. interest_savings is what the variable mortgage saved
+// over the fixed, negative when it cost more.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BacktestRun {
+    pub start: NaiveDate,
+    pub fixed_interest: Decimal,
+    pub variable_interest: Decimal,
+    pub interest_savings: Decimal,
+    pub fixed_balance: Decimal,
+    pub variable_balance: Decimal,
+}
+
+// Statistics of interest_savings across the runs. variable_cheaper is the fraction of runs in
+// which the variable mortgage cost less.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BacktestReport {
+    pub runs: Vec<BacktestRun>,
+    pub variable_cheaper: Decimal,
+    pub mean_savings: Decimal,
+    pub median_savings: Decimal,
+    pub min_savings: Decimal,
+    pub max_savings: Decimal,
+}
+
+impl Backtest {
+    // fixed_rates are the fixed rates offered for the fixed mortgage's term, and prime_rates the
+    // prime rate. The mortgages' own rates are replaced by the historical ones.
+    pub fn run(
+        &self,
+        fixed: &CanadianMortgage,
+        variable: &VariableRateMortgage,
+        fixed_rates: &RateHistory,
+        prime_rates: &RateHistory,
+    ) -> Result<BacktestReport> {
+        let mut runs = Vec::new();
+        let mut start = self.first_start;
+        while start <= self.last_start {
+            runs.push(self.run_from(start, fixed, variable, fixed_rates, prime_rates)?);
+            if self.step_months == 0 {
+                break;
+            }
+            start = match start.checked_add_months(Months::new(self.step_months)) {
+                Some(start) => start,
+                None => break,
+            };
+        }
+        if runs.is_empty() {
+            return Err(MortgageError::EmptyBacktest {
+                first_start: self.first_start,
+                last_start: self.last_start,
+            });
+        }
+
+        let mut savings: Vec<Decimal> = runs.iter().map(|run| run.interest_savings).collect();
+        savings.sort();
+        let n = Decimal::from(savings.len());
+        let middle = savings.len() / 2;
+        let median_savings = if savings.len().is_multiple_of(2) {
+            (savings[middle - 1] + savings[middle]) / dec!(2)
+        } else {
+            savings[middle]
+        };
+        let cheaper = savings.iter().filter(|saving| **saving > dec!(0)).count();
+
+        Ok(BacktestReport {
+            variable_cheaper: Decimal::from(cheaper) / n,
+            mean_savings: savings.iter().sum::<Decimal>() / n,
+            median_savings,
+            min_savings: savings[0],
+            max_savings: savings[savings.len() - 1],
+            runs,
+        })
+    }
+
+    fn run_from(
+        &self,
+        start: NaiveDate,
+        fixed: &CanadianMortgage,
+        variable: &VariableRateMortgage,
+        fixed_rates: &RateHistory,
+        prime_rates: &RateHistory,
+    ) -> Result<BacktestRun> {
+        let frequency = fixed.payment_frequency();
+        let payments = self
+            .horizon_years
+            .checked_mul(frequency.payments_per_year())
+            .ok_or(MortgageError::NumericOverflow("number of payments"))?;
+        let term_payments = fixed.term_payments();
+
+        let mut renewing = fixed.with_annual_rate(fixed_rates.rate_on(start)?)?;
+        let mut renewal = term_payments + 1;
+        while renewal <= payments {
+            let rate = fixed_rates.rate_on(frequency.payment_date(start, renewal)?)?;
+            renewing = renewing.apply_rate_change(renewal, rate)?;
+            renewal += term_payments;
+        }
+        let schedule = renewing.amortization_schedule()?;
+        let held = &schedule[..schedule.len().min(payments as usize)];
+        let fixed_interest: Decimal = held.iter().map(|row| row.interest).sum();
+        let fixed_balance = held.last().map_or(fixed.principal(), |row| row.balance);
+
+        let variable_payments = self
+            .horizon_years
+            .checked_mul(variable.payment_frequency().payments_per_year())
+            .ok_or(MortgageError::NumericOverflow("number of payments"))?;
+        let last_date = variable
+            .payment_frequency()
+            .payment_date(start, variable_payments)?;
+        let replay = variable.replay(prime_rates, start, last_date)?;
+
+        Ok(BacktestRun {
+            start,
+            fixed_interest,
+            variable_interest: replay.interest_paid,
+            interest_savings: fixed_interest - replay.interest_paid,
+            fixed_balance,
+            variable_balance: replay.balance,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HistoricalRate, PaymentFrequency};
+    use alloc::vec;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn history(rates: &[(NaiveDate, Decimal)]) -> RateHistory {
+        RateHistory::new(
+            rates
+                .iter()
+                .map(|&(date, rate)| HistoricalRate { date, rate })
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    fn mortgages() -> (CanadianMortgage, VariableRateMortgage) {
+        let fixed = CanadianMortgage::new(dec!(430000), dec!(5), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_term(5)
+            .unwrap();
+        let variable = VariableRateMortgage::new(
+            dec!(430000),
+            dec!(5),
+            dec!(-0.5),
+            25,
+            PaymentFrequency::Monthly,
+        )
+        .unwrap();
+        (fixed, variable)
+    }
+
+    #[test]
+    fn fixed_mortgage_renews_at_the_rate_then() {
+        let (fixed, variable) = mortgages();
+        let fixed_rates = history(&[(date(2010, 1, 1), dec!(4)), (date(2014, 12, 15), dec!(3))]);
+        let prime_rates = history(&[(date(2010, 1, 1), dec!(3))]);
+        let backtest = Backtest {
+            first_start: date(2010, 1, 1),
+            last_start: date(2010, 1, 1),
+            step_months: 12,
+            horizon_years: 10,
+        };
+        let report = backtest
+            .run(&fixed, &variable, &fixed_rates, &prime_rates)
+            .unwrap();
+
+        let renewed = fixed
+            .with_annual_rate(dec!(4))
+            .unwrap()
+            .apply_rate_change(61, dec!(3))
+            .unwrap()
+            .amortization_schedule()
+            .unwrap();
+        let run = report.runs[0];
+        assert_eq!(
+            run.fixed_interest,
+            renewed[..120]
+                .iter()
+                .map(|row| row.interest)
+                .sum::<Decimal>()
+        );
+        assert_eq!(run.fixed_balance, renewed[119].balance);
+        assert_eq!(
+            run.interest_savings,
+            run.fixed_interest - run.variable_interest
+        );
+        assert!(
+            run.variable_interest < run.fixed_interest,
+            "2.5% against 4% then 3%"
+        );
+    }
+
+    #[test]
+    fn statistics_are_across_rolling_starts() {
+        let (fixed, variable) = mortgages();
+        let fixed_rates = history(&[(date(2010, 1, 1), dec!(4))]);
+        let prime_rates = history(&[
+            (date(2010, 1, 1), dec!(3)),
+            (date(2012, 1, 1), dec!(6)),
+            (date(2014, 1, 1), dec!(3)),
+        ]);
+        let backtest = Backtest {
+            first_start: date(2010, 1, 1),
+            last_start: date(2014, 1, 1),
+            step_months: 12,
+            horizon_years: 2,
+        };
+        let report = backtest
+            .run(&fixed, &variable, &fixed_rates, &prime_rates)
+            .unwrap();
+
+        let starts: Vec<NaiveDate> = report.runs.iter().map(|run| run.start).collect();
+        assert_eq!(
+            starts,
+            vec![
+                date(2010, 1, 1),
+                date(2011, 1, 1),
+                date(2012, 1, 1),
+                date(2013, 1, 1),
+                date(2014, 1, 1),
+            ]
+        );
+        assert_eq!(report.variable_cheaper, dec!(0.4));
+        assert!(report.min_savings < dec!(0) && report.max_savings > dec!(0));
+        assert_eq!(report.min_savings, report.runs[2].interest_savings);
+        assert_eq!(report.median_savings, report.runs[1].interest_savings);
+
+        let forever = Backtest {
+            horizon_years: u64::MAX,
+            ..backtest
+        };
+        assert_eq!(
+            forever.run(&fixed, &variable, &fixed_rates, &prime_rates),
+            Err(MortgageError::NumericOverflow("number of payments"))
+        );
+
+        let backwards = Backtest {
+            first_start: date(2014, 1, 1),
+            last_start: date(2010, 1, 1),
+            ..backtest
+        };
+        assert_eq!(
+            backwards.run(&fixed, &variable, &fixed_rates, &prime_rates),
+            Err(MortgageError::EmptyBacktest {
+                first_start: date(2014, 1, 1),
+                last_start: date(2010, 1, 1),
+            })
+        );
+    }
+}
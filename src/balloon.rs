@@ -0,0 +1,65 @@
+// Private and vendor take-back mortgages often amortize the payments over a long period but
+// fall due much sooner, with the whole remaining balance paid as a balloon with the final payment.
+use crate::{CanadianMortgage, MortgageError, Result};
+
+impl CanadianMortgage {
+    // The balance falls due with payment number payment_number, which pays the interest for the
+    // period plus everything still owing.
+    pub fn with_balloon_payment(mut self, payment_number: u64) -> Result<CanadianMortgage> {
+        if payment_number == 0 {
+            return Err(MortgageError::InvalidPaymentNumber(payment_number));
+        }
+
+        self.balloon_payment = Some(payment_number);
+        Ok(self)
+    }
+
+    pub fn balloon_payment(&self) -> Option<u64> {
+        self.balloon_payment
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CanadianMortgage, PaymentFrequency};
+    use rust_decimal_macros::*;
+
+    fn mortgage() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(300000), dec!(7), 25, PaymentFrequency::Monthly).unwrap()
+    }
+
+    #[test]
+    fn balance_is_due_with_the_balloon() {
+        let balloon = mortgage().with_balloon_payment(36).unwrap();
+        let schedule = balloon.amortization_schedule().unwrap();
+        let payment = balloon.payment().unwrap();
+
+        assert_eq!(payment, mortgage().payment().unwrap());
+        assert_eq!(schedule.len(), 36);
+        assert_eq!(schedule[34].payment, payment);
+
+        let last = schedule.last().unwrap();
+        let owing = mortgage().balance_at(35).unwrap();
+        assert_eq!(last.principal.round_dp(10), owing.round_dp(10));
+        assert_eq!(last.payment, last.interest + last.principal);
+        assert_eq!(last.balance, dec!(0));
+        assert_eq!(balloon.balance_at(36).unwrap(), dec!(0));
+        assert_eq!(balloon.balance_at(35).unwrap(), schedule[34].balance);
+    }
+
+    #[test]
+    fn balloon_with_interest_only() {
+        let schedule = mortgage()
+            .with_term(5)
+            .unwrap()
+            .with_interest_only(true)
+            .with_balloon_payment(60)
+            .unwrap()
+            .amortization_schedule()
+            .unwrap();
+
+        assert_eq!(schedule.len(), 60);
+        assert_eq!(schedule[59].principal, dec!(300000));
+        assert!(mortgage().with_balloon_payment(0).is_err());
+    }
+}
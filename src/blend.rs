@@ -0,0 +1,203 @@
+// Instead of breaking a mortgage to get a lower rate or more money, many lenders will blend the
+// rate on the remaining term with their current rate for a new, longer term.
+use crate::{CanadianMortgage, MortgageError, Result};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// The cost over the new term of blending and extending versus breaking the mortgage and
+// starting over at the current rate. Interest is over the new term; savings is positive when
+// blending is cheaper. Breaking also means paying back any cash back, which blending doesn't.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlendAndExtend {
+    pub blended_rate: Decimal,
+    pub payment: Decimal,
+    pub blended_interest: Decimal,
+    pub break_penalty: Decimal,
+    pub clawback: Decimal,
+    pub break_interest: Decimal,
+    pub savings: Decimal,
+}
+
+// The rate weighted by dollars and time: the existing balance keeps old_rate for the
+// months_remaining in its term and gets new_rate for the rest of the new term, and any
+// new_money gets new_rate for the whole new term.
+pub fn blended_rate(
+    balance: Decimal,
+    old_rate: Decimal,
+    months_remaining: u64,
+    new_money: Decimal,
+    new_rate: Decimal,
+    new_term_months: u64,
+) -> Result<Decimal> {
+    crate::validate_rate(old_rate)?;
+    crate::validate_rate(new_rate)?;
+    if new_money < dec!(0) {
+        return Err(MortgageError::InvalidNewMoney(new_money));
+    }
+    if balance + new_money <= dec!(0) {
+        return Err(MortgageError::InvalidPrincipal(balance + new_money));
+    }
+    if new_term_months == 0 || new_term_months < months_remaining {
+        return Err(MortgageError::NewTermTooShort {
+            new_term_months,
+            months_remaining,
+        });
+    }
+
+    let months_remaining = crate::decimal_from_u64(months_remaining)?;
+    let new_term_months = crate::decimal_from_u64(new_term_months)?;
+
+    let old_money = balance * old_rate * months_remaining
+        + balance * new_rate * (new_term_months - months_remaining);
+    let new_money_interest = new_money * new_rate * new_term_months;
+
+    (old_money + new_money_interest)
+        .checked_div((balance + new_money) * new_term_months)
+        .ok_or(MortgageError::NumericOverflow("blended rate"))
+}
+
+impl CanadianMortgage {
+    // Blend and extend right after payment_number into a new term of new_term_years at the
+    // lender's current_rate, optionally borrowing new_money. The new mortgage keeps the
+    // remaining amortization in whole years.
+    pub fn blend_and_extend(
+        &self,
+        payment_number: u64,
+        current_rate: Decimal,
+        new_term_years: u64,
+        new_money: Decimal,
+    ) -> Result<BlendAndExtend> {
+        if payment_number > self.term_payments() {
+            return Err(MortgageError::BeyondTerm {
+                payment_number,
+                term_payments: self.term_payments(),
+            });
+        }
+        if new_money < dec!(0) {
+            return Err(MortgageError::InvalidNewMoney(new_money));
+        }
+
+        let months_elapsed = payment_number * 12 / self.payment_frequency.payments_per_year();
+        let months_remaining = (self.term_years * 12).saturating_sub(months_elapsed);
+        let amortization_remaining = self
+            .amortization_period
+            .checked_sub(months_elapsed / 12)
+            .ok_or(MortgageError::NumericOverflow("remaining amortization"))?;
+
+        let balance = self.balance_at(payment_number)?;
+        let blended_rate = blended_rate(
+            balance,
+            self.annual_rate,
+            months_remaining,
+            new_money,
+            current_rate,
+            new_term_years
+                .checked_mul(12)
+                .ok_or(MortgageError::NumericOverflow("new term"))?,
+        )?;
+
+        let renewed = |rate| {
+            CanadianMortgage::new(
+                balance + new_money,
+                rate,
+                amortization_remaining,
+                self.payment_frequency,
+            )?
+            .with_term(new_term_years)
+        };
+
+        let blended = renewed(blended_rate)?;
+        let blended_interest = blended.interest_paid_in_term()?;
+        let break_penalty = self.break_penalty(payment_number, current_rate)?;
+        let clawback = self.cash_back_clawback(payment_number)?;
+        let break_interest = renewed(current_rate)?.interest_paid_in_term()?;
+
+        Ok(BlendAndExtend {
+            blended_rate,
+            payment: blended.payment()?,
+            blended_interest,
+            break_penalty,
+            clawback,
+            break_interest,
+            savings: break_penalty + clawback + break_interest - blended_interest,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+
+    #[test]
+    fn rate_is_weighted_by_time_and_money() {
+        assert_eq!(
+            blended_rate(dec!(300000), dec!(5), 24, dec!(0), dec!(3), 60).unwrap(),
+            dec!(3.8)
+        );
+        assert_eq!(
+            blended_rate(dec!(300000), dec!(5), 24, dec!(100000), dec!(3), 60).unwrap(),
+            dec!(3.6)
+        );
+        assert!(blended_rate(dec!(300000), dec!(5), 24, dec!(0), dec!(3), 12).is_err());
+        assert_eq!(
+            blended_rate(dec!(0), dec!(5), 24, dec!(0), dec!(3), 60),
+            Err(MortgageError::InvalidPrincipal(dec!(0)))
+        );
+        assert_eq!(
+            blended_rate(dec!(300000), dec!(5), 24, dec!(-1), dec!(3), 60),
+            Err(MortgageError::InvalidNewMoney(dec!(-1)))
+        );
+    }
+
+    #[test]
+    fn blending_versus_breaking() {
+        let mortgage = CanadianMortgage::new(dec!(430000), dec!(5), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_term(5)
+            .unwrap();
+        let blend = mortgage.blend_and_extend(36, dec!(3), 5, dec!(0)).unwrap();
+
+        assert_eq!(blend.blended_rate.round_dp(10), dec!(3.8));
+        assert!(blend.blended_rate > dec!(3));
+        assert_eq!(
+            blend.break_penalty,
+            mortgage.break_penalty(36, dec!(3)).unwrap()
+        );
+        assert!(blend.break_interest < blend.blended_interest);
+        assert_eq!(
+            blend.savings,
+            blend.break_penalty + blend.break_interest - blend.blended_interest
+        );
+
+        let renewed = CanadianMortgage::new(
+            mortgage.balance_at(36).unwrap(),
+            dec!(3.8),
+            22,
+            PaymentFrequency::Monthly,
+        )
+        .unwrap();
+        assert_eq!(blend.payment, renewed.payment().unwrap());
+    }
+
+    #[test]
+    fn blending_is_within_the_term() {
+        let mortgage = CanadianMortgage::new(dec!(430000), dec!(5), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_term(5)
+            .unwrap();
+
+        assert_eq!(
+            mortgage.blend_and_extend(400, dec!(3), 5, dec!(0)),
+            Err(MortgageError::BeyondTerm {
+                payment_number: 400,
+                term_payments: 60,
+            })
+        );
+        assert_eq!(
+            mortgage.blend_and_extend(36, dec!(3), 5, dec!(-1000)),
+            Err(MortgageError::InvalidNewMoney(dec!(-1000)))
+        );
+    }
+}
@@ -0,0 +1,295 @@
+// A builder for mortgages with more options than fit comfortably in CanadianMortgage::new.
+// Every field is checked by build, and all the problems are reported together.
+use crate::{
+    insurance, parse, stress_test, CanadianMortgage, Compounding, DayCount, MortgageError,
+    MortgageType, PaymentFrequency, Result, RoundingPolicy,
+};
+use alloc::vec::Vec;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+#[derive(Debug, Clone)]
+pub struct MortgageBuilder {
+    // Text that couldn't be read is kept as the error, to be reported by build.
+    principal: Option<Result<Decimal>>,
+    interest_rate: Option<Result<Decimal>>,
+    amortization_period: u64,
+    term_years: Option<u64>,
+    payment_frequency: PaymentFrequency,
+    qualifying_benchmark: Decimal,
+    property_value: Option<Decimal>,
+    rounding: RoundingPolicy,
+    day_count: DayCount,
+    compounding: Compounding,
+    mortgage_type: MortgageType,
+    interest_only: bool,
+    balloon_payment: Option<u64>,
+}
+
+impl CanadianMortgage {
+    // Monthly payments over 25 years unless set otherwise. The principal and interest rate are required.
+    pub fn builder() -> MortgageBuilder {
+        MortgageBuilder {
+            principal: None,
+            interest_rate: None,
+            amortization_period: 25,
+            term_years: None,
+            payment_frequency: PaymentFrequency::Monthly,
+            qualifying_benchmark: stress_test::DEFAULT_BENCHMARK_RATE,
+            property_value: None,
+            rounding: RoundingPolicy::Unrounded,
+            day_count: DayCount::Actual365,
+            compounding: Compounding::SemiAnnual,
+            mortgage_type: MortgageType::Closed,
+            interest_only: false,
+            balloon_payment: None,
+        }
+    }
+}
+
+impl MortgageBuilder {
+    pub fn principal(mut self, principal: Decimal) -> MortgageBuilder {
+        self.principal = Some(Ok(principal));
+        self
+    }
+
+    // The principal as written, read by parse::money, e.g. "$430,000" or "430k".
+    pub fn principal_text(mut self, principal: &str) -> MortgageBuilder {
+        self.principal = Some(parse::money(principal));
+        self
+    }
+
+    // The annual interest rate as a percentage, compounded semi-annually.
+    pub fn interest_rate(mut self, interest_rate: Decimal) -> MortgageBuilder {
+        self.interest_rate = Some(Ok(interest_rate));
+        self
+    }
+
+    // The interest rate as written, read by parse::rate, e.g. "4.59%".
+    pub fn interest_rate_text(mut self, interest_rate: &str) -> MortgageBuilder {
+        self.interest_rate = Some(parse::rate(interest_rate));
+        self
+    }
+
+    pub fn amortization_period(mut self, years: u64) -> MortgageBuilder {
+        self.amortization_period = years;
+        self
+    }
+
+    pub fn term(mut self, years: u64) -> MortgageBuilder {
+        self.term_years = Some(years);
+        self
+    }
+
+    pub fn payment_frequency(mut self, payment_frequency: PaymentFrequency) -> MortgageBuilder {
+        self.payment_frequency = payment_frequency;
+        self
+    }
+
+    pub fn qualifying_benchmark(mut self, benchmark: Decimal) -> MortgageBuilder {
+        self.qualifying_benchmark = benchmark;
+        self
+    }
+
+    // Add default insurance for a property worth property_value; see with_default_insurance.
+    pub fn default_insurance(mut self, property_value: Decimal) -> MortgageBuilder {
+        self.property_value = Some(property_value);
+        self
+    }
+
+    pub fn rounding(mut self, rounding: RoundingPolicy) -> MortgageBuilder {
+        self.rounding = rounding;
+        self
+    }
+
+    pub fn day_count(mut self, day_count: DayCount) -> MortgageBuilder {
+        self.day_count = day_count;
+        self
+    }
+
+    pub fn compounding(mut self, compounding: Compounding) -> MortgageBuilder {
+        self.compounding = compounding;
+        self
+    }
+
+    pub fn mortgage_type(mut self, mortgage_type: MortgageType) -> MortgageBuilder {
+        self.mortgage_type = mortgage_type;
+        self
+    }
+
+    pub fn interest_only(mut self, interest_only: bool) -> MortgageBuilder {
+        self.interest_only = interest_only;
+        self
+    }
+
+    pub fn balloon_payment(mut self, payment_number: u64) -> MortgageBuilder {
+        self.balloon_payment = Some(payment_number);
+        self
+    }
+
+    // A single problem is returned as is; several are returned together as InvalidFields.
+    pub fn build(self) -> Result<CanadianMortgage> {
+        let mut errors = Vec::new();
+
+        match &self.principal {
+            None => errors.push(MortgageError::MissingField("principal")),
+            Some(Err(e)) => errors.push(e.clone()),
+            Some(Ok(principal)) if *principal <= dec!(0) => {
+                errors.push(MortgageError::InvalidPrincipal(*principal))
+            }
+            Some(Ok(_)) => {}
+        }
+        match &self.interest_rate {
+            None => errors.push(MortgageError::MissingField("interest_rate")),
+            Some(Err(e)) => errors.push(e.clone()),
+            Some(Ok(rate)) => errors.extend(crate::validate_rate(*rate).err()),
+        }
+        if self.amortization_period == 0 {
+            errors.push(MortgageError::InvalidAmortization(self.amortization_period));
+        }
+        match self.term_years {
+            Some(term) if term == 0 || term > self.amortization_period => {
+                errors.push(MortgageError::InvalidTerm {
+                    term,
+                    amortization: self.amortization_period,
+                })
+            }
+            _ => {}
+        }
+        errors.extend(crate::validate_rate(self.qualifying_benchmark).err());
+        if self.balloon_payment == Some(0) {
+            errors.push(MortgageError::InvalidPaymentNumber(0));
+        }
+        if let (Some(Ok(principal)), Some(property_value)) = (&self.principal, self.property_value)
+        {
+            let principal = *principal;
+            if principal > dec!(0) {
+                errors.extend(
+                    insurance::premium(principal, property_value, self.amortization_period).err(),
+                );
+            }
+        }
+
+        match errors.len() {
+            0 => {}
+            1 => return Err(errors.remove(0)),
+            _ => return Err(MortgageError::InvalidFields(errors)),
+        }
+
+        let mut mortgage = CanadianMortgage::new(
+            self.principal
+                .and_then(|principal| principal.ok())
+                .unwrap_or_default(),
+            self.interest_rate
+                .and_then(|rate| rate.ok())
+                .unwrap_or_default(),
+            self.amortization_period,
+            self.payment_frequency,
+        )?
+        .with_term(self.term_years.unwrap_or(self.amortization_period))?
+        .with_qualifying_benchmark(self.qualifying_benchmark)?
+        .with_rounding(self.rounding)
+        .with_day_count(self.day_count)
+        .with_compounding(self.compounding)?
+        .with_mortgage_type(self.mortgage_type)
+        .with_interest_only(self.interest_only);
+        if let Some(payment_number) = self.balloon_payment {
+            mortgage = mortgage.with_balloon_payment(payment_number)?;
+        }
+        if let Some(property_value) = self.property_value {
+            mortgage = mortgage.with_default_insurance(property_value)?;
+        }
+
+        Ok(mortgage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn builder_defaults_to_monthly_over_25_years() {
+        let built = CanadianMortgage::builder()
+            .principal(dec!(430000))
+            .interest_rate(dec!(4.59))
+            .build()
+            .unwrap();
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+
+        assert_eq!(built.payment().unwrap(), mortgage.payment().unwrap());
+        assert_eq!(built.term_years(), 25);
+    }
+
+    #[test]
+    fn builder_sets_options() {
+        let mortgage = CanadianMortgage::builder()
+            .principal(dec!(475000))
+            .interest_rate(dec!(4.59))
+            .amortization_period(30)
+            .term(5)
+            .payment_frequency(PaymentFrequency::AcceleratedBiWeekly)
+            .default_insurance(dec!(500000))
+            .rounding(RoundingPolicy::HalfEvenToCent)
+            .build()
+            .unwrap();
+
+        assert_eq!(mortgage.term_years(), 5);
+        assert_eq!(
+            mortgage.insurance_premium(),
+            dec!(475000) * dec!(4.20) / dec!(100)
+        );
+        assert_eq!(mortgage.rounding(), RoundingPolicy::HalfEvenToCent);
+    }
+
+    #[test]
+    fn every_invalid_field_is_reported() {
+        assert_eq!(
+            CanadianMortgage::builder()
+                .interest_rate(dec!(120))
+                .amortization_period(10)
+                .term(15)
+                .build()
+                .err(),
+            Some(MortgageError::InvalidFields(vec![
+                MortgageError::MissingField("principal"),
+                MortgageError::InvalidRate(dec!(120)),
+                MortgageError::InvalidTerm {
+                    term: 15,
+                    amortization: 10
+                },
+            ]))
+        );
+        assert_eq!(
+            CanadianMortgage::builder()
+                .principal(dec!(430000))
+                .interest_rate(dec!(4.59))
+                .amortization_period(0)
+                .build()
+                .err(),
+            Some(MortgageError::InvalidAmortization(0))
+        );
+    }
+
+    #[test]
+    fn builder_reads_text() {
+        let mortgage = CanadianMortgage::builder()
+            .principal_text("$430,000")
+            .interest_rate_text("4.59%")
+            .build()
+            .unwrap();
+        assert_eq!(mortgage.principal(), dec!(430000));
+        assert_eq!(mortgage.nominal_rate(), dec!(4.59));
+
+        assert_eq!(
+            CanadianMortgage::builder()
+                .principal_text("lots")
+                .interest_rate_text("4.59%")
+                .build()
+                .err(),
+            Some(MortgageError::UnparsableAmount("lots".into()))
+        );
+    }
+}
@@ -0,0 +1,279 @@
+// Payment dates for a schedule, starting from the date of the first payment.
+// Monthly payments fall on the same day each month, or the last day of shorter months.
+// Semi-monthly payments fall on the 1st and 15th. Weekly and biweekly payments fall on the same
+// day of the week as the first payment.
+use crate::{CanadianMortgage, MortgageError, PaymentFrequency, Result, ScheduleRow};
+use alloc::vec::Vec;
+use chrono::{Datelike, Days, Months, NaiveDate};
+use core::convert::TryFrom;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DatedScheduleRow {
+    pub date: NaiveDate,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub row: ScheduleRow,
+}
+
+// Lenders charge interest from the closing date to the interest adjustment date (IAD), one payment
+// period before the first regular payment, so that the first payment covers exactly one period.
+// The interest is simple interest at the annual rate, counting days by the mortgage's day count
+// convention, and is due at closing.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InterestAdjustment {
+    pub adjustment_date: NaiveDate,
+    pub days: u64,
+    pub per_diem: Decimal,
+    pub interest: Decimal,
+}
+
+impl PaymentFrequency {
+    // The date of payment_number, counting the first payment as 1.
+    pub fn payment_date(
+        &self,
+        first_payment_date: NaiveDate,
+        payment_number: u64,
+    ) -> Result<NaiveDate> {
+        if payment_number == 0 {
+            return Err(MortgageError::InvalidPaymentNumber(payment_number));
+        }
+        let n = payment_number - 1;
+
+        let date = match self {
+            PaymentFrequency::Monthly => u32::try_from(n)
+                .ok()
+                .and_then(|n| first_payment_date.checked_add_months(Months::new(n))),
+            PaymentFrequency::SemiMonthly => {
+                let starts_mid_month = match first_payment_date.day() {
+                    1 => false,
+                    15 => true,
+                    _ => return Err(MortgageError::InvalidPaymentDate(first_payment_date)),
+                };
+                let half_months = n + starts_mid_month as u64;
+                let first_of_month = first_payment_date.with_day(1);
+                u32::try_from(half_months / 2)
+                    .ok()
+                    .and_then(|months| first_of_month?.checked_add_months(Months::new(months)))
+                    .and_then(|date| {
+                        date.with_day(if half_months.is_multiple_of(2) { 1 } else { 15 })
+                    })
+            }
+            PaymentFrequency::BiWeekly | PaymentFrequency::AcceleratedBiWeekly => n
+                .checked_mul(14)
+                .and_then(|days| first_payment_date.checked_add_days(Days::new(days))),
+            PaymentFrequency::Weekly | PaymentFrequency::AcceleratedWeekly => n
+                .checked_mul(7)
+                .and_then(|days| first_payment_date.checked_add_days(Days::new(days))),
+        };
+
+        date.ok_or(MortgageError::NumericOverflow("payment date"))
+    }
+
+    // The date one payment period before first_payment_date.
+    pub(crate) fn period_before(&self, first_payment_date: NaiveDate) -> Result<NaiveDate> {
+        let date = match self {
+            PaymentFrequency::Monthly => first_payment_date.checked_sub_months(Months::new(1)),
+            PaymentFrequency::SemiMonthly => match first_payment_date.day() {
+                1 => first_payment_date
+                    .checked_sub_months(Months::new(1))
+                    .and_then(|date| date.with_day(15)),
+                15 => first_payment_date.with_day(1),
+                _ => return Err(MortgageError::InvalidPaymentDate(first_payment_date)),
+            },
+            PaymentFrequency::BiWeekly | PaymentFrequency::AcceleratedBiWeekly => {
+                first_payment_date.checked_sub_days(Days::new(14))
+            }
+            PaymentFrequency::Weekly | PaymentFrequency::AcceleratedWeekly => {
+                first_payment_date.checked_sub_days(Days::new(7))
+            }
+        };
+
+        date.ok_or(MortgageError::NumericOverflow("payment date"))
+    }
+}
+
+impl CanadianMortgage {
+    pub fn dated_schedule(&self, first_payment_date: NaiveDate) -> Result<Vec<DatedScheduleRow>> {
+        self.amortization_schedule()?
+            .into_iter()
+            .map(|row| {
+                Ok(DatedScheduleRow {
+                    date: self
+                        .payment_frequency
+                        .payment_date(first_payment_date, row.payment_number)?,
+                    row,
+                })
+            })
+            .collect()
+    }
+
+    // The interest owing at closing for the days between closing_date and the interest adjustment date.
+    pub fn interest_adjustment(
+        &self,
+        closing_date: NaiveDate,
+        first_payment_date: NaiveDate,
+    ) -> Result<InterestAdjustment> {
+        let adjustment_date = self.payment_frequency.period_before(first_payment_date)?;
+        let days = self.day_count.days(closing_date, adjustment_date);
+        if days < 0 {
+            return Err(MortgageError::InvalidClosingDate(closing_date));
+        }
+
+        let per_diem =
+            self.principal * self.annual_rate / dec!(100) / self.day_count.days_in_year();
+        Ok(InterestAdjustment {
+            adjustment_date,
+            days: days as u64,
+            per_diem,
+            interest: self.accrued_interest(self.principal, closing_date, adjustment_date),
+        })
+    }
+
+    // The date of the final payment.
+    pub fn payoff_date(&self, first_payment_date: NaiveDate) -> Result<NaiveDate> {
+        let number_of_payments = self.amortization_schedule()?.len() as u64;
+        self.payment_frequency
+            .payment_date(first_payment_date, number_of_payments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn monthly_dates_keep_the_day_or_use_month_end() {
+        let monthly = PaymentFrequency::Monthly;
+        let first = date(2024, 1, 31);
+        assert_eq!(monthly.payment_date(first, 1).unwrap(), first);
+        assert_eq!(monthly.payment_date(first, 2).unwrap(), date(2024, 2, 29));
+        assert_eq!(monthly.payment_date(first, 3).unwrap(), date(2024, 3, 31));
+        assert_eq!(monthly.payment_date(first, 14).unwrap(), date(2025, 2, 28));
+        assert!(monthly.payment_date(first, 0).is_err());
+    }
+
+    #[test]
+    fn semi_monthly_dates_alternate_between_1st_and_15th() {
+        let semi_monthly = PaymentFrequency::SemiMonthly;
+        let first = date(2024, 11, 15);
+        assert_eq!(
+            semi_monthly.payment_date(first, 2).unwrap(),
+            date(2024, 12, 1)
+        );
+        assert_eq!(
+            semi_monthly.payment_date(first, 3).unwrap(),
+            date(2024, 12, 15)
+        );
+        assert_eq!(
+            semi_monthly.payment_date(first, 4).unwrap(),
+            date(2025, 1, 1)
+        );
+        assert_eq!(
+            semi_monthly.payment_date(date(2024, 12, 1), 2).unwrap(),
+            date(2024, 12, 15)
+        );
+        assert_eq!(
+            semi_monthly.payment_date(date(2024, 12, 3), 1),
+            Err(MortgageError::InvalidPaymentDate(date(2024, 12, 3)))
+        );
+    }
+
+    #[test]
+    fn weekly_dates_keep_the_day_of_the_week() {
+        let first = date(2024, 1, 5);
+        let biweekly = PaymentFrequency::AcceleratedBiWeekly
+            .payment_date(first, 27)
+            .unwrap();
+        assert_eq!(biweekly, date(2025, 1, 3));
+        assert_eq!(biweekly.weekday(), first.weekday());
+        assert_eq!(
+            PaymentFrequency::Weekly.payment_date(first, 2).unwrap(),
+            date(2024, 1, 12)
+        );
+    }
+
+    #[test]
+    fn interest_is_adjusted_from_closing_to_one_period_before_first_payment() {
+        let mortgage =
+            CanadianMortgage::new(dec!(365000), dec!(5), 25, PaymentFrequency::Monthly).unwrap();
+        let adjustment = mortgage
+            .interest_adjustment(date(2024, 5, 17), date(2024, 7, 1))
+            .unwrap();
+
+        assert_eq!(adjustment.adjustment_date, date(2024, 6, 1));
+        assert_eq!(adjustment.days, 15);
+        assert_eq!(adjustment.per_diem, dec!(50));
+        assert_eq!(adjustment.interest, dec!(750));
+
+        let on_adjustment_date = mortgage
+            .interest_adjustment(date(2024, 6, 1), date(2024, 7, 1))
+            .unwrap();
+        assert_eq!(on_adjustment_date.interest, dec!(0));
+
+        assert_eq!(
+            mortgage.interest_adjustment(date(2024, 6, 10), date(2024, 7, 1)),
+            Err(MortgageError::InvalidClosingDate(date(2024, 6, 10)))
+        );
+
+        let thirty_360 = mortgage
+            .with_day_count(crate::DayCount::Thirty360)
+            .interest_adjustment(date(2024, 5, 17), date(2024, 7, 1))
+            .unwrap();
+        assert_eq!(thirty_360.days, 14);
+        assert_eq!(
+            thirty_360.interest,
+            dec!(365000) * dec!(0.05) * dec!(14) / dec!(360)
+        );
+    }
+
+    #[test]
+    fn adjustment_date_follows_payment_frequency() {
+        let first = date(2024, 7, 1);
+        assert_eq!(
+            PaymentFrequency::SemiMonthly.period_before(first).unwrap(),
+            date(2024, 6, 15)
+        );
+        assert_eq!(
+            PaymentFrequency::SemiMonthly
+                .period_before(date(2024, 7, 15))
+                .unwrap(),
+            first
+        );
+        assert_eq!(
+            PaymentFrequency::AcceleratedBiWeekly
+                .period_before(first)
+                .unwrap(),
+            date(2024, 6, 17)
+        );
+        assert_eq!(
+            PaymentFrequency::Weekly.period_before(first).unwrap(),
+            date(2024, 6, 24)
+        );
+    }
+
+    #[test]
+    fn schedule_rows_are_dated() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        let schedule = mortgage.dated_schedule(date(2024, 3, 1)).unwrap();
+
+        assert_eq!(schedule.len(), 300);
+        assert_eq!(schedule[0].date, date(2024, 3, 1));
+        assert_eq!(
+            schedule[0].row,
+            mortgage.amortization_schedule().unwrap()[0]
+        );
+        assert_eq!(schedule[299].date, date(2049, 2, 1));
+        assert_eq!(
+            mortgage.payoff_date(date(2024, 3, 1)).unwrap(),
+            date(2049, 2, 1)
+        );
+    }
+}
@@ -0,0 +1,95 @@
+// Some lenders pay cash back at closing, a percentage of the principal, usually in exchange for
+// a higher rate. Breaking the mortgage before the end of the term means paying it back, pro-rated
+// by the months left in the term, on top of the prepayment penalty.
+use crate::{CanadianMortgage, MortgageError, Result};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+impl CanadianMortgage {
+    // percentage of the principal is paid as cash back at closing.
+    pub fn with_cash_back(mut self, percentage: Decimal) -> Result<CanadianMortgage> {
+        if percentage <= dec!(0) || percentage > dec!(100) {
+            return Err(MortgageError::InvalidCashBack(percentage));
+        }
+        self.cash_back = Some(percentage);
+        Ok(self)
+    }
+
+    // The cash back paid at closing.
+    pub fn cash_back(&self) -> Decimal {
+        self.cash_back.map_or(dec!(0), |percentage| {
+            self.principal * percentage / dec!(100)
+        })
+    }
+
+    // The cash back owed if the mortgage is broken right after payment_number, in proportion to
+    // the whole months left in the term.
+    pub fn cash_back_clawback(&self, payment_number: u64) -> Result<Decimal> {
+        if payment_number > self.term_payments() {
+            return Err(MortgageError::BeyondTerm {
+                payment_number,
+                term_payments: self.term_payments(),
+            });
+        }
+
+        let term_months = self.term_years * 12;
+        let months_elapsed = payment_number * 12 / self.payment_frequency.payments_per_year();
+        let months_remaining = crate::decimal_from_u64(term_months - months_elapsed)?;
+        Ok(self.cash_back() * months_remaining / crate::decimal_from_u64(term_months)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+
+    fn mortgage() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(400000), dec!(5.29), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_term(5)
+            .unwrap()
+            .with_cash_back(dec!(3))
+            .unwrap()
+    }
+
+    #[test]
+    fn clawback_is_pro_rated() {
+        let mortgage = mortgage();
+        assert_eq!(mortgage.cash_back(), dec!(12000));
+        assert_eq!(mortgage.cash_back_clawback(0).unwrap(), dec!(12000));
+        assert_eq!(mortgage.cash_back_clawback(24).unwrap(), dec!(7200));
+        assert_eq!(mortgage.cash_back_clawback(60).unwrap(), dec!(0));
+        assert!(mortgage.cash_back_clawback(61).is_err());
+    }
+
+    #[test]
+    fn clawback_is_part_of_the_cost_of_breaking() {
+        let mortgage = mortgage();
+        assert_eq!(
+            mortgage.break_cost(24, dec!(3.29)).unwrap(),
+            mortgage.break_penalty(24, dec!(3.29)).unwrap() + dec!(7200)
+        );
+
+        let blend = mortgage
+            .blend_and_extend(24, dec!(3.29), 5, dec!(0))
+            .unwrap();
+        assert_eq!(blend.clawback, dec!(7200));
+        assert_eq!(
+            blend.savings,
+            blend.break_penalty + blend.clawback + blend.break_interest - blend.blended_interest
+        );
+    }
+
+    #[test]
+    fn cash_back_is_a_percentage() {
+        let mortgage =
+            CanadianMortgage::new(dec!(400000), dec!(5.29), 25, PaymentFrequency::Monthly).unwrap();
+        assert_eq!(mortgage.cash_back(), dec!(0));
+        assert_eq!(
+            mortgage.clone().with_cash_back(dec!(0)).unwrap_err(),
+            MortgageError::InvalidCashBack(dec!(0))
+        );
+        assert!(mortgage.with_cash_back(dec!(101)).is_err());
+    }
+}
@@ -0,0 +1,146 @@
+// Lender statements are kept in whole cents. A schedule in cents rounds every amount to the cent,
+// as the lender does, and carries the balance as a whole number of cents, so it reconciles
+// against the statements to the penny. Decimal is only used for the rate math.
+use crate::{CanadianMortgage, MortgageError, Result, RoundingPolicy};
+use alloc::vec::Vec;
+use core::fmt;
+use rust_decimal::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Cents(pub i64);
+
+impl Cents {
+    // amount rounded half to even to the cent.
+    pub fn from_decimal(amount: Decimal) -> Result<Cents> {
+        amount
+            .round_dp_with_strategy(2, RoundingStrategy::MidpointNearestEven)
+            .checked_mul(Decimal::ONE_HUNDRED)
+            .and_then(|cents| cents.to_i64())
+            .map(Cents)
+            .ok_or(MortgageError::NumericOverflow("cents"))
+    }
+
+    pub fn to_decimal(self) -> Decimal {
+        Decimal::new(self.0, 2)
+    }
+
+    pub fn checked_add(self, other: Cents) -> Result<Cents> {
+        self.0
+            .checked_add(other.0)
+            .map(Cents)
+            .ok_or(MortgageError::NumericOverflow("cents"))
+    }
+
+    pub fn checked_sub(self, other: Cents) -> Result<Cents> {
+        self.0
+            .checked_sub(other.0)
+            .map(Cents)
+            .ok_or(MortgageError::NumericOverflow("cents"))
+    }
+}
+
+// e.g. 2401.50
+impl fmt::Display for Cents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_decimal())
+    }
+}
+
+// A ScheduleRow in whole cents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CentsScheduleRow {
+    pub payment_number: u64,
+    pub payment: Cents,
+    pub interest: Cents,
+    pub principal: Cents,
+    pub prepayment: Cents,
+    pub balance: Cents,
+}
+
+impl CanadianMortgage {
+    // The amortization schedule in whole cents. The principal is rounded to the cent, and an
+    // unrounded mortgage rounds payments and interest half to even to the cent. The final
+    // payment pays off exactly the cents left owing.
+    pub fn cents_schedule(&self) -> Result<Vec<CentsScheduleRow>> {
+        let mut mortgage = self.clone();
+        if mortgage.rounding == RoundingPolicy::Unrounded {
+            mortgage.rounding = RoundingPolicy::HalfEvenToCent;
+        }
+        let mut balance = Cents::from_decimal(self.principal)?;
+        mortgage.principal = balance.to_decimal();
+
+        let schedule = mortgage.amortization_schedule()?;
+        let mut rows = Vec::with_capacity(schedule.len());
+        for row in schedule {
+            let interest = Cents::from_decimal(row.interest)?;
+            let prepayment = Cents::from_decimal(row.prepayment)?;
+            let principal = if row.balance == Decimal::ZERO {
+                balance.checked_sub(prepayment)?
+            } else {
+                Cents::from_decimal(row.principal)?
+            };
+            balance = balance.checked_sub(principal)?.checked_sub(prepayment)?;
+
+            rows.push(CentsScheduleRow {
+                payment_number: row.payment_number,
+                payment: interest.checked_add(principal)?,
+                interest,
+                principal,
+                prepayment,
+                balance,
+            });
+        }
+
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+    use alloc::string::ToString;
+    use rust_decimal_macros::*;
+
+    #[test]
+    fn amounts_are_whole_cents() {
+        assert_eq!(Cents::from_decimal(dec!(2401.495)).unwrap(), Cents(240150));
+        assert_eq!(Cents::from_decimal(dec!(2401.485)).unwrap(), Cents(240148));
+        assert_eq!(Cents::from_decimal(dec!(-0.015)).unwrap(), Cents(-2));
+        assert_eq!(Cents(240150).to_decimal(), dec!(2401.50));
+        assert_eq!(Cents(240150).to_string(), "2401.50");
+        assert!(Cents(i64::MAX).checked_add(Cents(1)).is_err());
+        assert!(Cents::from_decimal(Decimal::MAX).is_err());
+    }
+
+    #[test]
+    fn schedule_reconciles_to_the_penny() {
+        let mortgage = CanadianMortgage::new(
+            dec!(430000.004),
+            dec!(4.59),
+            25,
+            PaymentFrequency::AcceleratedWeekly,
+        )
+        .unwrap()
+        .with_lump_sum(52, dec!(10000))
+        .unwrap();
+        let schedule = mortgage.cents_schedule().unwrap();
+
+        assert_eq!(
+            schedule[0].payment,
+            Cents::from_decimal(mortgage.payment().unwrap()).unwrap()
+        );
+        assert_eq!(schedule[51].prepayment, Cents(1000000));
+        assert_eq!(schedule.last().unwrap().balance, Cents(0));
+
+        let mut balance = Cents(43000000);
+        for row in &schedule {
+            assert_eq!(row.payment.0, row.interest.0 + row.principal.0);
+            balance = Cents(balance.0 - row.principal.0 - row.prepayment.0);
+            assert_eq!(row.balance, balance);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+// Series for charting a mortgage, as (x, y) points in f64 for plotting libraries. x is the time in
+// years from the start of the mortgage. Long schedules are downsampled to every nth payment,
+// always keeping the last one, so a chart of weekly payments doesn't draw 1,300 points.
+use crate::{CanadianMortgage, Result, ScheduleRow};
+use alloc::vec;
+use alloc::vec::Vec;
+use rust_decimal::prelude::*;
+
+// balance, cumulative_interest and cumulative_principal start from the point at year zero.
+// cumulative_principal includes prepayments. interest and principal are the composition of
+// each sampled payment, so they add up to the payment.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChartSeries {
+    pub balance: Vec<(f64, f64)>,
+    pub cumulative_interest: Vec<(f64, f64)>,
+    pub cumulative_principal: Vec<(f64, f64)>,
+    pub interest: Vec<(f64, f64)>,
+    pub principal: Vec<(f64, f64)>,
+}
+
+impl CanadianMortgage {
+    // At most max_points sampled payments per series, and at least one.
+    pub fn chart_series(&self, max_points: usize) -> Result<ChartSeries> {
+        let schedule = self.amortization_schedule()?;
+        let payments_per_year = self.payment_frequency.payments_per_year() as f64;
+        Ok(ChartSeries::from_schedule(
+            &schedule,
+            self.principal,
+            payments_per_year,
+            max_points,
+        ))
+    }
+}
+
+impl ChartSeries {
+    fn from_schedule(
+        schedule: &[ScheduleRow],
+        principal: Decimal,
+        payments_per_year: f64,
+        max_points: usize,
+    ) -> ChartSeries {
+        let step = schedule.len().div_ceil(max_points.max(1)).max(1);
+        let mut series = ChartSeries {
+            balance: vec![(0.0, to_f64(principal))],
+            cumulative_interest: vec![(0.0, 0.0)],
+            cumulative_principal: vec![(0.0, 0.0)],
+            ..ChartSeries::default()
+        };
+
+        let mut cumulative_interest = Decimal::ZERO;
+        let mut cumulative_principal = Decimal::ZERO;
+        for (i, row) in schedule.iter().enumerate() {
+            cumulative_interest += row.interest;
+            cumulative_principal += row.principal + row.prepayment;
+            let sampled = (schedule.len() - 1 - i).is_multiple_of(step);
+            if !sampled {
+                continue;
+            }
+
+            let x = row.payment_number as f64 / payments_per_year;
+            series.balance.push((x, to_f64(row.balance)));
+            series
+                .cumulative_interest
+                .push((x, to_f64(cumulative_interest)));
+            series
+                .cumulative_principal
+                .push((x, to_f64(cumulative_principal)));
+            series.interest.push((x, to_f64(row.interest)));
+            series.principal.push((x, to_f64(row.principal)));
+        }
+
+        series
+    }
+}
+
+fn to_f64(amount: Decimal) -> f64 {
+    amount.to_f64().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+    use rust_decimal_macros::*;
+
+    #[test]
+    fn series_are_downsampled() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Weekly).unwrap();
+        let series = mortgage.chart_series(100).unwrap();
+
+        assert!(series.interest.len() <= 100);
+        assert_eq!(series.balance.len(), series.interest.len() + 1);
+        assert_eq!(series.balance[0], (0.0, 430000.0));
+        assert_eq!(series.balance.last().unwrap().1, 0.0);
+        assert!(
+            (series.balance.last().unwrap().0 - 25.0).abs() < 0.5,
+            "ends with the last payment"
+        );
+        assert!((series.cumulative_principal.last().unwrap().1 - 430000.0).abs() < 0.01);
+        assert!(series
+            .balance
+            .windows(2)
+            .all(|points| points[1].0 > points[0].0 && points[1].1 < points[0].1));
+    }
+
+    #[test]
+    fn composition_adds_up_to_the_payment() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        let series = mortgage.chart_series(1000).unwrap();
+        let payment = mortgage.payment().unwrap().to_f64().unwrap();
+
+        assert_eq!(series.interest.len(), 300, "short schedules aren't sampled");
+        assert_eq!(series.interest[0].0, 1.0 / 12.0);
+        assert!((series.interest[0].1 + series.principal[0].1 - payment).abs() < 1e-9);
+        assert!(series.interest[0].1 > series.principal[0].1);
+        assert!(series.interest[299].1 < series.principal[299].1);
+    }
+}
@@ -0,0 +1,91 @@
+// The cash a buyer needs on closing day, on top of the mortgage: the down payment, land transfer tax,
+// and provincial sales tax on any default insurance premium. The premium itself is added to the
+// mortgage, but the tax on it can't be and must be paid at closing. Legal fees, title insurance and
+// adjustments vary too much to include.
+use crate::insurance::{self, Insurer, MortgageClass};
+use crate::{land_transfer_tax, Province, Result};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClosingCosts {
+    pub down_payment: Decimal,
+    pub land_transfer_tax: Decimal,
+    pub insurance_premium: Decimal,
+    pub premium_sales_tax: Decimal,
+    pub cash_to_close: Decimal,
+}
+
+// The costs of buying a home in province for purchase_price with down_payment, financing the rest
+// over amortization_period years. High-ratio mortgages are insured by insurer.
+pub fn closing_costs(
+    province: Province,
+    purchase_price: Decimal,
+    down_payment: Decimal,
+    amortization_period: u64,
+    insurer: Insurer,
+) -> Result<ClosingCosts> {
+    let loan_to_value = insurance::purchase_loan_to_value(purchase_price, down_payment)?;
+    let insurance_premium = if MortgageClass::from_loan_to_value(loan_to_value).requires_insurance()
+    {
+        insurer.premium(
+            purchase_price - down_payment,
+            purchase_price,
+            amortization_period,
+        )?
+    } else {
+        dec!(0)
+    };
+    let land_transfer_tax = land_transfer_tax::land_transfer_tax(province, purchase_price)?;
+    let premium_sales_tax = insurance::premium_sales_tax(province, insurance_premium);
+
+    Ok(ClosingCosts {
+        down_payment,
+        land_transfer_tax,
+        insurance_premium,
+        premium_sales_tax,
+        cash_to_close: down_payment + land_transfer_tax + premium_sales_tax,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sales_tax_on_the_premium_is_due_at_closing() {
+        let costs = closing_costs(
+            Province::Ontario,
+            dec!(500000),
+            dec!(25000),
+            25,
+            Insurer::Cmhc,
+        )
+        .unwrap();
+
+        assert_eq!(costs.insurance_premium, dec!(19000));
+        assert_eq!(costs.premium_sales_tax, dec!(1520));
+        assert_eq!(costs.land_transfer_tax, dec!(6475));
+        assert_eq!(
+            costs.cash_to_close,
+            dec!(25000) + dec!(6475) + dec!(1520),
+            "the premium is financed"
+        );
+    }
+
+    #[test]
+    fn conventional_mortgages_are_not_insured() {
+        let costs = closing_costs(
+            Province::Alberta,
+            dec!(500000),
+            dec!(100000),
+            25,
+            Insurer::Cmhc,
+        )
+        .unwrap();
+
+        assert_eq!(costs.insurance_premium, dec!(0));
+        assert_eq!(costs.cash_to_close, dec!(100000));
+    }
+}
@@ -0,0 +1,180 @@
+// Any number of scenarios side by side, for "option A vs option B". The first scenario is the
+// baseline the others are measured against, year by year.
+use crate::payoff::PayoffPeriod;
+use crate::{PaymentFrequency, Result, Scenario, ScheduleRow};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "chrono")]
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScenarioComparison {
+    pub outcomes: Vec<ScenarioOutcome>,
+}
+
+// payment is the regular payment at the start of the mortgage. deltas has a year for every
+// year until the last of the scenarios is paid off.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScenarioOutcome {
+    pub name: String,
+    pub payment: Decimal,
+    pub payment_frequency: PaymentFrequency,
+    pub total_interest: Decimal,
+    pub balance_at_term_end: Decimal,
+    pub payoff: PayoffPeriod,
+    pub deltas: Vec<YearDelta>,
+}
+
+// This scenario's interest and principal paid in the year, and balance at the end of it, minus
+// the baseline's. Principal includes prepayments, and a paid off mortgage pays nothing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct YearDelta {
+    pub year: u64,
+    pub interest: Decimal,
+    pub principal: Decimal,
+    pub balance: Decimal,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct YearTotals {
+    interest: Decimal,
+    principal: Decimal,
+    balance: Decimal,
+}
+
+pub fn compare(scenarios: &[Scenario]) -> Result<ScenarioComparison> {
+    let mut outcomes = vec![];
+    let mut years = vec![];
+    for scenario in scenarios {
+        let mortgage = &scenario.mortgage;
+        let schedule = mortgage.amortization_schedule()?;
+        let summary = mortgage.summarize(&schedule);
+        outcomes.push(ScenarioOutcome {
+            name: scenario.name.clone(),
+            payment: mortgage.payment()?,
+            payment_frequency: mortgage.payment_frequency(),
+            total_interest: summary.total_interest,
+            balance_at_term_end: mortgage.balance_at_term_end()?,
+            payoff: PayoffPeriod::new(summary.number_of_payments, mortgage.payment_frequency())?,
+            deltas: vec![],
+        });
+        years.push(yearly(&schedule, mortgage.payment_frequency()));
+    }
+
+    let number_of_years = years.iter().map(Vec::len).max().unwrap_or_default();
+    let paid_off = YearTotals::default();
+    for (outcome, totals) in outcomes.iter_mut().zip(&years) {
+        outcome.deltas = (0..number_of_years)
+            .map(|i| {
+                let year = totals.get(i).unwrap_or(&paid_off);
+                let baseline = years[0].get(i).unwrap_or(&paid_off);
+                YearDelta {
+                    year: i as u64 + 1,
+                    interest: year.interest - baseline.interest,
+                    principal: year.principal - baseline.principal,
+                    balance: year.balance - baseline.balance,
+                }
+            })
+            .collect();
+    }
+
+    Ok(ScenarioComparison { outcomes })
+}
+
+fn yearly(schedule: &[ScheduleRow], payment_frequency: PaymentFrequency) -> Vec<YearTotals> {
+    let payments_per_year = payment_frequency.payments_per_year() as usize;
+    schedule
+        .chunks(payments_per_year)
+        .map(|rows| YearTotals {
+            interest: rows.iter().map(|row| row.interest).sum(),
+            principal: rows.iter().map(|row| row.principal + row.prepayment).sum(),
+            balance: rows.last().map(|row| row.balance).unwrap_or_default(),
+        })
+        .collect()
+}
+
+impl ScenarioOutcome {
+    #[cfg(feature = "chrono")]
+    pub fn payoff_date(&self, first_payment_date: NaiveDate) -> Result<NaiveDate> {
+        self.payment_frequency
+            .payment_date(first_payment_date, self.payoff.number_of_payments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CanadianMortgage, PaymentFrequency};
+    use rust_decimal_macros::*;
+
+    fn scenario(name: &str, mortgage: CanadianMortgage) -> Scenario {
+        Scenario {
+            name: name.into(),
+            mortgage,
+        }
+    }
+
+    #[test]
+    fn scenarios_are_compared_with_the_first() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly)
+                .unwrap()
+                .with_term(5)
+                .unwrap();
+        let lump_sum = mortgage.clone().with_lump_sum(12, dec!(20000)).unwrap();
+        let shorter =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 20, PaymentFrequency::Weekly).unwrap();
+        let comparison = compare(&[
+            scenario("as is", mortgage.clone()),
+            scenario("lump sum", lump_sum.clone()),
+            scenario("20 years, weekly", shorter),
+        ])
+        .unwrap();
+        let outcomes = &comparison.outcomes;
+
+        assert_eq!(outcomes[0].name, "as is");
+        assert_eq!(outcomes[0].payment, mortgage.payment().unwrap());
+        assert_eq!(
+            outcomes[0].balance_at_term_end,
+            mortgage.balance_at_term_end().unwrap()
+        );
+        assert_eq!(outcomes[0].payoff.years, 25);
+        assert!(outcomes[0]
+            .deltas
+            .iter()
+            .all(|delta| delta.interest.is_zero() && delta.balance.is_zero()));
+        assert_eq!(outcomes[0].deltas.len(), 25);
+
+        let first_year = outcomes[1].deltas[0];
+        assert_eq!(first_year.principal, dec!(20000));
+        assert_eq!(first_year.balance, dec!(-20000));
+        assert!(outcomes[1].deltas[1].interest < dec!(0));
+        assert!(outcomes[1].total_interest < outcomes[0].total_interest);
+        let last_year = outcomes[1].deltas[24];
+        assert_eq!(last_year.balance, dec!(0), "both paid off");
+        assert!(last_year.principal < dec!(0), "the lump sum paid off early");
+
+        assert_eq!(outcomes[2].payoff.years, 20);
+        assert_eq!(outcomes[2].payment_frequency, PaymentFrequency::Weekly);
+        assert!(outcomes[2].deltas[22].principal < dec!(0));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn payoff_dates_follow_the_payment_frequency() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        let comparison = compare(&[scenario("as is", mortgage)]).unwrap();
+        let first_payment_date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+
+        assert_eq!(
+            comparison.outcomes[0].payoff_date(first_payment_date),
+            Ok(NaiveDate::from_ymd_opt(2049, 12, 1).unwrap())
+        );
+    }
+}
@@ -0,0 +1,94 @@
+// How often interest is compounded on the quoted annual rate. Canadian law requires fixed rate
+// mortgages to be quoted compounded semi-annually (or annually); most variable rate mortgages
+// compound monthly, and lines of credit compound daily.
+use crate::{CanadianMortgage, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Compounding {
+    #[default]
+    SemiAnnual,
+    Monthly,
+    Daily,
+}
+
+impl Compounding {
+    pub fn periods_per_year(&self) -> u64 {
+        match self {
+            Compounding::SemiAnnual => 2,
+            Compounding::Monthly => 12,
+            Compounding::Daily => 365,
+        }
+    }
+}
+
+impl CanadianMortgage {
+    // Semi-annual unless set otherwise. The quoted rate stays the same; the rate charged changes.
+    pub fn with_compounding(mut self, compounding: Compounding) -> Result<CanadianMortgage> {
+        self.interest_rate = crate::rates::monthly_compounded_rate(self.annual_rate, compounding)?;
+        self.compounding = compounding;
+        Ok(self)
+    }
+
+    pub fn compounding(&self) -> Compounding {
+        self.compounding
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PaymentFrequency, VariableRateMortgage};
+    use rust_decimal_macros::*;
+
+    fn mortgage(compounding: Compounding) -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(5.55), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_compounding(compounding)
+            .unwrap()
+    }
+
+    #[test]
+    fn more_frequent_compounding_costs_more() {
+        let semi_annual = mortgage(Compounding::SemiAnnual).payment().unwrap();
+        let monthly = mortgage(Compounding::Monthly).payment().unwrap();
+        let daily = mortgage(Compounding::Daily).payment().unwrap();
+
+        assert_eq!(
+            semi_annual,
+            CanadianMortgage::new(dec!(430000), dec!(5.55), 25, PaymentFrequency::Monthly)
+                .unwrap()
+                .payment()
+                .unwrap()
+        );
+        assert!(semi_annual < monthly);
+        assert!(monthly < daily);
+    }
+
+    #[test]
+    fn monthly_compounding_matches_variable_rate_mortgage() {
+        let variable = VariableRateMortgage::new(
+            dec!(430000),
+            dec!(6.45),
+            dec!(-0.90),
+            25,
+            PaymentFrequency::Monthly,
+        )
+        .unwrap();
+        assert_eq!(
+            mortgage(Compounding::Monthly).payment().unwrap(),
+            variable.payment().unwrap()
+        );
+    }
+
+    #[test]
+    fn effective_rate_follows_compounding() {
+        assert_eq!(
+            mortgage(Compounding::Monthly)
+                .effective_rate()
+                .unwrap()
+                .round_dp(6),
+            dec!(5.693377)
+        );
+    }
+}
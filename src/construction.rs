@@ -0,0 +1,220 @@
+// A construction, or progress-draw, mortgage advances the principal in draws as a new home is built.
+// Until the home is complete only interest is paid, on what has been drawn so far, charged as
+// simple interest by the mortgage's day count from the date of each draw. Once it is complete the
+// mortgage converts to regular payments that amortize everything drawn.
+use crate::{CanadianMortgage, DatedScheduleRow, MortgageError, Result, ScheduleRow};
+use alloc::vec::Vec;
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// An advance of amount on date.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Draw {
+    pub date: NaiveDate,
+    pub amount: Decimal,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstructionMortgage {
+    mortgage: CanadianMortgage,
+    draws: Vec<Draw>,
+}
+
+impl ConstructionMortgage {
+    // mortgage is the mortgage approved once the home is complete. Its principal is the most
+    // that can be drawn, and its rate, amortization and payment frequency apply throughout.
+    pub fn new(mortgage: CanadianMortgage) -> ConstructionMortgage {
+        ConstructionMortgage {
+            mortgage,
+            draws: Vec::new(),
+        }
+    }
+
+    pub fn with_draw(mut self, date: NaiveDate, amount: Decimal) -> Result<ConstructionMortgage> {
+        if amount <= dec!(0) {
+            return Err(MortgageError::InvalidTransaction { date, amount });
+        }
+        let drawn = self.drawn() + amount;
+        if drawn > self.mortgage.principal {
+            return Err(MortgageError::DrawsExceedPrincipal {
+                drawn,
+                principal: self.mortgage.principal,
+            });
+        }
+
+        self.draws.push(Draw { date, amount });
+        Ok(self)
+    }
+
+    pub fn draws(&self) -> &[Draw] {
+        &self.draws
+    }
+
+    // The total advanced so far.
+    pub fn drawn(&self) -> Decimal {
+        self.draws.iter().map(|draw| draw.amount).sum()
+    }
+
+    // Interest-only payments from first_payment_date up to and including the first payment on or
+    // after completion_date, then regular payments amortizing the total drawn. Every draw must be
+    // made by the last interest-only payment. Draws made before the period of the first payment
+    // are charged interest from the start of that period.
+    pub fn schedule(
+        &self,
+        first_payment_date: NaiveDate,
+        completion_date: NaiveDate,
+    ) -> Result<Vec<DatedScheduleRow>> {
+        let frequency = self.mortgage.payment_frequency;
+
+        let mut rows = Vec::new();
+        let mut period_start = frequency.period_before(first_payment_date)?;
+        let mut payment_number = 0;
+        loop {
+            payment_number += 1;
+            let date = frequency.payment_date(first_payment_date, payment_number)?;
+            let interest: Decimal = self
+                .draws
+                .iter()
+                .filter(|draw| draw.date < date)
+                .map(|draw| {
+                    self.mortgage
+                        .accrued_interest(draw.amount, draw.date.max(period_start), date)
+                })
+                .sum();
+            let balance: Decimal = self
+                .draws
+                .iter()
+                .filter(|draw| draw.date <= date)
+                .map(|draw| draw.amount)
+                .sum();
+
+            rows.push(DatedScheduleRow {
+                date,
+                row: ScheduleRow {
+                    payment_number,
+                    payment: interest,
+                    interest,
+                    principal: dec!(0),
+                    prepayment: dec!(0),
+                    balance,
+                },
+            });
+            period_start = date;
+            if date >= completion_date {
+                break;
+            }
+        }
+
+        if let Some(draw) = self.draws.iter().find(|draw| draw.date > period_start) {
+            return Err(MortgageError::DrawAfterCompletion(draw.date));
+        }
+        let drawn = self.drawn();
+        if drawn == dec!(0) {
+            return Ok(rows);
+        }
+
+        let mut mortgage = self.mortgage.without_prepayments();
+        mortgage.principal = drawn;
+        let interest_only_payments = payment_number;
+        for mut row in mortgage.amortization_schedule()? {
+            row.payment_number += interest_only_payments;
+            rows.push(DatedScheduleRow {
+                date: frequency.payment_date(first_payment_date, row.payment_number)?,
+                row,
+            });
+        }
+
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn construction() -> ConstructionMortgage {
+        let mortgage =
+            CanadianMortgage::new(dec!(500000), dec!(5), 25, PaymentFrequency::Monthly).unwrap();
+        ConstructionMortgage::new(mortgage)
+            .with_draw(date(2024, 1, 1), dec!(200000))
+            .unwrap()
+            .with_draw(date(2024, 3, 16), dec!(150000))
+            .unwrap()
+            .with_draw(date(2024, 6, 1), dec!(150000))
+            .unwrap()
+    }
+
+    #[test]
+    fn interest_only_on_what_is_drawn() {
+        let schedule = construction()
+            .schedule(date(2024, 2, 1), date(2024, 6, 15))
+            .unwrap();
+
+        let first = &schedule[0].row;
+        assert_eq!(first.balance, dec!(200000));
+        assert_eq!(first.principal, dec!(0));
+        assert_eq!(
+            first.interest,
+            dec!(200000) * dec!(0.05) * dec!(31) / dec!(365)
+        );
+
+        let march = &schedule[2].row;
+        assert_eq!(schedule[2].date, date(2024, 4, 1));
+        assert_eq!(march.balance, dec!(350000));
+        assert_eq!(
+            march.interest.round_dp(10),
+            (dec!(200000) * dec!(0.05) * dec!(31) / dec!(365)
+                + dec!(150000) * dec!(0.05) * dec!(16) / dec!(365))
+            .round_dp(10),
+            "the March draw is charged from the day it was made"
+        );
+    }
+
+    #[test]
+    fn converts_to_amortizing_payments_after_completion() {
+        let construction = construction();
+        let schedule = construction
+            .schedule(date(2024, 2, 1), date(2024, 6, 15))
+            .unwrap();
+
+        let last_interest_only = &schedule[5];
+        assert_eq!(last_interest_only.date, date(2024, 7, 1));
+        assert_eq!(last_interest_only.row.principal, dec!(0));
+
+        let first_regular = &schedule[6];
+        assert_eq!(first_regular.date, date(2024, 8, 1));
+        assert_eq!(first_regular.row.payment_number, 7);
+        assert_eq!(
+            first_regular.row.payment,
+            CanadianMortgage::new(dec!(500000), dec!(5), 25, PaymentFrequency::Monthly)
+                .unwrap()
+                .payment()
+                .unwrap()
+        );
+        assert_eq!(schedule.len(), 6 + 300);
+        assert_eq!(schedule.last().unwrap().row.balance, dec!(0));
+    }
+
+    #[test]
+    fn draws_are_checked() {
+        assert!(construction()
+            .with_draw(date(2024, 7, 1), dec!(0.01))
+            .is_err());
+        let late = CanadianMortgage::new(dec!(500000), dec!(5), 25, PaymentFrequency::Monthly)
+            .map(ConstructionMortgage::new)
+            .unwrap()
+            .with_draw(date(2024, 9, 1), dec!(100000))
+            .unwrap();
+        assert_eq!(
+            late.schedule(date(2024, 2, 1), date(2024, 6, 15)),
+            Err(MortgageError::DrawAfterCompletion(date(2024, 9, 1)))
+        );
+    }
+}
@@ -0,0 +1,138 @@
+// Most variable rate mortgages are convertible: the borrower can lock in a fixed rate at any time
+// without a penalty. The balance at conversion is amortized at the fixed rate, compounded
+// semi-annually, with the payment recalculated for what is left of the amortization.
+use crate::schedule::PAID_OFF_TOLERANCE;
+use crate::{
+    CanadianMortgage, MortgageError, PrimeRateChange, Result, ScheduleRow, VariableRateMortgage,
+};
+use alloc::vec::Vec;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+impl VariableRateMortgage {
+    // The schedule with prime changing as given, converted to fixed_rate, an annual percentage,
+    // right after payment_number. Prime rate changes after the conversion don't apply.
+    // It is an error to convert once the mortgage is paid off.
+    pub fn convert_to_fixed(
+        &self,
+        prime_rate_changes: &[PrimeRateChange],
+        payment_number: u64,
+        fixed_rate: Decimal,
+    ) -> Result<Vec<ScheduleRow>> {
+        let mut rows = self.amortization_schedule(prime_rate_changes)?;
+        if payment_number >= rows.len() as u64 {
+            return Err(MortgageError::PaidOff {
+                payment_number,
+                payments: rows.len() as u64,
+            });
+        }
+        rows.truncate(payment_number as usize);
+
+        let mut balance = rows.last().map_or(self.principal(), |row| row.balance);
+        let fixed = CanadianMortgage::new(
+            balance,
+            fixed_rate,
+            self.amortization_period(),
+            self.payment_frequency(),
+        )?;
+        let rate = fixed.periodic_rate()?;
+        let payment = fixed.recalculated_payment(
+            balance,
+            fixed.interest_rate,
+            fixed.amortization_period,
+            payment_number,
+        )?;
+
+        let mut payment_number = payment_number;
+        while balance > dec!(0) {
+            payment_number += 1;
+            let interest = balance * rate;
+            let (payment_made, principal) = if balance + interest - payment < PAID_OFF_TOLERANCE {
+                (balance + interest, balance)
+            } else {
+                (payment, payment - interest)
+            };
+            balance -= principal;
+
+            rows.push(ScheduleRow {
+                payment_number,
+                payment: payment_made,
+                interest,
+                principal,
+                prepayment: dec!(0),
+                balance,
+            });
+        }
+
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+
+    fn mortgage() -> VariableRateMortgage {
+        VariableRateMortgage::new(
+            dec!(430000),
+            dec!(6.45),
+            dec!(-0.90),
+            25,
+            PaymentFrequency::Monthly,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn schedules_are_spliced_at_the_conversion() {
+        let changes = [PrimeRateChange {
+            payment_number: 7,
+            prime_rate: dec!(6.95),
+        }];
+        let variable = mortgage().amortization_schedule(&changes).unwrap();
+        let converted = mortgage()
+            .convert_to_fixed(&changes, 24, dec!(4.79))
+            .unwrap();
+
+        assert_eq!(converted[..24], variable[..24]);
+        let balance = variable[23].balance;
+        let fixed =
+            CanadianMortgage::new(balance, dec!(4.79), 25, PaymentFrequency::Monthly).unwrap();
+        assert_eq!(
+            converted[24].interest,
+            balance * fixed.periodic_rate().unwrap()
+        );
+        assert_eq!(converted[24].payment_number, 25);
+
+        let remaining =
+            CanadianMortgage::new(balance, dec!(4.79), 23, PaymentFrequency::Monthly).unwrap();
+        assert_eq!(
+            converted[24].payment.round_dp(10),
+            remaining.payment().unwrap().round_dp(10),
+            "amortized over what is left"
+        );
+        assert_eq!(converted.len(), 300);
+        assert_eq!(converted.last().unwrap().balance, dec!(0));
+    }
+
+    #[test]
+    fn converting_before_the_first_payment() {
+        let converted = mortgage().convert_to_fixed(&[], 0, dec!(4.79)).unwrap();
+        let fixed =
+            CanadianMortgage::new(dec!(430000), dec!(4.79), 25, PaymentFrequency::Monthly).unwrap();
+        assert_eq!(converted, fixed.amortization_schedule().unwrap());
+    }
+
+    #[test]
+    fn can_only_convert_what_is_owing() {
+        assert_eq!(
+            mortgage().convert_to_fixed(&[], 300, dec!(4.79)),
+            Err(MortgageError::PaidOff {
+                payment_number: 300,
+                payments: 300
+            })
+        );
+        assert!(mortgage().convert_to_fixed(&[], 12, dec!(-1)).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+// Federally regulated lenders must disclose the cost of borrowing over the term of a mortgage:
+// the interest plus any non-interest charges, and the annual percentage rate (APR) it works out to.
+// The APR is C / (T * P) * 100, where C is the cost of borrowing, T is the term in years and P is
+// the average principal outstanding over the term.
+// https://laws-lois.justice.gc.ca/eng/regulations/SOR-2001-101/page-1.html
+use crate::{CanadianMortgage, PaymentFrequency, Result};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// Charges paid to the lender, or to a third party when the lender requires it, that count towards
+// the cost of borrowing. Insurance premiums and legal fees the borrower chose don't count.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BorrowingCharges {
+    pub lender_fees: Decimal,
+    pub appraisal: Decimal,
+    pub other: Decimal,
+}
+
+impl BorrowingCharges {
+    pub fn total(&self) -> Decimal {
+        self.lender_fees + self.appraisal + self.other
+    }
+}
+
+// The figures a lender must disclose before a mortgage is signed, all over the term.
+// interest_rate is the nominal annual rate as a percentage, as quoted.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CostOfBorrowingDisclosure {
+    pub principal: Decimal,
+    pub interest_rate: Decimal,
+    pub apr: Decimal,
+    pub term_years: u64,
+    pub amortization_period: u64,
+    pub payment_frequency: PaymentFrequency,
+    pub payment: Decimal,
+    pub number_of_payments: u64,
+    pub total_payments: Decimal,
+    pub total_interest: Decimal,
+    pub non_interest_charges: Decimal,
+    pub total_cost_of_borrowing: Decimal,
+    pub balance_at_term_end: Decimal,
+}
+
+impl CanadianMortgage {
+    pub fn cost_of_borrowing_disclosure(
+        &self,
+        charges: &BorrowingCharges,
+    ) -> Result<CostOfBorrowingDisclosure> {
+        let schedule = self.amortization_schedule()?;
+        let term = &schedule[..schedule.len().min(self.term_payments() as usize)];
+        let total_interest: Decimal = term.iter().map(|row| row.interest).sum();
+
+        Ok(CostOfBorrowingDisclosure {
+            principal: self.principal,
+            interest_rate: self.annual_rate,
+            apr: self.apr(charges)?,
+            term_years: self.term_years,
+            amortization_period: self.amortization_period,
+            payment_frequency: self.payment_frequency,
+            payment: self.payment()?,
+            number_of_payments: term.len() as u64,
+            total_payments: term.iter().map(|row| row.payment + row.prepayment).sum(),
+            total_interest,
+            non_interest_charges: charges.total(),
+            total_cost_of_borrowing: total_interest + charges.total(),
+            balance_at_term_end: self.balance_at_term_end()?,
+        })
+    }
+
+    // The interest paid over the term plus charges.
+    pub fn cost_of_borrowing(&self, charges: &BorrowingCharges) -> Result<Decimal> {
+        Ok(self.interest_paid_in_term()? + charges.total())
+    }
+
+    // The annual percentage rate as a percentage. The principal outstanding in each payment period
+    // of the term is the balance at its start.
+    pub fn apr(&self, charges: &BorrowingCharges) -> Result<Decimal> {
+        let cost = self.interest_paid_in_term()? + charges.total();
+        self.annualized_over_term(cost)
+    }
+
+    // cost as an annual percentage of the average principal outstanding over the term.
+    pub(crate) fn annualized_over_term(&self, cost: Decimal) -> Result<Decimal> {
+        let schedule = self.amortization_schedule()?;
+        let term = &schedule[..schedule.len().min(self.term_payments() as usize)];
+        let outstanding: Decimal = term
+            .iter()
+            .map(|row| row.balance + row.principal + row.prepayment)
+            .sum();
+        let average_principal = outstanding / crate::decimal_from_u64(term.len() as u64)?;
+        Ok(cost / (crate::decimal_from_u64(self.term_years)? * average_principal) * dec!(100))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+
+    fn mortgage() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_term(5)
+            .unwrap()
+    }
+
+    #[test]
+    fn apr_without_charges_is_the_rate_paid() {
+        let apr = mortgage().apr(&BorrowingCharges::default()).unwrap();
+        assert_eq!(
+            apr.round_dp(10),
+            (mortgage().interest_rate * dec!(100)).round_dp(10),
+            "the rate compounded monthly, not semi-annually"
+        );
+    }
+
+    #[test]
+    fn disclosure_covers_the_term() {
+        let charges = BorrowingCharges {
+            lender_fees: dec!(500),
+            ..BorrowingCharges::default()
+        };
+        let mortgage = mortgage();
+        let disclosure = mortgage.cost_of_borrowing_disclosure(&charges).unwrap();
+
+        assert_eq!(disclosure.principal, dec!(430000));
+        assert_eq!(disclosure.term_years, 5);
+        assert_eq!(disclosure.amortization_period, 25);
+        assert_eq!(disclosure.number_of_payments, 60);
+        assert_eq!(
+            disclosure.total_interest,
+            mortgage.interest_paid_in_term().unwrap()
+        );
+        assert_eq!(
+            disclosure.total_cost_of_borrowing,
+            mortgage.cost_of_borrowing(&charges).unwrap()
+        );
+        assert_eq!(disclosure.apr, mortgage.apr(&charges).unwrap());
+        assert_eq!(
+            disclosure.balance_at_term_end,
+            mortgage.balance_at_term_end().unwrap()
+        );
+        assert_eq!(
+            (disclosure.total_payments - disclosure.total_interest).round_dp(10),
+            (dec!(430000) - disclosure.balance_at_term_end).round_dp(10)
+        );
+    }
+
+    #[test]
+    fn charges_raise_the_apr() {
+        let charges = BorrowingCharges {
+            lender_fees: dec!(1000),
+            appraisal: dec!(400),
+            other: dec!(100),
+        };
+        let mortgage = mortgage();
+
+        assert_eq!(
+            mortgage.cost_of_borrowing(&charges).unwrap(),
+            mortgage.interest_paid_in_term().unwrap() + dec!(1500)
+        );
+        let apr = mortgage.apr(&charges).unwrap();
+        assert!(apr > mortgage.apr(&BorrowingCharges::default()).unwrap());
+        assert_eq!(apr.round_dp(2), dec!(4.62));
+    }
+}
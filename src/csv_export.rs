@@ -0,0 +1,122 @@
+// CSV export for spreadsheets. Amounts are rounded to the given number of decimal places.
+use crate::{MortgageError, Result, ScheduleRow, Summary};
+use rust_decimal::Decimal;
+
+pub trait ToCsv {
+    fn to_csv(&self, precision: u32) -> Result<String>;
+}
+
+impl ToCsv for [ScheduleRow] {
+    fn to_csv(&self, precision: u32) -> Result<String> {
+        let mut writer = csv::Writer::from_writer(vec![]);
+        writer
+            .write_record([
+                "payment_number",
+                "payment",
+                "interest",
+                "principal",
+                "prepayment",
+                "balance",
+            ])
+            .map_err(csv_error)?;
+
+        for row in self {
+            writer
+                .write_record([
+                    row.payment_number.to_string(),
+                    format_amount(row.payment, precision),
+                    format_amount(row.interest, precision),
+                    format_amount(row.principal, precision),
+                    format_amount(row.prepayment, precision),
+                    format_amount(row.balance, precision),
+                ])
+                .map_err(csv_error)?;
+        }
+
+        into_string(writer)
+    }
+}
+
+impl ToCsv for Summary {
+    fn to_csv(&self, precision: u32) -> Result<String> {
+        let mut writer = csv::Writer::from_writer(vec![]);
+        writer
+            .write_record([
+                "number_of_payments",
+                "total_payments",
+                "total_interest",
+                "interest_to_principal",
+                "capitalized_interest",
+                "premium_interest",
+                "interest_only",
+            ])
+            .map_err(csv_error)?;
+        writer
+            .write_record([
+                self.number_of_payments.to_string(),
+                format_amount(self.total_payments, precision),
+                format_amount(self.total_interest, precision),
+                format_amount(self.interest_to_principal, precision),
+                format_amount(self.capitalized_interest, precision),
+                format_amount(self.premium_interest, precision),
+                self.interest_only.to_string(),
+            ])
+            .map_err(csv_error)?;
+
+        into_string(writer)
+    }
+}
+
+fn format_amount(amount: Decimal, precision: u32) -> String {
+    format!("{:.*}", precision as usize, amount.round_dp(precision))
+}
+
+fn into_string(writer: csv::Writer<Vec<u8>>) -> Result<String> {
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| MortgageError::Serialization(e.to_string()))?;
+    String::from_utf8(bytes).map_err(|e| MortgageError::Serialization(e.to_string()))
+}
+
+fn csv_error(e: csv::Error) -> MortgageError {
+    MortgageError::Serialization(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CanadianMortgage, PaymentFrequency};
+    use rust_decimal_macros::*;
+
+    fn mortgage() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap()
+    }
+
+    #[test]
+    fn schedule_to_csv() {
+        let csv = mortgage()
+            .amortization_schedule()
+            .unwrap()
+            .to_csv(2)
+            .unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 301);
+        assert_eq!(
+            lines[0],
+            "payment_number,payment,interest,principal,prepayment,balance"
+        );
+        assert_eq!(lines[1], "1,2401.50,1629.24,772.26,0.00,429227.74");
+        assert!(lines[300].ends_with(",0.00"));
+    }
+
+    #[test]
+    fn summary_to_csv() {
+        let csv = mortgage().summary().unwrap().to_csv(0).unwrap();
+        assert_eq!(
+            csv,
+            "number_of_payments,total_payments,total_interest,interest_to_principal,capitalized_interest,premium_interest,interest_only\n\
+             300,720449,290449,1,0,0,false\n"
+        );
+    }
+}
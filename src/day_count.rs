@@ -0,0 +1,159 @@
+// How interest accrues between two dates. Actual/365 is the usual convention for Canadian
+// mortgages; some lenders and products count 360 days a year, or 30 days in every month.
+use crate::CanadianMortgage;
+#[cfg(feature = "chrono")]
+use crate::{MortgageError, Result};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+#[cfg(feature = "chrono")]
+use chrono::{Datelike, NaiveDate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DayCount {
+    #[default]
+    Actual365,
+    Actual360,
+    // 30/360 bond basis: every month has 30 days, and a 31st is treated as the 30th.
+    Thirty360,
+}
+
+impl DayCount {
+    pub fn days_in_year(&self) -> Decimal {
+        match self {
+            DayCount::Actual365 => dec!(365),
+            DayCount::Actual360 | DayCount::Thirty360 => dec!(360),
+        }
+    }
+
+    // The number of days from start to end, negative if end is before start.
+    #[cfg(feature = "chrono")]
+    pub fn days(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        match self {
+            DayCount::Actual365 | DayCount::Actual360 => {
+                end.signed_duration_since(start).num_days()
+            }
+            DayCount::Thirty360 => {
+                let start_day = start.day().min(30);
+                let end_day = if end.day() == 31 && start_day == 30 {
+                    30
+                } else {
+                    end.day()
+                };
+
+                360 * i64::from(end.year() - start.year())
+                    + 30 * (i64::from(end.month()) - i64::from(start.month()))
+                    + (i64::from(end_day) - i64::from(start_day))
+            }
+        }
+    }
+
+    // The fraction of a year from start to end.
+    #[cfg(feature = "chrono")]
+    pub fn year_fraction(&self, start: NaiveDate, end: NaiveDate) -> Decimal {
+        Decimal::from(self.days(start, end)) / self.days_in_year()
+    }
+}
+
+impl CanadianMortgage {
+    // Actual/365 unless set otherwise.
+    pub fn with_day_count(mut self, day_count: DayCount) -> CanadianMortgage {
+        self.day_count = day_count;
+        self
+    }
+
+    pub fn day_count(&self) -> DayCount {
+        self.day_count
+    }
+
+    // Simple interest on balance at the annual rate from start to end, by the day count convention.
+    // Use it for payout amounts, closing adjustments and interest on arrears.
+    #[cfg(feature = "chrono")]
+    pub fn accrued_interest(&self, balance: Decimal, start: NaiveDate, end: NaiveDate) -> Decimal {
+        let days = Decimal::from(self.day_count.days(start, end));
+        balance * self.annual_rate / dec!(100) * days / self.day_count.days_in_year()
+    }
+
+    // The interest on balance for the day as_of_date, up to the next day.
+    // Under 30/360 that is nothing on the 30th of a 31 day month, and three days on
+    // February 28th in a common year.
+    #[cfg(feature = "chrono")]
+    pub fn per_diem_interest(&self, balance: Decimal, as_of_date: NaiveDate) -> Result<Decimal> {
+        let next_day = as_of_date
+            .succ_opt()
+            .ok_or(MortgageError::NumericOverflow("per diem date"))?;
+        Ok(self.accrued_interest(balance, as_of_date, next_day))
+    }
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn days_between_dates() {
+        let start = date(2024, 1, 31);
+        let end = date(2024, 3, 31);
+
+        assert_eq!(DayCount::Actual365.days(start, end), 60);
+        assert_eq!(DayCount::Actual360.days(start, end), 60);
+        assert_eq!(DayCount::Thirty360.days(start, end), 60);
+        assert_eq!(
+            DayCount::Thirty360.days(date(2024, 2, 15), date(2024, 3, 31)),
+            46
+        );
+        assert_eq!(DayCount::Thirty360.days(end, start), -60);
+    }
+
+    #[test]
+    fn per_diem_follows_day_count() {
+        let mortgage =
+            CanadianMortgage::new(dec!(365000), dec!(5), 25, crate::PaymentFrequency::Monthly)
+                .unwrap();
+        assert_eq!(
+            mortgage
+                .per_diem_interest(dec!(365000), date(2024, 3, 31))
+                .unwrap(),
+            dec!(50)
+        );
+        assert_eq!(
+            mortgage.accrued_interest(dec!(365000), date(2024, 3, 1), date(2024, 4, 1)),
+            dec!(1550)
+        );
+
+        let thirty_360 = mortgage.with_day_count(DayCount::Thirty360);
+        assert_eq!(
+            thirty_360
+                .per_diem_interest(dec!(360000), date(2024, 3, 30))
+                .unwrap(),
+            dec!(0)
+        );
+        assert_eq!(
+            thirty_360
+                .per_diem_interest(dec!(360000), date(2023, 2, 28))
+                .unwrap(),
+            dec!(150)
+        );
+    }
+
+    #[test]
+    fn year_fractions() {
+        let start = date(2024, 1, 1);
+        let end = date(2025, 1, 1);
+
+        assert_eq!(
+            DayCount::Actual365.year_fraction(start, end),
+            dec!(366) / dec!(365)
+        );
+        assert_eq!(
+            DayCount::Actual360.year_fraction(start, end),
+            dec!(366) / dec!(360)
+        );
+        assert_eq!(DayCount::Thirty360.year_fraction(start, end), dec!(1));
+    }
+}
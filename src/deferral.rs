@@ -0,0 +1,170 @@
+// A payment deferral skips several payments in a row, as lenders offered during the pandemic.
+// Interest keeps compounding on the capitalized interest while payments are deferred.
+// Afterwards either the payment stays the same and the amortization is extended, or the
+// payment is recalculated to pay off the larger balance over the original amortization.
+use crate::{CanadianMortgage, MortgageError, Result};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeferralOutcome {
+    ExtendAmortization,
+    RecalculatePayment,
+}
+
+// payments payments are deferred, starting with first_payment_number.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PaymentDeferral {
+    pub first_payment_number: u64,
+    pub payments: u64,
+    pub outcome: DeferralOutcome,
+}
+
+impl PaymentDeferral {
+    pub(crate) fn defers(&self, payment_number: u64) -> bool {
+        payment_number >= self.first_payment_number
+            && payment_number < self.first_payment_number + self.payments
+    }
+}
+
+// The effect of a deferral compared to making every payment. payment is the regular payment
+// once payments resume.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeferralImpact {
+    pub capitalized_interest: Decimal,
+    pub payment: Decimal,
+    pub payments_added: u64,
+    pub extra_interest: Decimal,
+}
+
+impl CanadianMortgage {
+    pub fn with_payment_deferral(
+        mut self,
+        first_payment_number: u64,
+        payments: u64,
+        outcome: DeferralOutcome,
+    ) -> Result<CanadianMortgage> {
+        if first_payment_number == 0 {
+            return Err(MortgageError::InvalidPaymentNumber(first_payment_number));
+        }
+        if payments == 0 {
+            return Err(MortgageError::InvalidPaymentNumber(payments));
+        }
+
+        self.deferral = Some(PaymentDeferral {
+            first_payment_number,
+            payments,
+            outcome,
+        });
+        Ok(self)
+    }
+
+    pub fn payment_deferral(&self) -> Option<PaymentDeferral> {
+        self.deferral
+    }
+
+    pub fn deferral_impact(&self) -> Result<DeferralImpact> {
+        let mut baseline = self.clone();
+        baseline.deferral = None;
+        let baseline_schedule = baseline.amortization_schedule()?;
+        let schedule = self.amortization_schedule()?;
+
+        let (capitalized_interest, resumed) = match self.deferral {
+            Some(deferral) => (
+                schedule
+                    .iter()
+                    .filter(|row| deferral.defers(row.payment_number))
+                    .map(|row| row.interest)
+                    .sum(),
+                deferral.first_payment_number + deferral.payments,
+            ),
+            None => (dec!(0), 1),
+        };
+        let total_interest =
+            |rows: &[crate::ScheduleRow]| -> Decimal { rows.iter().map(|row| row.interest).sum() };
+
+        Ok(DeferralImpact {
+            capitalized_interest,
+            payment: schedule
+                .get(resumed as usize - 1)
+                .map_or(dec!(0), |row| row.payment),
+            payments_added: (schedule.len() as u64).saturating_sub(baseline_schedule.len() as u64),
+            extra_interest: total_interest(&schedule) - total_interest(&baseline_schedule),
+        })
+    }
+
+    // The payment that pays off balance at interest_rate, a decimal fraction compounded monthly,
+    // over what is left of amortization_period years once payments_made payments have been made.
+    pub(crate) fn recalculated_payment(
+        &self,
+        balance: Decimal,
+        interest_rate: Decimal,
+        amortization_period: u64,
+        payments_made: u64,
+    ) -> Result<Decimal> {
+        let months_elapsed = payments_made * 12 / self.payment_frequency.payments_per_year();
+        let months_remaining = (amortization_period * 12)
+            .saturating_sub(months_elapsed)
+            .max(1);
+
+        let monthly_payment =
+            crate::mortgage_payment(balance, interest_rate / dec!(12), months_remaining)?;
+        Ok(self.rounding.round_payment(
+            self.payment_frequency
+                .scale_monthly_payment(monthly_payment),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+
+    fn mortgage() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap()
+    }
+
+    #[test]
+    fn deferred_interest_compounds() {
+        let deferred = mortgage()
+            .with_payment_deferral(13, 6, DeferralOutcome::ExtendAmortization)
+            .unwrap();
+        let schedule = deferred.amortization_schedule().unwrap();
+
+        assert!(schedule[12..18].iter().all(|row| row.payment == dec!(0)));
+        assert!(
+            schedule[17].interest > schedule[12].interest,
+            "interest on interest"
+        );
+
+        let impact = deferred.deferral_impact().unwrap();
+        assert_eq!(
+            impact.capitalized_interest,
+            schedule[12..18].iter().map(|row| row.interest).sum()
+        );
+        assert_eq!(impact.payment, mortgage().payment().unwrap());
+        assert!(impact.payments_added > 6);
+        assert!(impact.extra_interest > impact.capitalized_interest);
+    }
+
+    #[test]
+    fn recalculated_payment_keeps_the_amortization() {
+        let deferred = mortgage()
+            .with_payment_deferral(13, 6, DeferralOutcome::RecalculatePayment)
+            .unwrap();
+        let schedule = deferred.amortization_schedule().unwrap();
+        let impact = deferred.deferral_impact().unwrap();
+
+        assert_eq!(schedule.len(), 300, "paid off on the original date");
+        assert!(impact.payment > mortgage().payment().unwrap());
+        assert_eq!(schedule[18].payment, impact.payment);
+        assert_eq!(schedule.last().unwrap().balance, dec!(0));
+        assert!(mortgage()
+            .with_payment_deferral(13, 0, DeferralOutcome::RecalculatePayment)
+            .is_err());
+    }
+}
@@ -0,0 +1,216 @@
+use crate::Province;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Error), uniffi(flat_error))]
+pub enum MortgageError {
+    #[error("interest rate of {0}% must be between 0% and 100%")]
+    InvalidRate(Decimal),
+
+    #[error("rate floor of {floor}% is above the rate cap of {cap}%")]
+    RateFloorAboveCap { floor: Decimal, cap: Decimal },
+
+    #[error("interest must be compounded at least once a year, not {0} times")]
+    InvalidCompoundingFrequency(u64),
+
+    #[error("unknown payment frequency: {0}")]
+    UnknownPaymentFrequency(String),
+
+    #[error("unknown locale: {0}")]
+    UnknownLocale(String),
+
+    #[error("unknown column: {0}")]
+    UnknownColumn(String),
+
+    #[error("amortization period of {0} years must be at least 1 year")]
+    InvalidAmortization(u64),
+
+    #[error("term of {term} years must be between 1 year and the amortization period of {amortization} years")]
+    InvalidTerm { term: u64, amortization: u64 },
+
+    #[error("recast over {recast} years must be at most the amortization period of {amortization} years")]
+    RecastBeyondAmortization { recast: u64, amortization: u64 },
+
+    #[error("introductory period of {months} months must be between 1 month and the term of {term_months} months")]
+    InvalidIntroductoryPeriod { months: u64, term_months: u64 },
+
+    #[error("new term of {new_term_months} months must be at least the {months_remaining} months remaining in the current term")]
+    NewTermTooShort {
+        new_term_months: u64,
+        months_remaining: u64,
+    },
+
+    #[error("principal of {0} must be greater than zero")]
+    InvalidPrincipal(Decimal),
+
+    #[error("payment of {0} must be greater than zero")]
+    InvalidPayment(Decimal),
+
+    #[error("new money of {0} must not be negative")]
+    InvalidNewMoney(Decimal),
+
+    #[error("property value of {0} must be greater than zero")]
+    InvalidPropertyValue(Decimal),
+
+    #[error("trigger threshold of {0}% must be at least 100% of the original principal")]
+    InvalidTriggerThreshold(Decimal),
+
+    #[error("cash back of {0}% must be greater than zero and at most 100%")]
+    InvalidCashBack(Decimal),
+
+    #[error("down payment of {0} must be between zero and the purchase price")]
+    InvalidDownPayment(Decimal),
+
+    #[error("loan-to-value of {0}% is above the maximum insurable 95%")]
+    Uninsurable(Decimal),
+
+    #[error("borrowing of {amount} is over the readvanceable limit of {limit}")]
+    ReadvanceLimitExceeded { amount: Decimal, limit: Decimal },
+
+    #[error("reverse mortgage advance of {amount} is over the limit of {limit}")]
+    ReverseMortgageLimitExceeded { amount: Decimal, limit: Decimal },
+
+    #[error("gross income of {0} must be greater than zero")]
+    InvalidIncome(Decimal),
+
+    #[error("{0:?} is not supported")]
+    UnsupportedProvince(Province),
+
+    #[error("prepayment of {amount} on payment {payment_number} must be positive and made with a scheduled payment")]
+    InvalidPrepayment {
+        payment_number: u64,
+        amount: Decimal,
+    },
+
+    #[error("lump sums of {amount} in year {year} exceed the prepayment privilege of {limit}")]
+    LumpSumLimitExceeded {
+        year: u64,
+        amount: Decimal,
+        limit: Decimal,
+    },
+
+    #[error("payment increases of {percentage}% in year {year} exceed the prepayment privilege of {limit}%")]
+    PaymentIncreaseLimitExceeded {
+        year: u64,
+        percentage: Decimal,
+        limit: Decimal,
+    },
+
+    #[error(
+        "payment {payment_number} is after the last of the {term_payments} payments in the term"
+    )]
+    BeyondTerm {
+        payment_number: u64,
+        term_payments: u64,
+    },
+
+    #[error("the mortgage is paid off by payment {payments}, so nothing is owing after payment {payment_number}")]
+    PaidOff { payment_number: u64, payments: u64 },
+
+    #[error("payment of {payment} is less than the required payment of {minimum}")]
+    PaymentBelowMinimum { payment: Decimal, minimum: Decimal },
+
+    #[error("payment of {payment} does not cover the interest of {interest}")]
+    PaymentBelowInterest { payment: Decimal, interest: Decimal },
+
+    #[error("payment number {0} must be at least 1")]
+    InvalidPaymentNumber(u64),
+
+    #[cfg(feature = "chrono")]
+    #[error("semi-monthly payments must start on the 1st or 15th, not {0}")]
+    InvalidPaymentDate(chrono::NaiveDate),
+
+    #[cfg(feature = "chrono")]
+    #[error("no rate on {0}, before the rate history starts")]
+    NoRateOn(chrono::NaiveDate),
+
+    #[cfg(feature = "chrono")]
+    #[error("no start dates from {first_start} to {last_start}")]
+    EmptyBacktest {
+        first_start: chrono::NaiveDate,
+        last_start: chrono::NaiveDate,
+    },
+
+    #[cfg(feature = "chrono")]
+    #[error("more than one rate on {0}")]
+    DuplicateRateDate(chrono::NaiveDate),
+
+    #[cfg(feature = "chrono")]
+    #[error("rate hold expired on {0}")]
+    RateHoldExpired(chrono::NaiveDate),
+
+    #[cfg(feature = "chrono")]
+    #[error("closing date {0} is after the interest adjustment date")]
+    InvalidClosingDate(chrono::NaiveDate),
+
+    #[cfg(feature = "chrono")]
+    #[error("transaction of {amount} on {date} must be greater than zero")]
+    InvalidTransaction {
+        date: chrono::NaiveDate,
+        amount: Decimal,
+    },
+
+    #[cfg(feature = "chrono")]
+    #[error("balance of {balance} on {date} is over the credit limit of {limit}")]
+    CreditLimitExceeded {
+        date: chrono::NaiveDate,
+        balance: Decimal,
+        limit: Decimal,
+    },
+
+    #[error("draws of {drawn} are more than the principal of {principal}")]
+    DrawsExceedPrincipal { drawn: Decimal, principal: Decimal },
+
+    #[cfg(feature = "chrono")]
+    #[error("draw on {0} is after the mortgage converts to regular payments")]
+    DrawAfterCompletion(chrono::NaiveDate),
+
+    #[error("payment frequency {found} does not match {expected}")]
+    MismatchedPaymentFrequency {
+        expected: crate::PaymentFrequency,
+        found: crate::PaymentFrequency,
+    },
+
+    #[error("could not read {0:?} as an amount of money")]
+    UnparsableAmount(String),
+
+    #[error("could not read {0:?} as an interest rate")]
+    UnparsableRate(String),
+
+    #[error("could not read {0:?} as a date")]
+    UnparsableDate(String),
+
+    #[error("numeric overflow while computing {0}")]
+    NumericOverflow(&'static str),
+
+    #[error("invalid scenario file: {0}")]
+    InvalidScenarioFile(String),
+
+    #[error("could not fetch rates: {0}")]
+    RateUnavailable(String),
+
+    #[error("could not serialize: {0}")]
+    Serialization(String),
+
+    #[error("could not convert {0}")]
+    ConversionFailure(String),
+
+    #[error("{0} is required")]
+    MissingField(&'static str),
+
+    #[error("{}", join(.0))]
+    InvalidFields(Vec<MortgageError>),
+}
+
+fn join(errors: &[MortgageError]) -> String {
+    errors
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+pub type Result<T> = core::result::Result<T, MortgageError>;
@@ -0,0 +1,149 @@
+// With the fast-math feature, grid and Monte Carlo workloads, which only need totals over the
+// schedule, make the payments in f64 rather than Decimal. Rates and payments are still worked
+// out in Decimal; only the payment-by-payment recurrence is in f64, so the totals are good to
+// about 15 significant digits rather than to the cent.
+use crate::schedule::PAID_OFF_TOLERANCE;
+use crate::{MortgageError, Result};
+use alloc::format;
+use rust_decimal::prelude::*;
+
+// What is left owing after making payments, and the interest charged on them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Amortization {
+    pub(crate) balance: f64,
+    pub(crate) interest: f64,
+    pub(crate) payments: u64,
+}
+
+// Make up to limit payments of payment on balance at rate, the periodic rate, stopping once it
+// is paid off. A payment that doesn't cover the interest is an error unless
+// negative_amortization is allowed, in which case the interest is added to the balance.
+pub(crate) fn amortize(
+    balance: f64,
+    rate: f64,
+    payment: f64,
+    limit: u64,
+    negative_amortization: bool,
+) -> Result<Amortization> {
+    let tolerance = to_f64(PAID_OFF_TOLERANCE)?;
+    let mut amortization = Amortization {
+        balance,
+        interest: 0.0,
+        payments: 0,
+    };
+
+    // Payments that can't pay the mortgage off are made all at once with the growth factor
+    // (1 + r)**k, so only the last few are made one at a time.
+    let skipped = payments_before_payoff(balance, rate, payment, negative_amortization).min(limit);
+    if skipped > 0 {
+        let growth = (1.0 + rate).powf(skipped as f64);
+        amortization.balance = balance * growth - payment * (growth - 1.0) / rate;
+        amortization.interest = skipped as f64 * payment - (balance - amortization.balance);
+        amortization.payments = skipped;
+    }
+
+    while amortization.balance > 0.0 && amortization.payments < limit {
+        let interest = amortization.balance * rate;
+        if payment <= interest && !negative_amortization {
+            return Err(MortgageError::PaymentBelowInterest {
+                payment: from_f64(payment)?,
+                interest: from_f64(interest)?,
+            });
+        }
+
+        amortization.interest += interest;
+        amortization.payments += 1;
+        if amortization.balance + interest - payment < tolerance {
+            amortization.balance = 0.0;
+        } else {
+            amortization.balance += interest - payment;
+        }
+    }
+
+    Ok(amortization)
+}
+
+// How many payments can be made without any chance of paying off balance, allowing a couple for
+// rounding. Every payment when they don't cover the interest and negative amortization is allowed.
+// n = ln(a / (a - p * r)) / ln(1 + r)
+fn payments_before_payoff(
+    balance: f64,
+    rate: f64,
+    payment: f64,
+    negative_amortization: bool,
+) -> u64 {
+    if balance <= 0.0 || rate <= 0.0 {
+        return 0;
+    }
+    if payment <= balance * rate {
+        return if negative_amortization { u64::MAX } else { 0 };
+    }
+    let payments = (payment / (payment - balance * rate)).ln() / rate.ln_1p();
+    (payments.floor() as u64).saturating_sub(2)
+}
+
+pub(crate) fn to_f64(x: Decimal) -> Result<f64> {
+    x.to_f64()
+        .ok_or_else(|| MortgageError::ConversionFailure(format!("Decimal to f64: {}", x)))
+}
+
+pub(crate) fn from_f64(x: f64) -> Result<Decimal> {
+    Decimal::from_f64(x)
+        .ok_or_else(|| MortgageError::ConversionFailure(format!("f64 to Decimal: {}", x)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CanadianMortgage, PaymentFrequency};
+    use rust_decimal_macros::*;
+
+    #[test]
+    fn totals_match_the_schedule() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Weekly).unwrap();
+        let amortization = amortize(
+            430000.0,
+            to_f64(mortgage.periodic_rate().unwrap()).unwrap(),
+            to_f64(mortgage.payment().unwrap()).unwrap(),
+            u64::MAX,
+            false,
+        )
+        .unwrap();
+
+        let summary = mortgage.summary().unwrap();
+        assert_eq!(amortization.balance, 0.0);
+        assert_eq!(
+            from_f64(amortization.interest).unwrap().round_dp(2),
+            summary.total_interest.round_dp(2)
+        );
+        assert_eq!(
+            amortization.payments,
+            mortgage.amortization_schedule().unwrap().len() as u64
+        );
+    }
+
+    #[test]
+    fn payments_made_at_once_match_one_at_a_time() {
+        let at_once = amortize(430000.0, 0.004, 2500.0, 120, false).unwrap();
+        let mut balance = 430000.0;
+        let mut interest = 0.0;
+        for _ in 0..120 {
+            let one = amortize(balance, 0.004, 2500.0, 1, false).unwrap();
+            balance = one.balance;
+            interest += one.interest;
+        }
+
+        assert_eq!(at_once.payments, 120);
+        assert!((at_once.balance - balance).abs() < 1e-6);
+        assert!((at_once.interest - interest).abs() < 1e-6);
+    }
+
+    #[test]
+    fn payments_must_cover_interest() {
+        assert!(amortize(100000.0, 0.01, 1000.0, u64::MAX, false).is_err());
+        let grown = amortize(100000.0, 0.01, 500.0, 12, true).unwrap();
+        assert_eq!(grown.payments, 12);
+        assert!(grown.balance > 100000.0);
+    }
+}
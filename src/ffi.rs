@@ -0,0 +1,347 @@
+// C ABI for C, C++ and Swift. See include/canadian_mortgage.h.
+//
+// Every function returns a CmStatus and writes its result through an out pointer.
+// Amounts are doubles; the calculations themselves are done in Decimal.
+// Frequencies are indexes into PaymentFrequency::ALL: 0 monthly, 1 semi-monthly, 2 biweekly,
+// 3 accelerated biweekly, 4 weekly and 5 accelerated weekly.
+use crate::{insurance, CanadianMortgage, MortgageError, PaymentFrequency, Result, ScheduleRow};
+use rust_decimal::prelude::*;
+use std::os::raw::c_char;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidFrequency = 2,
+    InvalidRate = 3,
+    InvalidAmortization = 4,
+    InvalidTerm = 5,
+    InvalidPrincipal = 6,
+    InvalidPropertyValue = 7,
+    Uninsurable = 8,
+    PaymentBelowInterest = 9,
+    NumericOverflow = 10,
+    ConversionFailure = 11,
+    IndexOutOfRange = 12,
+    Other = 99,
+}
+
+impl CmStatus {
+    // A status from C, where it may be any value at all; unknown values are Other.
+    fn from_raw(status: u32) -> CmStatus {
+        match status {
+            0 => CmStatus::Ok,
+            1 => CmStatus::NullPointer,
+            2 => CmStatus::InvalidFrequency,
+            3 => CmStatus::InvalidRate,
+            4 => CmStatus::InvalidAmortization,
+            5 => CmStatus::InvalidTerm,
+            6 => CmStatus::InvalidPrincipal,
+            7 => CmStatus::InvalidPropertyValue,
+            8 => CmStatus::Uninsurable,
+            9 => CmStatus::PaymentBelowInterest,
+            10 => CmStatus::NumericOverflow,
+            11 => CmStatus::ConversionFailure,
+            12 => CmStatus::IndexOutOfRange,
+            _ => CmStatus::Other,
+        }
+    }
+}
+
+impl From<&MortgageError> for CmStatus {
+    fn from(e: &MortgageError) -> CmStatus {
+        match e {
+            MortgageError::InvalidRate(_) => CmStatus::InvalidRate,
+            MortgageError::InvalidAmortization(_) => CmStatus::InvalidAmortization,
+            MortgageError::InvalidTerm { .. } | MortgageError::NewTermTooShort { .. } => {
+                CmStatus::InvalidTerm
+            }
+            MortgageError::InvalidPrincipal(_) => CmStatus::InvalidPrincipal,
+            MortgageError::InvalidPropertyValue(_) => CmStatus::InvalidPropertyValue,
+            MortgageError::Uninsurable(_) => CmStatus::Uninsurable,
+            MortgageError::UnknownPaymentFrequency(_) => CmStatus::InvalidFrequency,
+            MortgageError::PaymentBelowInterest { .. } => CmStatus::PaymentBelowInterest,
+            MortgageError::NumericOverflow(_) => CmStatus::NumericOverflow,
+            MortgageError::ConversionFailure(_) => CmStatus::ConversionFailure,
+            _ => CmStatus::Other,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CmScheduleRow {
+    pub payment_number: u64,
+    pub payment: f64,
+    pub interest: f64,
+    pub principal: f64,
+    pub prepayment: f64,
+    pub balance: f64,
+}
+
+// An amortization schedule owned by the library; release it with cm_schedule_free.
+pub struct CmSchedule {
+    rows: Vec<ScheduleRow>,
+}
+
+// A static, NUL-terminated description of a status. The status is taken as its raw value, since
+// C can pass any integer where a CmStatus is expected.
+#[no_mangle]
+pub extern "C" fn cm_status_message(status: u32) -> *const c_char {
+    let message: &'static [u8] = match CmStatus::from_raw(status) {
+        CmStatus::Ok => b"ok\0",
+        CmStatus::NullPointer => b"null pointer\0",
+        CmStatus::InvalidFrequency => b"invalid payment frequency\0",
+        CmStatus::InvalidRate => b"interest rate must be between 0% and 100%\0",
+        CmStatus::InvalidAmortization => b"amortization period must be at least 1 year\0",
+        CmStatus::InvalidTerm => b"invalid term\0",
+        CmStatus::InvalidPrincipal => b"principal must be greater than zero\0",
+        CmStatus::InvalidPropertyValue => b"property value must be greater than zero\0",
+        CmStatus::Uninsurable => b"loan-to-value is above the maximum insurable 95%\0",
+        CmStatus::PaymentBelowInterest => b"payment does not cover the interest\0",
+        CmStatus::NumericOverflow => b"numeric overflow\0",
+        CmStatus::ConversionFailure => b"could not convert a number\0",
+        CmStatus::IndexOutOfRange => b"index out of range\0",
+        CmStatus::Other => b"error\0",
+    };
+    message.as_ptr() as *const c_char
+}
+
+/// # Safety
+/// out_payment must be null or point to a writable double.
+#[no_mangle]
+pub unsafe extern "C" fn cm_payment(
+    principal: f64,
+    rate: f64,
+    amortization: u32,
+    frequency: u32,
+    out_payment: *mut f64,
+) -> CmStatus {
+    if out_payment.is_null() {
+        return CmStatus::NullPointer;
+    }
+    let payment = match mortgage(principal, rate, amortization, frequency) {
+        Ok(mortgage) => mortgage.payment().and_then(to_f64),
+        Err(status) => return status,
+    };
+    write(payment, out_payment)
+}
+
+/// # Safety
+/// out_premium must be null or point to a writable double.
+#[no_mangle]
+pub unsafe extern "C" fn cm_insurance_premium(
+    loan: f64,
+    property_value: f64,
+    amortization: u32,
+    out_premium: *mut f64,
+) -> CmStatus {
+    if out_premium.is_null() {
+        return CmStatus::NullPointer;
+    }
+    let premium = from_f64(loan)
+        .and_then(|loan| Ok((loan, from_f64(property_value)?)))
+        .and_then(|(loan, property_value)| {
+            insurance::premium(loan, property_value, amortization.into())
+        })
+        .and_then(to_f64);
+    write(premium, out_premium)
+}
+
+/// # Safety
+/// out_schedule must be null or point to a writable pointer. On success it receives a schedule
+/// that must be released with cm_schedule_free.
+#[no_mangle]
+pub unsafe extern "C" fn cm_schedule_new(
+    principal: f64,
+    rate: f64,
+    amortization: u32,
+    frequency: u32,
+    out_schedule: *mut *mut CmSchedule,
+) -> CmStatus {
+    if out_schedule.is_null() {
+        return CmStatus::NullPointer;
+    }
+    let rows = match mortgage(principal, rate, amortization, frequency) {
+        Ok(mortgage) => mortgage.amortization_schedule(),
+        Err(status) => return status,
+    };
+    match rows {
+        Ok(rows) => {
+            *out_schedule = Box::into_raw(Box::new(CmSchedule { rows }));
+            CmStatus::Ok
+        }
+        Err(e) => CmStatus::from(&e),
+    }
+}
+
+/// # Safety
+/// schedule must be null or a schedule from cm_schedule_new that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn cm_schedule_len(schedule: *const CmSchedule) -> usize {
+    schedule.as_ref().map_or(0, |schedule| schedule.rows.len())
+}
+
+/// # Safety
+/// schedule must be null or a schedule from cm_schedule_new that has not been freed, and
+/// out_row must be null or point to a writable CmScheduleRow.
+#[no_mangle]
+pub unsafe extern "C" fn cm_schedule_row(
+    schedule: *const CmSchedule,
+    index: usize,
+    out_row: *mut CmScheduleRow,
+) -> CmStatus {
+    let schedule = match schedule.as_ref() {
+        Some(schedule) if !out_row.is_null() => schedule,
+        _ => return CmStatus::NullPointer,
+    };
+    let row = match schedule.rows.get(index) {
+        Some(row) => row,
+        None => return CmStatus::IndexOutOfRange,
+    };
+
+    let converted = (|| {
+        Ok(CmScheduleRow {
+            payment_number: row.payment_number,
+            payment: to_f64(row.payment)?,
+            interest: to_f64(row.interest)?,
+            principal: to_f64(row.principal)?,
+            prepayment: to_f64(row.prepayment)?,
+            balance: to_f64(row.balance)?,
+        })
+    })();
+    write(converted, out_row)
+}
+
+/// # Safety
+/// schedule must be null or a schedule from cm_schedule_new that has not already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn cm_schedule_free(schedule: *mut CmSchedule) {
+    if !schedule.is_null() {
+        drop(Box::from_raw(schedule));
+    }
+}
+
+fn mortgage(
+    principal: f64,
+    rate: f64,
+    amortization: u32,
+    frequency: u32,
+) -> std::result::Result<CanadianMortgage, CmStatus> {
+    let frequency = *PaymentFrequency::ALL
+        .get(frequency as usize)
+        .ok_or(CmStatus::InvalidFrequency)?;
+    let mortgage = from_f64(principal).and_then(|principal| {
+        CanadianMortgage::new(principal, from_f64(rate)?, amortization.into(), frequency)
+    });
+    mortgage.map_err(|e| CmStatus::from(&e))
+}
+
+fn from_f64(value: f64) -> Result<Decimal> {
+    Decimal::from_f64(value)
+        .ok_or_else(|| MortgageError::ConversionFailure(format!("f64 to Decimal: {}", value)))
+}
+
+fn to_f64(value: Decimal) -> Result<f64> {
+    value
+        .to_f64()
+        .ok_or_else(|| MortgageError::ConversionFailure(format!("Decimal to f64: {}", value)))
+}
+
+unsafe fn write<T>(result: Result<T>, out: *mut T) -> CmStatus {
+    match result {
+        Ok(value) => {
+            *out = value;
+            CmStatus::Ok
+        }
+        Err(e) => CmStatus::from(&e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+    use std::ptr;
+
+    #[test]
+    fn payment_and_errors() {
+        let mut payment = 0.0;
+        unsafe {
+            assert_eq!(
+                cm_payment(430000.0, 4.59, 25, 0, &mut payment),
+                CmStatus::Ok
+            );
+            assert!((payment - 2401.4953).abs() < 0.0001);
+
+            assert_eq!(
+                cm_payment(430000.0, 120.0, 25, 0, &mut payment),
+                CmStatus::InvalidRate
+            );
+            assert_eq!(
+                cm_payment(430000.0, 4.59, 25, 6, &mut payment),
+                CmStatus::InvalidFrequency
+            );
+            assert_eq!(
+                cm_payment(430000.0, 4.59, 25, 0, ptr::null_mut()),
+                CmStatus::NullPointer
+            );
+
+            let message = CStr::from_ptr(cm_status_message(CmStatus::InvalidRate as u32));
+            assert_eq!(
+                message.to_str().unwrap(),
+                "interest rate must be between 0% and 100%"
+            );
+            let unknown = CStr::from_ptr(cm_status_message(42));
+            assert_eq!(unknown.to_str().unwrap(), "error");
+        }
+    }
+
+    #[test]
+    fn insurance_premium() {
+        let mut premium = 0.0;
+        unsafe {
+            assert_eq!(
+                cm_insurance_premium(475000.0, 500000.0, 25, &mut premium),
+                CmStatus::Ok
+            );
+            assert_eq!(premium, 19000.0);
+            assert_eq!(
+                cm_insurance_premium(490000.0, 500000.0, 25, &mut premium),
+                CmStatus::Uninsurable
+            );
+        }
+    }
+
+    #[test]
+    fn schedule() {
+        unsafe {
+            let mut schedule = ptr::null_mut();
+            assert_eq!(
+                cm_schedule_new(430000.0, 4.59, 25, 0, &mut schedule),
+                CmStatus::Ok
+            );
+            assert_eq!(cm_schedule_len(schedule), 300);
+
+            let mut row = CmScheduleRow {
+                payment_number: 0,
+                payment: 0.0,
+                interest: 0.0,
+                principal: 0.0,
+                prepayment: 0.0,
+                balance: 0.0,
+            };
+            assert_eq!(cm_schedule_row(schedule, 299, &mut row), CmStatus::Ok);
+            assert_eq!(row.payment_number, 300);
+            assert_eq!(row.balance, 0.0);
+            assert_eq!(
+                cm_schedule_row(schedule, 300, &mut row),
+                CmStatus::IndexOutOfRange
+            );
+
+            cm_schedule_free(schedule);
+            assert_eq!(cm_schedule_len(ptr::null()), 0);
+        }
+    }
+}
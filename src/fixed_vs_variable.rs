@@ -0,0 +1,161 @@
+// Choosing between a fixed and a variable rate quote over the same term. The variable mortgage
+// is run over an assumed path of prime rate changes, and the break-even is how far prime would
+// have to move, all at once and for the whole term, for the two to cost the same interest.
+use crate::{CanadianMortgage, PrimeRateChange, Result, ScheduleRow, VariableRateMortgage};
+use alloc::vec::Vec;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// Bisection steps for the break-even, enough for well under a hundredth of a basis point.
+const BREAK_EVEN_ITERATIONS: u32 = 40;
+
+// Interest and balances over the fixed mortgage's term. interest_savings is what the variable
+// mortgage saves, negative when it costs more. break_even_prime_change is in percentage points,
+// added to prime and to every change in the path.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FixedVsVariable {
+    pub term_years: u64,
+    pub fixed_interest: Decimal,
+    pub variable_interest: Decimal,
+    pub interest_savings: Decimal,
+    pub fixed_balance_at_renewal: Decimal,
+    pub variable_balance_at_renewal: Decimal,
+    pub break_even_prime_change: Decimal,
+}
+
+pub fn compare_fixed_variable(
+    fixed: &CanadianMortgage,
+    variable: &VariableRateMortgage,
+    prime_rate_changes: &[PrimeRateChange],
+) -> Result<FixedVsVariable> {
+    let term_years = fixed.term_years();
+    let fixed_schedule = fixed.amortization_schedule()?;
+    let (fixed_interest, fixed_balance_at_renewal) =
+        over_term(&fixed_schedule, fixed.term_payments(), fixed.principal());
+
+    let variable_term_payments = term_years * variable.payment_frequency().payments_per_year();
+    let variable_over_term = |shift: Decimal| -> Result<(Decimal, Decimal)> {
+        let changes: Vec<PrimeRateChange> = prime_rate_changes
+            .iter()
+            .map(|change| PrimeRateChange {
+                prime_rate: change.prime_rate + shift,
+                ..*change
+            })
+            .collect();
+        let shifted = variable.with_prime_shift(shift)?;
+        Ok(over_term(
+            &shifted.amortization_schedule(&changes)?,
+            variable_term_payments,
+            variable.principal(),
+        ))
+    };
+    let (variable_interest, variable_balance_at_renewal) = variable_over_term(dec!(0))?;
+
+    // Between a shift that takes the lowest rate on the path to zero, and one that puts every
+    // rate a point above the fixed rate.
+    let lowest_rate = prime_rate_changes
+        .iter()
+        .map(|change| change.prime_rate)
+        .fold(variable.prime_rate(), Decimal::min)
+        + variable.spread();
+    let mut low = -lowest_rate;
+    let mut high = fixed.nominal_rate() + dec!(1) - lowest_rate;
+    for _ in 0..BREAK_EVEN_ITERATIONS {
+        let middle = (low + high) / dec!(2);
+        if variable_over_term(middle)?.0 < fixed_interest {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    Ok(FixedVsVariable {
+        term_years,
+        fixed_interest,
+        variable_interest,
+        interest_savings: fixed_interest - variable_interest,
+        fixed_balance_at_renewal,
+        variable_balance_at_renewal,
+        break_even_prime_change: (low + high) / dec!(2),
+    })
+}
+
+// Interest paid over the first payments, and the balance left after them.
+fn over_term(schedule: &[ScheduleRow], payments: u64, principal: Decimal) -> (Decimal, Decimal) {
+    let term = &schedule[..schedule.len().min(payments as usize)];
+    (
+        term.iter().map(|row| row.interest).sum(),
+        term.last().map_or(principal, |row| row.balance),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+
+    fn fixed() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_term(5)
+            .unwrap()
+    }
+
+    fn variable() -> VariableRateMortgage {
+        VariableRateMortgage::new(
+            dec!(430000),
+            dec!(5.35),
+            dec!(-0.90),
+            25,
+            PaymentFrequency::Monthly,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn compares_interest_over_the_term() {
+        let comparison = compare_fixed_variable(&fixed(), &variable(), &[]).unwrap();
+
+        assert_eq!(comparison.term_years, 5);
+        assert_eq!(
+            comparison.fixed_interest,
+            fixed().interest_paid_in_term().unwrap()
+        );
+        assert_eq!(
+            comparison.interest_savings,
+            comparison.fixed_interest - comparison.variable_interest
+        );
+        assert!(
+            comparison.interest_savings > dec!(0),
+            "4.45% compounded monthly is cheaper than 4.59% compounded semi-annually"
+        );
+        assert!(comparison.variable_balance_at_renewal < comparison.fixed_balance_at_renewal);
+    }
+
+    #[test]
+    fn break_even_costs_the_same() {
+        let path = [PrimeRateChange {
+            payment_number: 13,
+            prime_rate: dec!(4.95),
+        }];
+        let comparison = compare_fixed_variable(&fixed(), &variable(), &path).unwrap();
+        assert!(comparison.break_even_prime_change > dec!(0));
+
+        let shift = comparison.break_even_prime_change;
+        let shifted = VariableRateMortgage::new(
+            dec!(430000),
+            dec!(5.35) + shift,
+            dec!(-0.90),
+            25,
+            PaymentFrequency::Monthly,
+        )
+        .unwrap();
+        let shifted_path = [PrimeRateChange {
+            payment_number: 13,
+            prime_rate: dec!(4.95) + shift,
+        }];
+        let at_break_even = compare_fixed_variable(&fixed(), &shifted, &shifted_path).unwrap();
+        assert_eq!(at_break_even.interest_savings.round_dp(2), dec!(0));
+    }
+}
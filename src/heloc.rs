@@ -0,0 +1,249 @@
+// A home equity line of credit (HELOC) is a revolving balance secured against the home.
+// Money can be drawn and repaid at any time up to the credit limit. Interest is calculated
+// daily at prime plus a spread, compounding daily, and billed monthly. The minimum payment
+// is the interest billed, so a HELOC never has to be paid down.
+use crate::{DayCount, MortgageError, Result};
+use alloc::vec::Vec;
+use chrono::{Days, Months, NaiveDate};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// A draw or repayment on a HELOC.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HelocTransaction {
+    pub date: NaiveDate,
+    pub amount: Decimal,
+}
+
+// One monthly statement. interest is what accrued over the period, and is billed on
+// statement_date as the minimum payment. balance is owing after the minimum payment is made.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HelocStatement {
+    pub statement_date: NaiveDate,
+    pub draws: Decimal,
+    pub repayments: Decimal,
+    pub interest: Decimal,
+    pub minimum_payment: Decimal,
+    pub balance: Decimal,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Heloc {
+    credit_limit: Decimal,
+    prime_rate: Decimal,
+    spread: Decimal,
+    day_count: DayCount,
+    draws: Vec<HelocTransaction>,
+    repayments: Vec<HelocTransaction>,
+}
+
+impl Heloc {
+    // prime_rate is the lender's prime rate as a percentage, and spread is added to it.
+    pub fn new(credit_limit: Decimal, prime_rate: Decimal, spread: Decimal) -> Result<Heloc> {
+        if credit_limit <= dec!(0) {
+            return Err(MortgageError::InvalidPrincipal(credit_limit));
+        }
+        crate::validate_rate(prime_rate + spread)?;
+
+        Ok(Heloc {
+            credit_limit,
+            prime_rate,
+            spread,
+            day_count: DayCount::Actual365,
+            draws: Vec::new(),
+            repayments: Vec::new(),
+        })
+    }
+
+    // Actual/365 unless set otherwise.
+    pub fn with_day_count(mut self, day_count: DayCount) -> Heloc {
+        self.day_count = day_count;
+        self
+    }
+
+    // Borrow amount on date. It is an error, when the statements are generated, for the balance
+    // to go over the credit limit.
+    pub fn with_draw(mut self, date: NaiveDate, amount: Decimal) -> Result<Heloc> {
+        if amount <= dec!(0) {
+            return Err(MortgageError::InvalidTransaction { date, amount });
+        }
+        self.draws.push(HelocTransaction { date, amount });
+        Ok(self)
+    }
+
+    // Pay amount off the balance on date, on top of the minimum payment.
+    // Repaying more than is owing leaves the balance at zero.
+    pub fn with_repayment(mut self, date: NaiveDate, amount: Decimal) -> Result<Heloc> {
+        if amount <= dec!(0) {
+            return Err(MortgageError::InvalidTransaction { date, amount });
+        }
+        self.repayments.push(HelocTransaction { date, amount });
+        Ok(self)
+    }
+
+    pub fn credit_limit(&self) -> Decimal {
+        self.credit_limit
+    }
+
+    // The annual interest rate as a percentage, compounded daily.
+    pub fn interest_rate(&self) -> Decimal {
+        self.prime_rate + self.spread
+    }
+
+    pub fn draws(&self) -> &[HelocTransaction] {
+        &self.draws
+    }
+
+    pub fn repayments(&self) -> &[HelocTransaction] {
+        &self.repayments
+    }
+
+    // Monthly statements for the months billing periods starting on start, each ending the day
+    // before its statement date a month later. Draws and repayments made before start are
+    // part of the opening balance. Each day, draws and repayments are applied and then interest
+    // accrues on the balance plus the interest accrued so far in the period.
+    pub fn minimum_payment_schedule(
+        &self,
+        start: NaiveDate,
+        months: u32,
+    ) -> Result<Vec<HelocStatement>> {
+        let daily_rate = self.interest_rate() / dec!(100) / self.day_count.days_in_year();
+
+        let mut statements = Vec::new();
+        let mut balance = dec!(0);
+        let mut period_start = start;
+        let mut first_day = true;
+
+        for month in 1..=months {
+            let statement_date = start
+                .checked_add_months(Months::new(month))
+                .ok_or(MortgageError::NumericOverflow("statement date"))?;
+
+            let mut draws = dec!(0);
+            let mut repayments = dec!(0);
+            let mut interest = dec!(0);
+            let mut date = period_start;
+
+            while date < statement_date {
+                let on_date = |transaction: &&HelocTransaction| {
+                    transaction.date == date || (first_day && transaction.date < date)
+                };
+                let drawn: Decimal = self.draws.iter().filter(on_date).map(|t| t.amount).sum();
+                let repaid: Decimal = self
+                    .repayments
+                    .iter()
+                    .filter(on_date)
+                    .map(|t| t.amount)
+                    .sum();
+                first_day = false;
+
+                balance = (balance + drawn - repaid).max(dec!(0));
+                if balance > self.credit_limit {
+                    return Err(MortgageError::CreditLimitExceeded {
+                        date,
+                        balance,
+                        limit: self.credit_limit,
+                    });
+                }
+                draws += drawn;
+                repayments += repaid;
+                interest += (balance + interest) * daily_rate;
+
+                date = date
+                    .checked_add_days(Days::new(1))
+                    .ok_or(MortgageError::NumericOverflow("statement date"))?;
+            }
+
+            statements.push(HelocStatement {
+                statement_date,
+                draws,
+                repayments,
+                interest,
+                minimum_payment: interest,
+                balance,
+            });
+            period_start = statement_date;
+        }
+
+        Ok(statements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn heloc() -> Heloc {
+        Heloc::new(dec!(100000), dec!(6.95), dec!(0.5)).unwrap()
+    }
+
+    #[test]
+    fn interest_compounds_daily() {
+        let heloc = heloc().with_draw(date(2024, 1, 1), dec!(50000)).unwrap();
+        let statements = heloc.minimum_payment_schedule(date(2024, 1, 1), 2).unwrap();
+
+        let daily_rate = dec!(0.0745) / dec!(365);
+        let january = dec!(50000) * ((dec!(1) + daily_rate).powu(31) - dec!(1));
+        assert_eq!(statements[0].statement_date, date(2024, 2, 1));
+        assert_eq!(statements[0].interest.round_dp(10), january.round_dp(10));
+        assert_eq!(statements[0].minimum_payment, statements[0].interest);
+        assert_eq!(statements[0].draws, dec!(50000));
+        assert_eq!(statements[0].balance, dec!(50000), "interest only");
+        assert!(
+            statements[1].interest < statements[0].interest,
+            "February is shorter"
+        );
+    }
+
+    #[test]
+    fn draws_and_repayments_change_the_balance() {
+        let heloc = heloc()
+            .with_draw(date(2023, 12, 15), dec!(20000))
+            .unwrap()
+            .with_draw(date(2024, 1, 16), dec!(10000))
+            .unwrap()
+            .with_repayment(date(2024, 2, 20), dec!(50000))
+            .unwrap();
+        let statements = heloc.minimum_payment_schedule(date(2024, 1, 1), 3).unwrap();
+
+        assert_eq!(
+            statements[0].draws,
+            dec!(30000),
+            "earlier draws open the balance"
+        );
+        assert_eq!(statements[0].balance, dec!(30000));
+        assert_eq!(statements[1].repayments, dec!(50000));
+        assert_eq!(
+            statements[1].balance,
+            dec!(0),
+            "overpayment is not a credit"
+        );
+        assert_eq!(statements[2].interest, dec!(0));
+    }
+
+    #[test]
+    fn credit_limit_is_enforced() {
+        let heloc = heloc()
+            .with_draw(date(2024, 1, 1), dec!(60000))
+            .unwrap()
+            .with_draw(date(2024, 1, 10), dec!(50000))
+            .unwrap();
+        assert_eq!(
+            heloc.minimum_payment_schedule(date(2024, 1, 1), 1),
+            Err(MortgageError::CreditLimitExceeded {
+                date: date(2024, 1, 10),
+                balance: dec!(110000),
+                limit: dec!(100000),
+            })
+        );
+        assert!(heloc.clone().with_draw(date(2024, 1, 1), dec!(0)).is_err());
+        assert!(Heloc::new(dec!(0), dec!(6.95), dec!(0.5)).is_err());
+    }
+}
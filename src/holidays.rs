@@ -0,0 +1,465 @@
+// Statutory holidays and business days, so payment dates can be moved off weekends and holidays.
+// Each province and territory has its own statutory holidays; the federal calendar is the general
+// holidays under the Canada Labour Code, which federally regulated banks observe.
+// Fixed-date holidays that fall on a weekend are observed on the next weekday that is not already
+// a holiday, e.g. Christmas on a Saturday is observed on Monday and Boxing Day on Tuesday.
+use crate::{CanadianMortgage, DatedScheduleRow, Province, Result};
+use alloc::vec::Vec;
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HolidayCalendar {
+    province: Option<Province>,
+}
+
+// Following moves a date forward to the next business day. Modified following does too, unless
+// that is in the next month, in which case it moves back to the previous business day instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RollConvention {
+    Following,
+    ModifiedFollowing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Holiday {
+    NewYearsDay,
+    // Family Day, Louis Riel Day, Islander Day and Nova Scotia Heritage Day.
+    FamilyDay,
+    YukonHeritageDay,
+    GoodFriday,
+    // National Patriots' Day in Quebec.
+    VictoriaDay,
+    NationalIndigenousPeoplesDay,
+    SaintJeanBaptisteDay,
+    CanadaDay,
+    NunavutDay,
+    // The first Monday in August, e.g. BC Day, New Brunswick Day and Saskatchewan Day.
+    CivicDay,
+    DiscoveryDay,
+    LabourDay,
+    TruthAndReconciliationDay,
+    Thanksgiving,
+    RemembranceDay,
+    Christmas,
+    BoxingDay,
+}
+
+use Holiday::*;
+
+const FEDERAL: [Holiday; 11] = [
+    NewYearsDay,
+    GoodFriday,
+    VictoriaDay,
+    CanadaDay,
+    CivicDay,
+    LabourDay,
+    TruthAndReconciliationDay,
+    Thanksgiving,
+    RemembranceDay,
+    Christmas,
+    BoxingDay,
+];
+
+fn provincial_holidays(province: Province) -> &'static [Holiday] {
+    match province {
+        Province::Alberta => &[
+            NewYearsDay,
+            FamilyDay,
+            GoodFriday,
+            VictoriaDay,
+            CanadaDay,
+            LabourDay,
+            Thanksgiving,
+            RemembranceDay,
+            Christmas,
+        ],
+        Province::BritishColumbia => &[
+            NewYearsDay,
+            FamilyDay,
+            GoodFriday,
+            VictoriaDay,
+            CanadaDay,
+            CivicDay,
+            LabourDay,
+            TruthAndReconciliationDay,
+            Thanksgiving,
+            RemembranceDay,
+            Christmas,
+        ],
+        Province::Manitoba => &[
+            NewYearsDay,
+            FamilyDay,
+            GoodFriday,
+            VictoriaDay,
+            CanadaDay,
+            LabourDay,
+            Thanksgiving,
+            Christmas,
+        ],
+        Province::NewBrunswick => &[
+            NewYearsDay,
+            FamilyDay,
+            GoodFriday,
+            CanadaDay,
+            CivicDay,
+            LabourDay,
+            RemembranceDay,
+            Christmas,
+        ],
+        Province::NewfoundlandAndLabrador => &[
+            NewYearsDay,
+            GoodFriday,
+            CanadaDay,
+            LabourDay,
+            RemembranceDay,
+            Christmas,
+        ],
+        Province::NorthwestTerritories => &[
+            NewYearsDay,
+            GoodFriday,
+            VictoriaDay,
+            NationalIndigenousPeoplesDay,
+            CanadaDay,
+            CivicDay,
+            LabourDay,
+            TruthAndReconciliationDay,
+            Thanksgiving,
+            RemembranceDay,
+            Christmas,
+        ],
+        Province::NovaScotia => &[
+            NewYearsDay,
+            FamilyDay,
+            GoodFriday,
+            CanadaDay,
+            LabourDay,
+            Christmas,
+        ],
+        Province::Nunavut => &[
+            NewYearsDay,
+            GoodFriday,
+            VictoriaDay,
+            CanadaDay,
+            NunavutDay,
+            CivicDay,
+            LabourDay,
+            TruthAndReconciliationDay,
+            Thanksgiving,
+            RemembranceDay,
+            Christmas,
+        ],
+        Province::Ontario => &[
+            NewYearsDay,
+            FamilyDay,
+            GoodFriday,
+            VictoriaDay,
+            CanadaDay,
+            LabourDay,
+            Thanksgiving,
+            Christmas,
+            BoxingDay,
+        ],
+        Province::PrinceEdwardIsland => &[
+            NewYearsDay,
+            FamilyDay,
+            GoodFriday,
+            CanadaDay,
+            LabourDay,
+            TruthAndReconciliationDay,
+            RemembranceDay,
+            Christmas,
+        ],
+        Province::Quebec => &[
+            NewYearsDay,
+            GoodFriday,
+            VictoriaDay,
+            SaintJeanBaptisteDay,
+            CanadaDay,
+            LabourDay,
+            Thanksgiving,
+            Christmas,
+        ],
+        Province::Saskatchewan => &[
+            NewYearsDay,
+            FamilyDay,
+            GoodFriday,
+            VictoriaDay,
+            CanadaDay,
+            CivicDay,
+            LabourDay,
+            Thanksgiving,
+            RemembranceDay,
+            Christmas,
+        ],
+        Province::Yukon => &[
+            NewYearsDay,
+            YukonHeritageDay,
+            GoodFriday,
+            VictoriaDay,
+            NationalIndigenousPeoplesDay,
+            CanadaDay,
+            DiscoveryDay,
+            LabourDay,
+            TruthAndReconciliationDay,
+            Thanksgiving,
+            RemembranceDay,
+            Christmas,
+        ],
+    }
+}
+
+impl Holiday {
+    // The date of the holiday in year, and whether it is on a fixed day of the year.
+    // None if the holiday was not observed that year.
+    fn date(&self, year: i32) -> Option<(NaiveDate, bool)> {
+        let fixed = |month, day| NaiveDate::from_ymd_opt(year, month, day).map(|date| (date, true));
+        let nth_monday = |month, n| {
+            NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Mon, n)
+                .map(|date| (date, false))
+        };
+
+        match self {
+            NewYearsDay => fixed(1, 1),
+            FamilyDay => nth_monday(2, 3),
+            YukonHeritageDay => {
+                // The Friday before the last Sunday in February.
+                let march = NaiveDate::from_ymd_opt(year, 3, 1)?;
+                let days_since_sunday = march.pred_opt()?.weekday().num_days_from_sunday();
+                let last_sunday =
+                    march.checked_sub_days(Days::new(1 + days_since_sunday as u64))?;
+                Some((last_sunday.checked_sub_days(Days::new(2))?, false))
+            }
+            GoodFriday => Some((easter(year)?.checked_sub_days(Days::new(2))?, false)),
+            VictoriaDay => {
+                // The last Monday before May 25.
+                let may_24 = NaiveDate::from_ymd_opt(year, 5, 24)?;
+                let days_since_monday = may_24.weekday().num_days_from_monday();
+                Some((
+                    may_24.checked_sub_days(Days::new(days_since_monday as u64))?,
+                    false,
+                ))
+            }
+            NationalIndigenousPeoplesDay => fixed(6, 21),
+            SaintJeanBaptisteDay => fixed(6, 24),
+            CanadaDay => fixed(7, 1),
+            NunavutDay => fixed(7, 9),
+            CivicDay => nth_monday(8, 1),
+            DiscoveryDay => nth_monday(8, 3),
+            LabourDay => nth_monday(9, 1),
+            TruthAndReconciliationDay if year >= 2021 => fixed(9, 30),
+            TruthAndReconciliationDay => None,
+            Thanksgiving => nth_monday(10, 2),
+            RemembranceDay => fixed(11, 11),
+            Christmas => fixed(12, 25),
+            BoxingDay => fixed(12, 26),
+        }
+    }
+}
+
+// Easter Sunday in the Gregorian calendar, by the anonymous Gregorian algorithm.
+fn easter(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+impl HolidayCalendar {
+    pub fn federal() -> HolidayCalendar {
+        HolidayCalendar { province: None }
+    }
+
+    pub fn province(province: Province) -> HolidayCalendar {
+        HolidayCalendar {
+            province: Some(province),
+        }
+    }
+
+    // The days the holidays in year are observed, in order.
+    pub fn holidays(&self, year: i32) -> Vec<NaiveDate> {
+        let holidays = match self.province {
+            Some(province) => provincial_holidays(province),
+            None => &FEDERAL,
+        };
+
+        let mut dates: Vec<(NaiveDate, bool)> = holidays
+            .iter()
+            .filter_map(|holiday| holiday.date(year))
+            .collect();
+        dates.sort();
+
+        let mut observed: Vec<NaiveDate> = Vec::with_capacity(dates.len());
+        for (date, fixed) in dates {
+            let mut date = date;
+            if fixed && is_weekend(date) {
+                while is_weekend(date) || observed.contains(&date) {
+                    date = match date.succ_opt() {
+                        Some(next) => next,
+                        None => break,
+                    };
+                }
+            }
+            observed.push(date);
+        }
+        observed.sort();
+        observed
+    }
+
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays(date.year()).contains(&date)
+    }
+
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !is_weekend(date) && !self.is_holiday(date)
+    }
+
+    // date if it is a business day, otherwise the business day the convention moves it to.
+    pub fn roll(&self, date: NaiveDate, convention: RollConvention) -> NaiveDate {
+        let following = self.next_business_day(date, |date| date.succ_opt());
+        match convention {
+            RollConvention::Following => following,
+            RollConvention::ModifiedFollowing if following.month() == date.month() => following,
+            RollConvention::ModifiedFollowing => {
+                self.next_business_day(date, |date| date.pred_opt())
+            }
+        }
+    }
+
+    fn next_business_day(
+        &self,
+        mut date: NaiveDate,
+        step: impl Fn(NaiveDate) -> Option<NaiveDate>,
+    ) -> NaiveDate {
+        while !self.is_business_day(date) {
+            date = match step(date) {
+                Some(next) => next,
+                None => break,
+            };
+        }
+        date
+    }
+}
+
+impl CanadianMortgage {
+    // dated_schedule with every payment date moved to a business day.
+    pub fn business_day_schedule(
+        &self,
+        first_payment_date: NaiveDate,
+        calendar: &HolidayCalendar,
+        convention: RollConvention,
+    ) -> Result<Vec<DatedScheduleRow>> {
+        let mut schedule = self.dated_schedule(first_payment_date)?;
+        for row in &mut schedule {
+            row.date = calendar.roll(row.date, convention);
+        }
+        Ok(schedule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+    use alloc::vec;
+    use rust_decimal_macros::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn moveable_holidays() {
+        assert_eq!(easter(2024), Some(date(2024, 3, 31)));
+        assert_eq!(easter(2025), Some(date(2025, 4, 20)));
+
+        let ontario = HolidayCalendar::province(Province::Ontario).holidays(2025);
+        assert_eq!(
+            ontario,
+            vec![
+                date(2025, 1, 1),
+                date(2025, 2, 17),
+                date(2025, 4, 18),
+                date(2025, 5, 19),
+                date(2025, 7, 1),
+                date(2025, 9, 1),
+                date(2025, 10, 13),
+                date(2025, 12, 25),
+                date(2025, 12, 26),
+            ]
+        );
+
+        let yukon = HolidayCalendar::province(Province::Yukon);
+        assert!(yukon.is_holiday(date(2025, 2, 21)), "Heritage Day");
+        assert!(yukon.is_holiday(date(2025, 8, 18)), "Discovery Day");
+        assert!(!yukon.is_holiday(date(2025, 2, 17)));
+    }
+
+    #[test]
+    fn weekend_holidays_are_observed_on_weekdays() {
+        // Christmas 2021 was a Saturday and Boxing Day a Sunday.
+        let federal = HolidayCalendar::federal();
+        assert!(federal.is_holiday(date(2021, 12, 27)));
+        assert!(federal.is_holiday(date(2021, 12, 28)));
+
+        let quebec = HolidayCalendar::province(Province::Quebec);
+        assert!(!quebec.is_holiday(date(2021, 12, 28)), "no Boxing Day");
+        assert!(quebec.is_holiday(date(2024, 6, 24)));
+        assert!(!quebec.is_holiday(date(2024, 9, 30)));
+        assert!(federal.is_holiday(date(2024, 9, 30)));
+    }
+
+    #[test]
+    fn dates_roll_to_business_days() {
+        let ontario = HolidayCalendar::province(Province::Ontario);
+
+        // Saturday, Sunday then Canada Day.
+        assert_eq!(
+            ontario.roll(date(2023, 7, 1), RollConvention::Following),
+            date(2023, 7, 4)
+        );
+        assert_eq!(
+            ontario.roll(date(2023, 7, 4), RollConvention::Following),
+            date(2023, 7, 4)
+        );
+        // Saturday the 30th of November rolls back to Friday rather than into December.
+        assert_eq!(
+            ontario.roll(date(2024, 11, 30), RollConvention::Following),
+            date(2024, 12, 2)
+        );
+        assert_eq!(
+            ontario.roll(date(2024, 11, 30), RollConvention::ModifiedFollowing),
+            date(2024, 11, 29)
+        );
+    }
+
+    #[test]
+    fn schedule_dates_are_business_days() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        let ontario = HolidayCalendar::province(Province::Ontario);
+        let schedule = mortgage
+            .business_day_schedule(date(2024, 1, 1), &ontario, RollConvention::Following)
+            .unwrap();
+
+        assert_eq!(schedule[0].date, date(2024, 1, 2));
+        assert_eq!(schedule[5].date, date(2024, 6, 3), "June 1 is a Saturday");
+        assert!(schedule.iter().all(|row| ontario.is_business_day(row.date)));
+    }
+}
@@ -0,0 +1,237 @@
+// A hybrid, or split, mortgage divides the borrowing into components with their own rates and
+// terms, most often part fixed and part variable, to hedge against rate changes. The components
+// are paid together, so the payments and schedules are combined payment by payment.
+use crate::{
+    CanadianMortgage, MortgageError, PaymentFrequency, PrimeRateChange, Result, ScheduleRow,
+    Summary, VariableRateMortgage,
+};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use rust_decimal::prelude::*;
+
+#[derive(Debug, Clone)]
+pub enum HybridComponent {
+    Fixed(Box<CanadianMortgage>),
+    Variable(VariableRateMortgage),
+}
+
+impl HybridComponent {
+    pub fn principal(&self) -> Decimal {
+        match self {
+            HybridComponent::Fixed(mortgage) => mortgage.principal(),
+            HybridComponent::Variable(mortgage) => mortgage.principal(),
+        }
+    }
+
+    pub fn payment_frequency(&self) -> PaymentFrequency {
+        match self {
+            HybridComponent::Fixed(mortgage) => mortgage.payment_frequency(),
+            HybridComponent::Variable(mortgage) => mortgage.payment_frequency(),
+        }
+    }
+
+    pub fn payment(&self) -> Result<Decimal> {
+        match self {
+            HybridComponent::Fixed(mortgage) => mortgage.payment(),
+            HybridComponent::Variable(mortgage) => mortgage.payment(),
+        }
+    }
+
+    // prime_rate_changes only apply to variable components.
+    pub fn amortization_schedule(
+        &self,
+        prime_rate_changes: &[PrimeRateChange],
+    ) -> Result<Vec<ScheduleRow>> {
+        match self {
+            HybridComponent::Fixed(mortgage) => mortgage.amortization_schedule(),
+            HybridComponent::Variable(mortgage) => {
+                mortgage.amortization_schedule(prime_rate_changes)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HybridMortgage {
+    components: Vec<HybridComponent>,
+}
+
+impl HybridMortgage {
+    // Every component must have the same payment frequency, since they are paid together.
+    pub fn new(components: Vec<HybridComponent>) -> Result<HybridMortgage> {
+        let payment_frequency = match components.first() {
+            Some(component) => component.payment_frequency(),
+            None => return Err(MortgageError::MissingField("components")),
+        };
+        if let Some(component) = components
+            .iter()
+            .find(|component| component.payment_frequency() != payment_frequency)
+        {
+            return Err(MortgageError::MismatchedPaymentFrequency {
+                expected: payment_frequency,
+                found: component.payment_frequency(),
+            });
+        }
+
+        Ok(HybridMortgage { components })
+    }
+
+    pub fn components(&self) -> &[HybridComponent] {
+        &self.components
+    }
+
+    pub fn principal(&self) -> Decimal {
+        self.components
+            .iter()
+            .map(|component| component.principal())
+            .sum()
+    }
+
+    // The combined regular payment at the initial rates.
+    pub fn payment(&self) -> Result<Decimal> {
+        self.components
+            .iter()
+            .map(|component| component.payment())
+            .sum()
+    }
+
+    // The components' schedules added together payment by payment, until the last of them
+    // is paid off.
+    pub fn amortization_schedule(
+        &self,
+        prime_rate_changes: &[PrimeRateChange],
+    ) -> Result<Vec<ScheduleRow>> {
+        let mut rows: Vec<ScheduleRow> = Vec::new();
+        for component in &self.components {
+            for (i, row) in component
+                .amortization_schedule(prime_rate_changes)?
+                .into_iter()
+                .enumerate()
+            {
+                match rows.get_mut(i) {
+                    Some(combined) => {
+                        combined.payment += row.payment;
+                        combined.interest += row.interest;
+                        combined.principal += row.principal;
+                        combined.prepayment += row.prepayment;
+                        combined.balance += row.balance;
+                    }
+                    // Components before this one are already paid off.
+                    None => rows.push(row),
+                }
+            }
+        }
+
+        Ok(rows)
+    }
+
+    pub fn summary(&self, prime_rate_changes: &[PrimeRateChange]) -> Result<Summary> {
+        Ok(Summary::from_schedule(
+            &self.amortization_schedule(prime_rate_changes)?,
+            self.principal(),
+            false,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use rust_decimal_macros::*;
+
+    fn fixed(principal: Decimal, frequency: PaymentFrequency) -> CanadianMortgage {
+        CanadianMortgage::new(principal, dec!(4.59), 25, frequency)
+            .unwrap()
+            .with_term(5)
+            .unwrap()
+    }
+
+    fn variable(principal: Decimal) -> VariableRateMortgage {
+        VariableRateMortgage::new(
+            principal,
+            dec!(6.45),
+            dec!(-0.90),
+            20,
+            PaymentFrequency::Monthly,
+        )
+        .unwrap()
+    }
+
+    fn hybrid() -> HybridMortgage {
+        HybridMortgage::new(vec![
+            HybridComponent::Fixed(Box::new(fixed(dec!(300000), PaymentFrequency::Monthly))),
+            HybridComponent::Variable(variable(dec!(200000))),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn payments_are_combined() {
+        let hybrid = hybrid();
+        assert_eq!(hybrid.principal(), dec!(500000));
+        assert_eq!(
+            hybrid.payment().unwrap(),
+            fixed(dec!(300000), PaymentFrequency::Monthly)
+                .payment()
+                .unwrap()
+                + variable(dec!(200000)).payment().unwrap()
+        );
+    }
+
+    #[test]
+    fn schedules_are_combined_payment_by_payment() {
+        let hybrid = hybrid();
+        let changes = [PrimeRateChange {
+            payment_number: 13,
+            prime_rate: dec!(7.45),
+        }];
+        let schedule = hybrid.amortization_schedule(&changes).unwrap();
+        let fixed = fixed(dec!(300000), PaymentFrequency::Monthly)
+            .amortization_schedule()
+            .unwrap();
+        let variable = variable(dec!(200000))
+            .amortization_schedule(&changes)
+            .unwrap();
+
+        assert_eq!(
+            schedule.len(),
+            300,
+            "until the longer component is paid off"
+        );
+        assert_eq!(
+            schedule[12].interest,
+            fixed[12].interest + variable[12].interest
+        );
+        assert_eq!(
+            schedule[12].balance,
+            fixed[12].balance + variable[12].balance
+        );
+        assert_eq!(
+            schedule[250], fixed[250],
+            "the variable component is paid off"
+        );
+
+        let summary = hybrid.summary(&changes).unwrap();
+        assert_eq!(
+            (summary.total_payments - summary.total_interest).round_dp(10),
+            dec!(500000)
+        );
+    }
+
+    #[test]
+    fn components_are_paid_together() {
+        assert!(HybridMortgage::new(vec![]).is_err());
+        assert_eq!(
+            HybridMortgage::new(vec![
+                HybridComponent::Fixed(Box::new(fixed(dec!(300000), PaymentFrequency::BiWeekly,))),
+                HybridComponent::Variable(variable(dec!(200000))),
+            ])
+            .unwrap_err(),
+            MortgageError::MismatchedPaymentFrequency {
+                expected: PaymentFrequency::BiWeekly,
+                found: PaymentFrequency::Monthly,
+            }
+        );
+    }
+}
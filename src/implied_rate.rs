@@ -0,0 +1,107 @@
+// Back out the interest rate from a lender's quote or statement.
+// The payment rises with the rate, so the monthly rate is found by bisection and then
+// converted to the semi-annually compounded rate that Canadian lenders quote.
+use crate::{Compounding, MortgageError, PaymentFrequency, Result};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+const MAX_ITERATIONS: usize = 200;
+
+// The annual interest rate, as a percentage compounded semi-annually, at which a regular payment
+// at payment_frequency pays off principal over amortization_period years.
+// It is an error if no rate between 0% and 100% gives that payment.
+pub fn implied_rate(
+    principal: Decimal,
+    payment: Decimal,
+    amortization_period: u64,
+    payment_frequency: PaymentFrequency,
+) -> Result<Decimal> {
+    if principal <= dec!(0) {
+        return Err(MortgageError::InvalidPrincipal(principal));
+    }
+    if amortization_period == 0 {
+        return Err(MortgageError::InvalidAmortization(amortization_period));
+    }
+
+    let monthly_payment = payment_frequency.monthly_equivalent(payment);
+    let months = amortization_period
+        .checked_mul(12)
+        .ok_or(MortgageError::NumericOverflow("number of payments"))?;
+    let mut low = dec!(0);
+    let mut high =
+        crate::rates::monthly_compounded_rate(dec!(100), Compounding::SemiAnnual)? / dec!(12);
+
+    // Without interest the principal is paid off in equal parts.
+    if monthly_payment <= principal / crate::decimal_from_u64(months)?
+        || monthly_payment > monthly_payment_at(principal, high, months)
+    {
+        return Err(MortgageError::InvalidPayment(payment));
+    }
+
+    for _ in 0..MAX_ITERATIONS {
+        let middle = (low + high) / dec!(2);
+        if middle == low || middle == high {
+            break;
+        }
+        if monthly_payment_at(principal, middle, months) < monthly_payment {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    let monthly_rate = (low + high) / dec!(2) * dec!(12);
+    Ok(crate::rates::convert_compounding_basis(monthly_rate, 12, 2)? * dec!(100))
+}
+
+// mortgage_payment, except that a rate so high the growth factor overflows is treated as
+// interest only, which is what the payment approaches.
+fn monthly_payment_at(principal: Decimal, rate: Decimal, months: u64) -> Decimal {
+    match (dec!(1) + rate).checked_powu(months) {
+        Some(c) => principal * rate * c / (c - dec!(1)),
+        None => principal * rate,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CanadianMortgage;
+
+    #[test]
+    fn rate_is_recovered_from_payment() {
+        for payment_frequency in PaymentFrequency::ALL.iter() {
+            let mortgage =
+                CanadianMortgage::new(dec!(430000), dec!(4.59), 25, *payment_frequency).unwrap();
+            assert_eq!(
+                implied_rate(
+                    dec!(430000),
+                    mortgage.payment().unwrap(),
+                    25,
+                    *payment_frequency
+                )
+                .unwrap()
+                .round_dp(12),
+                dec!(4.59),
+                "{:?}",
+                payment_frequency
+            );
+        }
+    }
+
+    #[test]
+    fn lender_quote_rounded_to_the_cent() {
+        let rate =
+            implied_rate(dec!(500000), dec!(2905.18), 25, PaymentFrequency::Monthly).unwrap();
+        assert_eq!(rate.round_dp(2), dec!(4.99));
+    }
+
+    #[test]
+    fn payments_without_a_rate_are_rejected() {
+        assert_eq!(
+            implied_rate(dec!(300000), dec!(1000), 25, PaymentFrequency::Monthly),
+            Err(MortgageError::InvalidPayment(dec!(1000)))
+        );
+        assert!(implied_rate(dec!(300000), dec!(300000), 25, PaymentFrequency::Monthly).is_err());
+    }
+}
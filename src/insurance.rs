@@ -0,0 +1,512 @@
+// Mortgage default insurance is required when the down payment is less than 20% of the purchase price.
+// The premium is a percentage of the mortgage amount that depends on the loan-to-value ratio,
+// and is usually added to the mortgage rather than paid up front.
+// https://www.cmhc-schl.gc.ca/consumers/home-buying/mortgage-loan-insurance-for-consumers/cmhc-mortgage-loan-insurance-cost
+// https://www.sagen.ca/products/homeownership-program/
+// https://www.canadaguaranty.ca/premium-rates/
+use crate::{CanadianMortgage, MortgageError, Province, Result};
+use alloc::vec::Vec;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// Premiums as a percentage of the loan, by maximum loan-to-value percentage. CMHC, Sagen and
+// Canada Guaranty charge the same for standard purchases, so they share this rate card. Their
+// rate cards differ slightly for other programs, like borrowed down payments and self-employed
+// borrowers, which aren't modelled yet; until they are, the insurer doesn't change any premium.
+const PREMIUMS: [(Decimal, Decimal); 6] = [
+    (dec!(65), dec!(0.60)),
+    (dec!(75), dec!(1.70)),
+    (dec!(80), dec!(2.40)),
+    (dec!(85), dec!(2.80)),
+    (dec!(90), dec!(3.10)),
+    (dec!(95), dec!(4.00)),
+];
+
+// Added to the premium when the amortization is longer than 25 years.
+const EXTENDED_AMORTIZATION_SURCHARGE: Decimal = dec!(0.20);
+
+// The three mortgage default insurers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Insurer {
+    #[default]
+    Cmhc,
+    Sagen,
+    CanadaGuaranty,
+}
+
+impl Insurer {
+    // The premium as a percentage of the loan for a loan-to-value percentage and amortization in years.
+    pub fn premium_rate(
+        &self,
+        loan_to_value: Decimal,
+        amortization_period: u64,
+    ) -> Result<Decimal> {
+        let (_, rate) = PREMIUMS
+            .iter()
+            .find(|(max_loan_to_value, _)| loan_to_value <= *max_loan_to_value)
+            .ok_or(MortgageError::Uninsurable(loan_to_value))?;
+
+        if amortization_period > 25 {
+            Ok(rate + EXTENDED_AMORTIZATION_SURCHARGE)
+        } else {
+            Ok(*rate)
+        }
+    }
+
+    // The premium in dollars for a loan against a property worth property_value.
+    pub fn premium(
+        &self,
+        loan: Decimal,
+        property_value: Decimal,
+        amortization_period: u64,
+    ) -> Result<Decimal> {
+        let rate = self.premium_rate(loan_to_value(loan, property_value)?, amortization_period)?;
+        Ok(loan * rate / dec!(100))
+    }
+}
+
+// Mortgages for more than this percentage of the property value are high-ratio.
+const MAX_CONVENTIONAL_LOAN_TO_VALUE: Decimal = dec!(80);
+
+// Homes at or above this price can't be insured, so need 20% down.
+const MAX_INSURABLE_PRICE: Decimal = dec!(1500000);
+
+// The longest insurable amortization in years, and the longest for first-time buyers and new builds.
+const MAX_INSURED_AMORTIZATION: u64 = 25;
+const MAX_EXTENDED_INSURED_AMORTIZATION: u64 = 30;
+
+// Buildings with three or four units need at least 10% down.
+const MAX_INSURABLE_UNITS: u8 = 4;
+const MULTI_UNIT_MINIMUM_DOWN_PAYMENT: Decimal = dec!(0.10);
+
+// The least that can be put down on a purchase, and whether a mortgage for the rest can be insured.
+// With less than 20% down, insurance is required.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DownPayment {
+    pub minimum: Decimal,
+    pub insurable: bool,
+}
+
+// 5% of the first $500,000 of purchase_price and 10% of the rest, or 20% of the whole price
+// from $1.5 million, where insurance isn't available.
+// https://www.canada.ca/en/financial-consumer-agency/services/mortgages/down-payment.html
+pub fn minimum_down_payment(purchase_price: Decimal) -> Result<DownPayment> {
+    if purchase_price <= dec!(0) {
+        return Err(MortgageError::InvalidPropertyValue(purchase_price));
+    }
+
+    if purchase_price >= MAX_INSURABLE_PRICE {
+        return Ok(DownPayment {
+            minimum: purchase_price * dec!(0.20),
+            insurable: false,
+        });
+    }
+
+    let first_tier = purchase_price.min(dec!(500000));
+    Ok(DownPayment {
+        minimum: first_tier * dec!(0.05) + (purchase_price - first_tier) * dec!(0.10),
+        insurable: true,
+    })
+}
+
+// A home purchase to check for insurability. units is the number of dwellings in the building.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InsuredPurchase {
+    pub purchase_price: Decimal,
+    pub down_payment: Decimal,
+    pub amortization_period: u64,
+    pub owner_occupied: bool,
+    pub units: u8,
+    pub first_time_buyer: bool,
+    pub new_build: bool,
+}
+
+// A rule that keeps a purchase from being insured.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EligibilityFailure {
+    PurchasePriceTooHigh {
+        price: Decimal,
+        limit: Decimal,
+    },
+    DownPaymentTooLow {
+        down_payment: Decimal,
+        minimum: Decimal,
+    },
+    AmortizationTooLong {
+        years: u64,
+        limit: u64,
+    },
+    NotOwnerOccupied,
+    TooManyUnits {
+        units: u8,
+        limit: u8,
+    },
+}
+
+// Every rule purchase fails, or none if it can be insured.
+pub fn eligibility(purchase: &InsuredPurchase) -> Result<Vec<EligibilityFailure>> {
+    purchase_loan_to_value(purchase.purchase_price, purchase.down_payment)?;
+    let mut failures = Vec::new();
+
+    let mut minimum = minimum_down_payment(purchase.purchase_price)?.minimum;
+    if purchase.purchase_price >= MAX_INSURABLE_PRICE {
+        failures.push(EligibilityFailure::PurchasePriceTooHigh {
+            price: purchase.purchase_price,
+            limit: MAX_INSURABLE_PRICE,
+        });
+    } else if purchase.units > 2 {
+        minimum = minimum.max(purchase.purchase_price * MULTI_UNIT_MINIMUM_DOWN_PAYMENT);
+    }
+    if purchase.down_payment < minimum {
+        failures.push(EligibilityFailure::DownPaymentTooLow {
+            down_payment: purchase.down_payment,
+            minimum,
+        });
+    }
+
+    let limit = if purchase.first_time_buyer || purchase.new_build {
+        MAX_EXTENDED_INSURED_AMORTIZATION
+    } else {
+        MAX_INSURED_AMORTIZATION
+    };
+    if purchase.amortization_period > limit {
+        failures.push(EligibilityFailure::AmortizationTooLong {
+            years: purchase.amortization_period,
+            limit,
+        });
+    }
+
+    if !purchase.owner_occupied {
+        failures.push(EligibilityFailure::NotOwnerOccupied);
+    }
+    if purchase.units == 0 || purchase.units > MAX_INSURABLE_UNITS {
+        failures.push(EligibilityFailure::TooManyUnits {
+            units: purchase.units,
+            limit: MAX_INSURABLE_UNITS,
+        });
+    }
+
+    Ok(failures)
+}
+
+// The loan as a percentage of the property value.
+pub fn loan_to_value(loan: Decimal, property_value: Decimal) -> Result<Decimal> {
+    if property_value <= dec!(0) {
+        return Err(MortgageError::InvalidPropertyValue(property_value));
+    }
+    Ok(loan / property_value * dec!(100))
+}
+
+// The loan-to-value percentage of a mortgage for the rest of purchase_price after down_payment.
+pub fn purchase_loan_to_value(purchase_price: Decimal, down_payment: Decimal) -> Result<Decimal> {
+    if down_payment < dec!(0) || down_payment > purchase_price {
+        return Err(MortgageError::InvalidDownPayment(down_payment));
+    }
+    loan_to_value(purchase_price - down_payment, purchase_price)
+}
+
+// High-ratio mortgages, for more than 80% of the property value, must be insured.
+// Conventional mortgages don't need to be, and are the only ones eligible for some products,
+// like a readvanceable mortgage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MortgageClass {
+    HighRatio,
+    Conventional,
+}
+
+impl MortgageClass {
+    pub fn from_loan_to_value(loan_to_value: Decimal) -> MortgageClass {
+        if loan_to_value > MAX_CONVENTIONAL_LOAN_TO_VALUE {
+            MortgageClass::HighRatio
+        } else {
+            MortgageClass::Conventional
+        }
+    }
+
+    pub fn requires_insurance(&self) -> bool {
+        *self == MortgageClass::HighRatio
+    }
+}
+
+// Provincial sales tax on the premium, payable at closing since it can't be added to the mortgage.
+// Only Ontario, Quebec, Saskatchewan and Manitoba charge it.
+pub fn premium_sales_tax(province: Province, premium: Decimal) -> Decimal {
+    let rate = match province {
+        Province::Ontario => dec!(8),
+        Province::Quebec => dec!(9),
+        Province::Saskatchewan => dec!(6),
+        Province::Manitoba => dec!(7),
+        _ => dec!(0),
+    };
+    premium * rate / dec!(100)
+}
+
+// The CMHC premium rate; see Insurer::premium_rate.
+pub fn premium_rate(loan_to_value: Decimal, amortization_period: u64) -> Result<Decimal> {
+    Insurer::Cmhc.premium_rate(loan_to_value, amortization_period)
+}
+
+// The CMHC premium; see Insurer::premium.
+pub fn premium(
+    loan: Decimal,
+    property_value: Decimal,
+    amortization_period: u64,
+) -> Result<Decimal> {
+    Insurer::Cmhc.premium(loan, property_value, amortization_period)
+}
+
+impl CanadianMortgage {
+    // Capitalize the CMHC default insurance premium into the principal so payments include it.
+    pub fn with_default_insurance(self, property_value: Decimal) -> Result<CanadianMortgage> {
+        self.with_default_insurance_from(Insurer::Cmhc, property_value)
+    }
+
+    // Capitalize the default insurance premium charged by insurer.
+    pub fn with_default_insurance_from(
+        mut self,
+        insurer: Insurer,
+        property_value: Decimal,
+    ) -> Result<CanadianMortgage> {
+        let premium = insurer.premium(self.principal, property_value, self.amortization_period)?;
+        self.principal += premium;
+        self.insurance_premium += premium;
+        Ok(self)
+    }
+
+    // Whether the mortgage, before any insurance premium, is high-ratio against property_value.
+    pub fn mortgage_class(&self, property_value: Decimal) -> Result<MortgageClass> {
+        let loan = self.principal - self.insurance_premium;
+        Ok(MortgageClass::from_loan_to_value(loan_to_value(
+            loan,
+            property_value,
+        )?))
+    }
+
+    // The default insurance premium included in the principal, if any.
+    pub fn insurance_premium(&self) -> Decimal {
+        self.insurance_premium
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+    use alloc::vec;
+
+    #[test]
+    fn premium_rates_follow_loan_to_value_tiers() {
+        assert_eq!(premium_rate(dec!(95), 25).unwrap(), dec!(4.00));
+        assert_eq!(premium_rate(dec!(90.01), 25).unwrap(), dec!(4.00));
+        assert_eq!(premium_rate(dec!(90), 25).unwrap(), dec!(3.10));
+        assert_eq!(premium_rate(dec!(85), 25).unwrap(), dec!(2.80));
+        assert_eq!(premium_rate(dec!(50), 25).unwrap(), dec!(0.60));
+        assert_eq!(
+            premium_rate(dec!(95), 30).unwrap(),
+            dec!(4.20),
+            "30 year amortization surcharge"
+        );
+        assert_eq!(
+            premium_rate(dec!(95.5), 25),
+            Err(MortgageError::Uninsurable(dec!(95.5)))
+        );
+    }
+
+    #[test]
+    fn insurers_share_the_standard_rate_card() {
+        for insurer in [Insurer::Cmhc, Insurer::Sagen, Insurer::CanadaGuaranty] {
+            assert_eq!(insurer.premium_rate(dec!(90), 25).unwrap(), dec!(3.10));
+            assert!(insurer.premium_rate(dec!(95.5), 25).is_err());
+        }
+        assert_eq!(
+            Insurer::Sagen.premium_rate(dec!(95), 30).unwrap(),
+            dec!(4.20)
+        );
+        assert_eq!(
+            Insurer::CanadaGuaranty
+                .premium(dec!(475000), dec!(500000), 25)
+                .unwrap(),
+            dec!(19000)
+        );
+        assert_eq!(
+            premium(dec!(475000), dec!(500000), 25).unwrap(),
+            Insurer::default()
+                .premium(dec!(475000), dec!(500000), 25)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn sales_tax_on_premiums() {
+        assert_eq!(
+            premium_sales_tax(Province::Ontario, dec!(19000)),
+            dec!(1520)
+        );
+        assert_eq!(premium_sales_tax(Province::Quebec, dec!(19000)), dec!(1710));
+        assert_eq!(
+            premium_sales_tax(Province::BritishColumbia, dec!(19000)),
+            dec!(0)
+        );
+    }
+
+    #[test]
+    fn premium_in_dollars() {
+        assert_eq!(loan_to_value(dec!(475000), dec!(500000)).unwrap(), dec!(95));
+        assert_eq!(
+            premium(dec!(475000), dec!(500000), 25).unwrap(),
+            dec!(19000)
+        );
+        assert!(loan_to_value(dec!(475000), dec!(0)).is_err());
+    }
+
+    #[test]
+    fn high_ratio_above_80_percent() {
+        assert_eq!(
+            purchase_loan_to_value(dec!(500000), dec!(25000)).unwrap(),
+            dec!(95)
+        );
+        assert!(purchase_loan_to_value(dec!(500000), dec!(500000.01)).is_err());
+        assert!(purchase_loan_to_value(dec!(500000), dec!(-1)).is_err());
+
+        assert_eq!(
+            MortgageClass::from_loan_to_value(dec!(80)),
+            MortgageClass::Conventional
+        );
+        assert_eq!(
+            MortgageClass::from_loan_to_value(dec!(80.01)),
+            MortgageClass::HighRatio
+        );
+        assert!(MortgageClass::HighRatio.requires_insurance());
+
+        let insured =
+            CanadianMortgage::new(dec!(450000), dec!(4.59), 25, PaymentFrequency::Monthly)
+                .unwrap()
+                .with_default_insurance(dec!(500000))
+                .unwrap();
+        assert_eq!(
+            insured.mortgage_class(dec!(500000)).unwrap(),
+            MortgageClass::HighRatio
+        );
+    }
+
+    #[test]
+    fn minimum_down_payment_is_tiered() {
+        assert_eq!(
+            minimum_down_payment(dec!(400000)).unwrap(),
+            DownPayment {
+                minimum: dec!(20000),
+                insurable: true
+            }
+        );
+        assert_eq!(
+            minimum_down_payment(dec!(800000)).unwrap().minimum,
+            dec!(55000),
+            "5% of 500000 and 10% of 300000"
+        );
+        assert_eq!(
+            minimum_down_payment(dec!(1500000)).unwrap(),
+            DownPayment {
+                minimum: dec!(300000),
+                insurable: false
+            }
+        );
+        assert!(minimum_down_payment(dec!(0)).is_err());
+    }
+
+    fn purchase() -> InsuredPurchase {
+        InsuredPurchase {
+            purchase_price: dec!(600000),
+            down_payment: dec!(35000),
+            amortization_period: 25,
+            owner_occupied: true,
+            units: 1,
+            first_time_buyer: false,
+            new_build: false,
+        }
+    }
+
+    #[test]
+    fn eligible_purchase_has_no_failures() {
+        assert_eq!(eligibility(&purchase()).unwrap(), vec![]);
+        assert_eq!(
+            eligibility(&InsuredPurchase {
+                amortization_period: 30,
+                first_time_buyer: true,
+                ..purchase()
+            })
+            .unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn every_failed_rule_is_listed() {
+        assert_eq!(
+            eligibility(&InsuredPurchase {
+                down_payment: dec!(30000),
+                amortization_period: 30,
+                owner_occupied: false,
+                ..purchase()
+            })
+            .unwrap(),
+            vec![
+                EligibilityFailure::DownPaymentTooLow {
+                    down_payment: dec!(30000),
+                    minimum: dec!(35000)
+                },
+                EligibilityFailure::AmortizationTooLong {
+                    years: 30,
+                    limit: 25
+                },
+                EligibilityFailure::NotOwnerOccupied,
+            ]
+        );
+        assert_eq!(
+            eligibility(&InsuredPurchase {
+                purchase_price: dec!(1500000),
+                down_payment: dec!(300000),
+                units: 5,
+                ..purchase()
+            })
+            .unwrap(),
+            vec![
+                EligibilityFailure::PurchasePriceTooHigh {
+                    price: dec!(1500000),
+                    limit: dec!(1500000)
+                },
+                EligibilityFailure::TooManyUnits { units: 5, limit: 4 },
+            ]
+        );
+        assert_eq!(
+            eligibility(&InsuredPurchase {
+                units: 3,
+                ..purchase()
+            })
+            .unwrap(),
+            vec![EligibilityFailure::DownPaymentTooLow {
+                down_payment: dec!(35000),
+                minimum: dec!(60000)
+            }],
+            "three or four units need 10% down"
+        );
+    }
+
+    #[test]
+    fn premium_is_capitalized() {
+        let insured =
+            CanadianMortgage::new(dec!(450000), dec!(4.59), 25, PaymentFrequency::Monthly)
+                .unwrap()
+                .with_default_insurance(dec!(500000))
+                .unwrap();
+        assert_eq!(insured.insurance_premium(), dec!(13950));
+
+        let equivalent =
+            CanadianMortgage::new(dec!(463950), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        assert_eq!(insured.payment().unwrap(), equivalent.payment().unwrap());
+    }
+}
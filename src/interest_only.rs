@@ -0,0 +1,65 @@
+// Interest-only mortgages, common for the mortgage portion of a HELOC and for investor loans.
+// Each payment is the interest for the period, so the principal never goes down except through
+// prepayments. The schedule runs for the term, since there is no amortization to follow.
+use crate::CanadianMortgage;
+
+impl CanadianMortgage {
+    pub fn with_interest_only(mut self, interest_only: bool) -> CanadianMortgage {
+        self.interest_only = interest_only;
+        self
+    }
+
+    pub fn interest_only(&self) -> bool {
+        self.interest_only
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CanadianMortgage, PaymentFrequency};
+    use rust_decimal_macros::*;
+
+    fn mortgage() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(400000), dec!(6), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_term(5)
+            .unwrap()
+            .with_interest_only(true)
+    }
+
+    #[test]
+    fn payment_is_the_periodic_interest() {
+        let mortgage = mortgage();
+        assert_eq!(
+            mortgage.payment().unwrap(),
+            dec!(400000) * mortgage.periodic_rate().unwrap()
+        );
+    }
+
+    #[test]
+    fn principal_never_declines() {
+        let mortgage = mortgage();
+        let schedule = mortgage.amortization_schedule().unwrap();
+
+        assert_eq!(schedule.len(), 60, "the schedule runs for the term");
+        assert!(schedule.iter().all(|row| row.principal == dec!(0)
+            && row.balance == dec!(400000)
+            && row.payment == row.interest));
+        assert_eq!(mortgage.balance_at(12).unwrap(), dec!(400000));
+        assert_eq!(mortgage.balance_at(120).unwrap(), dec!(400000));
+
+        let summary = mortgage.summary().unwrap();
+        assert!(summary.interest_only);
+        assert_eq!(summary.total_payments, summary.total_interest);
+    }
+
+    #[test]
+    fn lump_sums_reduce_the_interest() {
+        let mortgage = mortgage().with_lump_sum(12, dec!(100000)).unwrap();
+        let schedule = mortgage.amortization_schedule().unwrap();
+
+        assert_eq!(schedule[11].balance, dec!(300000));
+        assert_eq!(schedule[12].interest, schedule[0].interest * dec!(0.75));
+        assert_eq!(mortgage.balance_at(60).unwrap(), dec!(300000));
+    }
+}
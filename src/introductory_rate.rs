@@ -0,0 +1,130 @@
+// Promotional products, often paired with cash back, charge a lower introductory rate for the
+// first months of the term before the regular rate applies. Payments start out calculated at the
+// introductory rate over the full amortization, and are recalculated at the regular rate for what
+// is left of it once the introductory period ends.
+use crate::{CanadianMortgage, MortgageError, Result};
+use rust_decimal::prelude::*;
+
+// rate is an annual percentage, compounded like the regular rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IntroductoryRate {
+    pub rate: Decimal,
+    pub months: u64,
+}
+
+impl CanadianMortgage {
+    // The introductory period must end within the term.
+    pub fn with_introductory_rate(
+        mut self,
+        rate: Decimal,
+        months: u64,
+    ) -> Result<CanadianMortgage> {
+        crate::validate_rate(rate)?;
+        let term_months = self.term_years * 12;
+        if months == 0 || months > term_months {
+            return Err(MortgageError::InvalidIntroductoryPeriod {
+                months,
+                term_months,
+            });
+        }
+
+        self.introductory_rate = Some(IntroductoryRate { rate, months });
+        Ok(self)
+    }
+
+    pub fn introductory_rate(&self) -> Option<IntroductoryRate> {
+        self.introductory_rate
+    }
+
+    // The number of payments made at the introductory rate.
+    pub(crate) fn introductory_payments(&self) -> u64 {
+        self.introductory_rate.map_or(0, |introductory| {
+            introductory.months * self.payment_frequency.payments_per_year() / 12
+        })
+    }
+
+    // The annual rate charged on the first payment, as a decimal fraction compounded monthly.
+    pub(crate) fn initial_interest_rate(&self) -> Result<Decimal> {
+        match self.introductory_rate {
+            Some(introductory) => {
+                crate::rates::monthly_compounded_rate(introductory.rate, self.compounding)
+            }
+            None => Ok(self.interest_rate),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BorrowingCharges, PaymentFrequency};
+    use rust_decimal_macros::*;
+
+    fn mortgage() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_term(5)
+            .unwrap()
+    }
+
+    #[test]
+    fn payment_is_recalculated_after_the_introductory_period() {
+        let promotional = mortgage().with_introductory_rate(dec!(1.99), 12).unwrap();
+        let schedule = promotional.amortization_schedule().unwrap();
+
+        let introductory =
+            CanadianMortgage::new(dec!(430000), dec!(1.99), 25, PaymentFrequency::Monthly).unwrap();
+        assert_eq!(
+            promotional.payment().unwrap(),
+            introductory.payment().unwrap()
+        );
+        assert_eq!(
+            schedule[11].payment.round_dp(10),
+            schedule[0].payment.round_dp(10)
+        );
+        assert_eq!(
+            schedule[11].balance.round_dp(10),
+            introductory.balance_at(12).unwrap().round_dp(10)
+        );
+
+        assert!(schedule[12].payment > schedule[11].payment);
+        assert_eq!(
+            schedule[12].interest.round_dp(10),
+            (schedule[11].balance * mortgage().periodic_rate().unwrap()).round_dp(10)
+        );
+        assert_eq!(schedule.len(), 300, "still paid off over the amortization");
+        assert_eq!(
+            promotional.balance_at(12).unwrap(),
+            schedule[11].balance,
+            "the closed form doesn't apply"
+        );
+    }
+
+    #[test]
+    fn apr_reflects_both_rates() {
+        let charges = BorrowingCharges::default();
+        let apr = mortgage()
+            .with_introductory_rate(dec!(1.99), 12)
+            .unwrap()
+            .apr(&charges)
+            .unwrap();
+
+        assert!(apr < mortgage().apr(&charges).unwrap());
+        assert!(apr > dec!(1.99));
+    }
+
+    #[test]
+    fn introductory_period_is_within_the_term() {
+        assert!(mortgage().with_introductory_rate(dec!(1.99), 0).is_err());
+        assert_eq!(
+            mortgage()
+                .with_introductory_rate(dec!(1.99), 61)
+                .unwrap_err(),
+            MortgageError::InvalidIntroductoryPeriod {
+                months: 61,
+                term_months: 60
+            }
+        );
+    }
+}
@@ -0,0 +1,133 @@
+// JSON export for web front ends.
+//
+// A schedule is an array with one object per payment:
+//
+// {
+//   "payment_number": 1,
+//   "payment": "2401.50",
+//   "interest": "1629.24",
+//   "principal": "772.26",
+//   "prepayment": "0",
+//   "balance": "429227.74",
+//   "cumulative_interest": "1629.24",
+//   "cumulative_principal": "772.26"
+// }
+//
+// Amounts are strings so no precision is lost to JSON numbers; they are not rounded.
+// cumulative_principal includes prepayments. Fields may be added but will not be renamed or removed.
+use crate::{CostOfBorrowingDisclosure, MortgageError, Result, ScheduleRow};
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+pub trait ToJson {
+    fn to_json(&self) -> Result<String>;
+}
+
+#[derive(Serialize)]
+struct JsonRow {
+    payment_number: u64,
+    payment: Decimal,
+    interest: Decimal,
+    principal: Decimal,
+    prepayment: Decimal,
+    balance: Decimal,
+    cumulative_interest: Decimal,
+    cumulative_principal: Decimal,
+}
+
+impl ToJson for [ScheduleRow] {
+    fn to_json(&self) -> Result<String> {
+        let mut cumulative_interest = Decimal::ZERO;
+        let mut cumulative_principal = Decimal::ZERO;
+
+        let rows: Vec<JsonRow> = self
+            .iter()
+            .map(|row| {
+                cumulative_interest += row.interest;
+                cumulative_principal += row.principal + row.prepayment;
+                JsonRow {
+                    payment_number: row.payment_number,
+                    payment: row.payment,
+                    interest: row.interest,
+                    principal: row.principal,
+                    prepayment: row.prepayment,
+                    balance: row.balance,
+                    cumulative_interest,
+                    cumulative_principal,
+                }
+            })
+            .collect();
+
+        serde_json::to_string(&rows).map_err(|e| MortgageError::Serialization(e.to_string()))
+    }
+}
+
+// The disclosure as one object with the same field names.
+impl ToJson for CostOfBorrowingDisclosure {
+    fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| MortgageError::Serialization(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BorrowingCharges, CanadianMortgage, PaymentFrequency};
+    use rust_decimal_macros::*;
+
+    #[test]
+    fn schedule_to_json() {
+        let schedule =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly)
+                .unwrap()
+                .with_lump_sum(2, dec!(1000))
+                .unwrap()
+                .amortization_schedule()
+                .unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&schedule.to_json().unwrap()).unwrap();
+        let rows = json.as_array().unwrap();
+        assert_eq!(rows.len(), schedule.len());
+
+        let second = &rows[1];
+        assert_eq!(second["payment_number"], 2);
+        assert_eq!(second["prepayment"], "1000");
+        assert_eq!(
+            second["balance"].as_str().unwrap(),
+            schedule[1].balance.to_string()
+        );
+        assert_eq!(
+            second["cumulative_interest"].as_str().unwrap(),
+            (schedule[0].interest + schedule[1].interest).to_string()
+        );
+        let cumulative_principal: Decimal = second["cumulative_principal"]
+            .as_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(
+            cumulative_principal.round_dp(10),
+            (dec!(430000) - schedule[1].balance).round_dp(10)
+        );
+    }
+
+    #[test]
+    fn disclosure_to_json() {
+        let disclosure =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly)
+                .unwrap()
+                .with_term(5)
+                .unwrap()
+                .cost_of_borrowing_disclosure(&BorrowingCharges::default())
+                .unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&disclosure.to_json().unwrap()).unwrap();
+        assert_eq!(json["principal"], "430000");
+        assert_eq!(json["term_years"], 5);
+        assert_eq!(json["payment_frequency"], "Monthly");
+        assert_eq!(
+            json["total_cost_of_borrowing"].as_str().unwrap(),
+            disclosure.total_cost_of_borrowing.to_string()
+        );
+    }
+}
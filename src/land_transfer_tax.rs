@@ -0,0 +1,145 @@
+// Land transfer tax is charged by the province on the purchase price when the property changes hands.
+// Most provinces use marginal brackets like income tax; municipal taxes (e.g. Toronto) and first-time
+// buyer rebates are not included.
+use crate::{MortgageError, Province, Result};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// Brackets are (lower bound, marginal rate as a percentage).
+type Brackets = [(Decimal, Decimal)];
+
+// https://www.ontario.ca/document/land-transfer-tax/calculating-land-transfer-tax
+const ONTARIO: [(Decimal, Decimal); 5] = [
+    (dec!(0), dec!(0.5)),
+    (dec!(55000), dec!(1.0)),
+    (dec!(250000), dec!(1.5)),
+    (dec!(400000), dec!(2.0)),
+    (dec!(2000000), dec!(2.5)),
+];
+
+// https://www2.gov.bc.ca/gov/content/taxes/property-taxes/property-transfer-tax
+const BRITISH_COLUMBIA: [(Decimal, Decimal); 4] = [
+    (dec!(0), dec!(1)),
+    (dec!(200000), dec!(2)),
+    (dec!(2000000), dec!(3)),
+    (dec!(3000000), dec!(5)),
+];
+
+// The "welcome tax" with the 2024 provincial default thresholds; some municipalities such as
+// Montreal add higher brackets.
+const QUEBEC: [(Decimal, Decimal); 3] = [
+    (dec!(0), dec!(0.5)),
+    (dec!(58900), dec!(1.0)),
+    (dec!(294600), dec!(1.5)),
+];
+
+// https://www.gov.mb.ca/finance/taxation/taxes/landtransfer.html
+const MANITOBA: [(Decimal, Decimal); 5] = [
+    (dec!(0), dec!(0)),
+    (dec!(30000), dec!(0.5)),
+    (dec!(90000), dec!(1.0)),
+    (dec!(150000), dec!(1.5)),
+    (dec!(200000), dec!(2.0)),
+];
+
+const NEW_BRUNSWICK: [(Decimal, Decimal); 1] = [(dec!(0), dec!(1))];
+
+// Properties of $30,000 or less are exempt.
+const PRINCE_EDWARD_ISLAND: [(Decimal, Decimal); 1] = [(dec!(0), dec!(1))];
+const PRINCE_EDWARD_ISLAND_EXEMPTION: Decimal = dec!(30000);
+
+// The land transfer tax owed on a property bought for purchase_price.
+// Alberta and Saskatchewan only charge registration fees, so they owe no tax.
+pub fn land_transfer_tax(province: Province, purchase_price: Decimal) -> Result<Decimal> {
+    if purchase_price <= dec!(0) {
+        return Err(MortgageError::InvalidPropertyValue(purchase_price));
+    }
+
+    match province {
+        Province::Ontario => bracketed_tax(&ONTARIO, purchase_price),
+        Province::BritishColumbia => bracketed_tax(&BRITISH_COLUMBIA, purchase_price),
+        Province::Quebec => bracketed_tax(&QUEBEC, purchase_price),
+        Province::Manitoba => bracketed_tax(&MANITOBA, purchase_price),
+        Province::NewBrunswick => bracketed_tax(&NEW_BRUNSWICK, purchase_price),
+        Province::PrinceEdwardIsland if purchase_price <= PRINCE_EDWARD_ISLAND_EXEMPTION => {
+            Ok(dec!(0))
+        }
+        Province::PrinceEdwardIsland => bracketed_tax(&PRINCE_EDWARD_ISLAND, purchase_price),
+        Province::Alberta | Province::Saskatchewan => Ok(dec!(0)),
+        _ => Err(MortgageError::UnsupportedProvince(province)),
+    }
+}
+
+fn bracketed_tax(brackets: &Brackets, amount: Decimal) -> Result<Decimal> {
+    brackets
+        .iter()
+        .enumerate()
+        .try_fold(dec!(0), |tax, (i, (lower, rate))| {
+            let upper = brackets
+                .get(i + 1)
+                .map_or(amount, |(next, _)| amount.min(*next));
+            (upper - lower)
+                .max(dec!(0))
+                .checked_mul(*rate / dec!(100))
+                .and_then(|bracket| tax.checked_add(bracket))
+        })
+        .ok_or(MortgageError::NumericOverflow("land transfer tax"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ontario() {
+        assert_eq!(
+            land_transfer_tax(Province::Ontario, dec!(500000)).unwrap(),
+            dec!(6475)
+        );
+        assert_eq!(
+            land_transfer_tax(Province::Ontario, dec!(50000)).unwrap(),
+            dec!(250)
+        );
+        assert_eq!(
+            land_transfer_tax(Province::Ontario, dec!(2500000)).unwrap(),
+            dec!(48975)
+        );
+    }
+
+    #[test]
+    fn huge_prices_do_not_overflow() {
+        assert!(land_transfer_tax(Province::Ontario, Decimal::MAX).is_ok());
+    }
+
+    #[test]
+    fn other_provinces() {
+        assert_eq!(
+            land_transfer_tax(Province::BritishColumbia, dec!(500000)).unwrap(),
+            dec!(8000)
+        );
+        assert_eq!(
+            land_transfer_tax(Province::Quebec, dec!(500000)).unwrap(),
+            dec!(5732.50)
+        );
+        assert_eq!(
+            land_transfer_tax(Province::Manitoba, dec!(300000)).unwrap(),
+            dec!(3650)
+        );
+        assert_eq!(
+            land_transfer_tax(Province::NewBrunswick, dec!(300000)).unwrap(),
+            dec!(3000)
+        );
+        assert_eq!(
+            land_transfer_tax(Province::PrinceEdwardIsland, dec!(25000)).unwrap(),
+            dec!(0)
+        );
+        assert_eq!(
+            land_transfer_tax(Province::Alberta, dec!(500000)).unwrap(),
+            dec!(0)
+        );
+        assert_eq!(
+            land_transfer_tax(Province::NovaScotia, dec!(500000)),
+            Err(MortgageError::UnsupportedProvince(Province::NovaScotia))
+        );
+    }
+}
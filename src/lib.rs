@@ -1,5 +1,169 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
 use rust_decimal::prelude::*;
 use rust_decimal_macros::*;
+
+mod acceleration;
+pub mod affordability;
+#[cfg(feature = "chrono")]
+mod backtest;
+mod balloon;
+pub mod blend;
+mod builder;
+#[cfg(feature = "chrono")]
+mod calendar;
+mod cash_back;
+mod cents;
+mod chart;
+pub mod closing_costs;
+mod comparison;
+mod compounding;
+#[cfg(feature = "chrono")]
+mod construction;
+mod conversion;
+mod cost_of_borrowing;
+#[cfg(feature = "csv")]
+mod csv_export;
+mod day_count;
+mod deferral;
+mod error;
+#[cfg(feature = "fast-math")]
+mod fast_math;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fixed_vs_variable;
+#[cfg(feature = "chrono")]
+mod heloc;
+#[cfg(feature = "chrono")]
+mod holidays;
+mod hybrid;
+pub mod implied_rate;
+pub mod insurance;
+mod interest_only;
+mod introductory_rate;
+#[cfg(feature = "json")]
+mod json;
+pub mod land_transfer_tax;
+mod locale;
+mod markdown;
+#[cfg(feature = "uniffi")]
+mod mobile;
+mod mortgage_type;
+pub mod numeric;
+mod offers;
+mod parallel;
+pub mod parse;
+mod payment_override;
+pub mod payoff;
+#[cfg(feature = "pdf")]
+mod pdf;
+pub mod penalty;
+mod prepayment;
+mod province;
+#[cfg(feature = "python")]
+mod python;
+mod rate_change;
+#[cfg(feature = "chrono")]
+mod rate_history;
+#[cfg(feature = "chrono")]
+mod rate_hold;
+pub mod rates;
+mod readvanceable;
+mod recast;
+mod reverse_mortgage;
+mod rounding;
+mod scenario;
+#[cfg(any(feature = "toml", feature = "yaml"))]
+pub mod scenario_file;
+mod schedule;
+mod second_mortgage;
+mod sensitivity;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(feature = "rand")]
+pub mod simulation;
+mod skip_payment;
+mod smith_maneuver;
+mod stress_test;
+mod summary;
+mod table;
+mod term;
+mod trigger_point;
+pub mod us;
+#[cfg(feature = "reqwest")]
+pub mod valet;
+mod variable;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "xlsx")]
+mod xlsx_export;
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
+
+#[cfg(feature = "chrono")]
+pub use backtest::{Backtest, BacktestReport, BacktestRun};
+pub use builder::MortgageBuilder;
+#[cfg(feature = "chrono")]
+pub use calendar::{DatedScheduleRow, InterestAdjustment};
+pub use cents::{Cents, CentsScheduleRow};
+pub use chart::ChartSeries;
+pub use comparison::{compare, ScenarioComparison, ScenarioOutcome, YearDelta};
+pub use compounding::Compounding;
+#[cfg(feature = "chrono")]
+pub use construction::{ConstructionMortgage, Draw};
+pub use cost_of_borrowing::{BorrowingCharges, CostOfBorrowingDisclosure};
+#[cfg(feature = "csv")]
+pub use csv_export::ToCsv;
+pub use day_count::DayCount;
+pub use deferral::{DeferralImpact, DeferralOutcome, PaymentDeferral};
+pub use error::{MortgageError, Result};
+pub use fixed_vs_variable::{compare_fixed_variable, FixedVsVariable};
+#[cfg(feature = "chrono")]
+pub use heloc::{Heloc, HelocStatement, HelocTransaction};
+#[cfg(feature = "chrono")]
+pub use holidays::{HolidayCalendar, RollConvention};
+pub use hybrid::{HybridComponent, HybridMortgage};
+pub use introductory_rate::IntroductoryRate;
+#[cfg(feature = "json")]
+pub use json::ToJson;
+pub use locale::{Locale, Localized};
+pub use markdown::ToMarkdown;
+pub use mortgage_type::{MortgageType, PrepaymentPrivileges, PrepaymentYear};
+pub use offers::{Offer, OfferCost};
+pub use payment_override::PaymentOverride;
+pub use prepayment::{LumpSum, PaymentIncrease, PrepaymentSavings};
+pub use province::Province;
+pub use rate_change::RateChange;
+#[cfg(feature = "chrono")]
+pub use rate_history::{HistoricalRate, HistoricalReplay, RateHistory};
+#[cfg(feature = "chrono")]
+pub use rate_hold::{RateHold, RateHoldValue};
+pub use readvanceable::{ReadvanceableMortgage, ReadvanceableRow};
+pub use reverse_mortgage::{ReverseMortgage, ReverseMortgageRow};
+pub use rounding::RoundingPolicy;
+pub use scenario::{PrepaymentScenario, Scenario, ScenarioReport, ScheduleDelta};
+pub use schedule::{FinalPayment, ScheduleIter, ScheduleRow};
+pub use second_mortgage::StackedMortgage;
+pub use sensitivity::{SensitivityGrid, SensitivityTable};
+pub use skip_payment::SkippedPaymentCost;
+pub use smith_maneuver::{AnnualInterest, SmithManeuverRow};
+pub use summary::Summary;
+pub use table::{Column, Money, ScheduleTable};
+pub use trigger_point::TriggerCure;
+pub use variable::{PrimeRateChange, VariablePayment, VariableRateMortgage};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", wasm_bindgen::prelude::wasm_bindgen)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
 pub enum PaymentFrequency {
     Monthly,
     SemiMonthly,
@@ -9,10 +173,127 @@ pub enum PaymentFrequency {
     AcceleratedWeekly,
 }
 
+impl fmt::Display for PaymentFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(Locale::EnCa.frequency(*self))
+    }
+}
+
+// Parses the frequencies as the command line spells them, "accelerated-biweekly", as well as
+// they are displayed. Case, spaces, hyphens and underscores don't matter, so "Bi-Weekly" and
+// "SEMI_MONTHLY" are accepted too.
+impl FromStr for PaymentFrequency {
+    type Err = MortgageError;
+
+    fn from_str(frequency: &str) -> Result<PaymentFrequency> {
+        let normalized: alloc::string::String = frequency
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '_'))
+            .flat_map(char::to_lowercase)
+            .collect();
+        match normalized.as_str() {
+            "monthly" => Ok(PaymentFrequency::Monthly),
+            "semimonthly" => Ok(PaymentFrequency::SemiMonthly),
+            "biweekly" => Ok(PaymentFrequency::BiWeekly),
+            "acceleratedbiweekly" => Ok(PaymentFrequency::AcceleratedBiWeekly),
+            "weekly" => Ok(PaymentFrequency::Weekly),
+            "acceleratedweekly" => Ok(PaymentFrequency::AcceleratedWeekly),
+            _ => Err(MortgageError::UnknownPaymentFrequency(frequency.into())),
+        }
+    }
+}
+
+impl TryFrom<&str> for PaymentFrequency {
+    type Error = MortgageError;
+
+    fn try_from(frequency: &str) -> Result<PaymentFrequency> {
+        frequency.parse()
+    }
+}
+
+impl PaymentFrequency {
+    pub const ALL: [PaymentFrequency; 6] = [
+        PaymentFrequency::Monthly,
+        PaymentFrequency::SemiMonthly,
+        PaymentFrequency::BiWeekly,
+        PaymentFrequency::AcceleratedBiWeekly,
+        PaymentFrequency::Weekly,
+        PaymentFrequency::AcceleratedWeekly,
+    ];
+
+    // The number of payments made in a year, which is also the number of compounding periods per year.
+    pub fn payments_per_year(&self) -> u64 {
+        match self {
+            PaymentFrequency::Monthly => 12,
+            PaymentFrequency::SemiMonthly => 24,
+            PaymentFrequency::BiWeekly | PaymentFrequency::AcceleratedBiWeekly => 26,
+            PaymentFrequency::Weekly | PaymentFrequency::AcceleratedWeekly => 52,
+        }
+    }
+
+    // Payments at every frequency are derived from the monthly payment.
+    // Accelerated payments split the monthly payment, the others spread a year of monthly payments evenly.
+    fn scale_monthly_payment(&self, monthly_payment: Decimal) -> Decimal {
+        match self {
+            PaymentFrequency::Monthly => monthly_payment,
+            PaymentFrequency::SemiMonthly => monthly_payment / dec!(2),
+            PaymentFrequency::BiWeekly => monthly_payment * dec!(12) / dec!(26),
+            PaymentFrequency::AcceleratedBiWeekly => monthly_payment / dec!(2),
+            PaymentFrequency::Weekly => monthly_payment * dec!(12) / dec!(52),
+            PaymentFrequency::AcceleratedWeekly => monthly_payment / dec!(4),
+        }
+    }
+
+    // The monthly payment that payment at this frequency is derived from.
+    fn monthly_equivalent(&self, payment: Decimal) -> Decimal {
+        match self {
+            PaymentFrequency::Monthly => payment,
+            PaymentFrequency::SemiMonthly => payment * dec!(2),
+            PaymentFrequency::BiWeekly => payment * dec!(26) / dec!(12),
+            PaymentFrequency::AcceleratedBiWeekly => payment * dec!(2),
+            PaymentFrequency::Weekly => payment * dec!(52) / dec!(12),
+            PaymentFrequency::AcceleratedWeekly => payment * dec!(4),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        into = "serialization::MortgageParameters",
+        try_from = "serialization::MortgageParameters"
+    )
+)]
 pub struct CanadianMortgage {
+    principal: Decimal,
+    annual_rate: Decimal,
     interest_rate: Decimal,
     amortization_period: u64,
+    term_years: u64,
     payment_frequency: PaymentFrequency,
+    qualifying_benchmark: Decimal,
+    insurance_premium: Decimal,
+    lump_sums: Vec<LumpSum>,
+    payment_increases: Vec<PaymentIncrease>,
+    double_ups: Vec<u64>,
+    rounding: RoundingPolicy,
+    day_count: DayCount,
+    compounding: Compounding,
+    mortgage_type: MortgageType,
+    prepayment_privileges: PrepaymentPrivileges,
+    enforce_privileges: bool,
+    interest_only: bool,
+    balloon_payment: Option<u64>,
+    skipped_payments: Vec<u64>,
+    deferral: Option<PaymentDeferral>,
+    posted_rate: Option<Decimal>,
+    introductory_rate: Option<IntroductoryRate>,
+    cash_back: Option<Decimal>,
+    rate_changes: Vec<RateChange>,
+    recast: Option<u64>,
+    payment_override: Option<PaymentOverride>,
 }
 
 impl CanadianMortgage {
@@ -20,55 +301,164 @@ impl CanadianMortgage {
     // interest_rate is the annual interest rate as a percentage: 6.5% means r = 0.065 per year (see mortgage_payment below).
     // amortization_period is the number of years over which you will repay this loan.
     // payment_frequency determines the number of payments, which is also the compounding interval frequency.
+    // The term defaults to the whole amortization period; see with_term.
     pub fn new(
+        mortgage_amount: Decimal,
         interest_rate: Decimal,
         amortization_period: u64,
         payment_frequency: PaymentFrequency,
-    ) -> anyhow::Result<CanadianMortgage> {
-        if interest_rate < dec!(0.0) || interest_rate > dec!(100.0) {
-            anyhow::anyhow!("interest rate is the annual interest rate be between 0% and 100%");
+    ) -> Result<CanadianMortgage> {
+        if mortgage_amount <= dec!(0) {
+            return Err(MortgageError::InvalidPrincipal(mortgage_amount));
         }
 
-        // Convert the interest rate percentage to a decimal fraction
-        let interest_rate = interest_rate / dec!(100);
-
-        // Convert from an annual rate compounded semi-annually to an rate compounded monthly.
-        // This is the strangeness of Canadian mortgages.
-        let interest_rate = convert_compounding_basis(interest_rate, 2, 12)?;
+        if amortization_period == 0 {
+            return Err(MortgageError::InvalidAmortization(amortization_period));
+        }
+        // Every count of payments is at most the amortization period in payments, so a mortgage
+        // whose count fits never overflows counting them.
+        if amortization_period
+            .checked_mul(payment_frequency.payments_per_year())
+            .is_none()
+        {
+            return Err(MortgageError::NumericOverflow("number of payments"));
+        }
 
         Ok(CanadianMortgage {
-            interest_rate: interest_rate,
-            amortization_period: amortization_period,
+            principal: mortgage_amount,
+            annual_rate: interest_rate,
+            interest_rate: rates::monthly_compounded_rate(interest_rate, Compounding::SemiAnnual)?,
+            amortization_period,
+            term_years: amortization_period,
             payment_frequency,
+            qualifying_benchmark: stress_test::DEFAULT_BENCHMARK_RATE,
+            insurance_premium: dec!(0),
+            lump_sums: Vec::new(),
+            payment_increases: Vec::new(),
+            double_ups: Vec::new(),
+            rounding: RoundingPolicy::Unrounded,
+            day_count: DayCount::Actual365,
+            compounding: Compounding::SemiAnnual,
+            mortgage_type: MortgageType::Closed,
+            prepayment_privileges: PrepaymentPrivileges::default(),
+            enforce_privileges: false,
+            interest_only: false,
+            balloon_payment: None,
+            skipped_payments: Vec::new(),
+            deferral: None,
+            posted_rate: None,
+            introductory_rate: None,
+            cash_back: None,
+            rate_changes: Vec::new(),
+            recast: None,
+            payment_override: None,
         })
     }
 
-    pub fn payment(&self, principal: Decimal) -> anyhow::Result<Decimal> {
+    // The first payment, at any introductory rate.
+    pub fn payment(&self) -> Result<Decimal> {
+        let interest_rate = self.initial_interest_rate()?;
+        if self.interest_only {
+            return self
+                .principal
+                .checked_mul(rates::periodic_rate(interest_rate, self.payment_frequency)?)
+                .map(|payment| self.rounding.round_payment(payment))
+                .ok_or(MortgageError::NumericOverflow("payment"));
+        }
+
         let monthly_payment = mortgage_payment(
-            principal,
-            self.interest_rate / dec!(12),
-            self.amortization_period * 12,
+            self.principal,
+            interest_rate / dec!(12),
+            self.amortization_months()?,
         )?;
 
-        let payment = match self.payment_frequency {
-            PaymentFrequency::Monthly => monthly_payment,
-            PaymentFrequency::SemiMonthly => monthly_payment / dec!(2),
-            PaymentFrequency::BiWeekly => monthly_payment * dec!(12) / dec!(26),
-            PaymentFrequency::AcceleratedBiWeekly => monthly_payment / dec!(2),
-            PaymentFrequency::Weekly => monthly_payment * dec!(12) / dec!(52),
-            PaymentFrequency::AcceleratedWeekly => monthly_payment / dec!(4),
-        };
-
-        Ok(payment)
+        Ok(self.rounding.round_payment(
+            self.payment_frequency
+                .scale_monthly_payment(monthly_payment),
+        ))
     }
 
-    pub fn affordability(&self, payment: Decimal) -> anyhow::Result<Decimal> {
-        Ok(affordability(
+    pub fn affordability(&self, payment: Decimal) -> Result<Decimal> {
+        affordability(
             payment,
             self.interest_rate / dec!(12),
-            self.amortization_period * 12,
-        )?)
+            self.amortization_months()?,
+        )
+    }
+
+    fn amortization_months(&self) -> Result<u64> {
+        self.amortization_period
+            .checked_mul(12)
+            .ok_or(MortgageError::NumericOverflow("number of payments"))
+    }
+
+    // The same mortgage at a different annual interest rate.
+    pub(crate) fn with_annual_rate(&self, interest_rate: Decimal) -> Result<CanadianMortgage> {
+        let mut mortgage = self.clone();
+        mortgage.introductory_rate = None;
+        mortgage.rate_changes.clear();
+        mortgage.annual_rate = interest_rate;
+        mortgage.interest_rate = rates::monthly_compounded_rate(interest_rate, self.compounding)?;
+        Ok(mortgage)
+    }
+
+    // The principal, including any capitalized insurance premium.
+    pub fn principal(&self) -> Decimal {
+        self.principal
+    }
+
+    // The annual interest rate as a percentage, as quoted; see compounding.
+    pub fn nominal_rate(&self) -> Decimal {
+        self.annual_rate
+    }
+
+    // The annual interest rate as a percentage, compounded annually.
+    pub fn effective_rate(&self) -> Result<Decimal> {
+        rates::effective(self.annual_rate, self.compounding.periods_per_year())
+    }
+
+    pub fn amortization_period(&self) -> u64 {
+        self.amortization_period
+    }
+
+    pub fn payment_frequency(&self) -> PaymentFrequency {
+        self.payment_frequency
     }
+
+    // The interest rate applied to the balance for each payment period, as a fraction.
+    // Monthly payments use the converted monthly rate directly; other frequencies
+    // convert it to the equivalent rate compounded once per payment.
+    pub fn periodic_rate(&self) -> Result<Decimal> {
+        rates::periodic_rate(self.interest_rate, self.payment_frequency)
+    }
+}
+
+// e.g. 500000.00 at 4.59% for a 5 year term, amortized over 25 years with monthly payments
+// followed by ", interest only" for an interest-only mortgage.
+impl fmt::Display for CanadianMortgage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:.2} at {}% for a {} year term, amortized over {} years with {} payments",
+            self.principal.round_dp(2),
+            self.annual_rate,
+            self.term_years,
+            self.amortization_period,
+            self.payment_frequency
+        )?;
+        if self.interest_only {
+            write!(f, ", interest only")?;
+        }
+        Ok(())
+    }
+}
+
+// Annual interest rates are percentages between 0% and 100%.
+fn validate_rate(interest_rate: Decimal) -> Result<()> {
+    if interest_rate < dec!(0.0) || interest_rate > dec!(100.0) {
+        return Err(MortgageError::InvalidRate(interest_rate));
+    }
+    Ok(())
 }
 
 // https://en.wikipedia.org/wiki/Mortgage_loan
@@ -78,9 +468,10 @@ impl CanadianMortgage {
 // p is the principal amount borrowed
 // r is the rate of interest expressed as a fraction; for a monthly payment, take the annual rate divided by 12
 // n is the number of payments; for monthly payments over 30 years, 12 months x 30 years = 360 payments.
-fn mortgage_payment(p: Decimal, r: Decimal, n: u64) -> anyhow::Result<Decimal> {
-    let c = (dec!(1.0) + r).powi(n);
-    Ok(p * r * c / (c - dec!(1.0)))
+// At a zero rate the principal is simply split evenly over the payments.
+// It is an error for (1 + r)**n to overflow, as it can with a high rate over many payments.
+fn mortgage_payment(p: Decimal, r: Decimal, n: u64) -> Result<Decimal> {
+    numeric::payment(p, r, n).ok_or(MortgageError::NumericOverflow("payment"))
 }
 
 // p = a * ((1 + r)**n - 1) / r / (1 + r)**n
@@ -88,63 +479,24 @@ fn mortgage_payment(p: Decimal, r: Decimal, n: u64) -> anyhow::Result<Decimal> {
 // p is the principal amount borrowed
 // r is the rate of interest expressed as a fraction; for a monthly payment, take the annual rate divided by 12
 // n is the number of payments; for monthly payments over 30 years, 12 months x 30 years = 360 payments.
-fn affordability(a: Decimal, r: Decimal, n: u64) -> anyhow::Result<Decimal> {
-    let c = (dec!(1.0) + r).powi(n);
-    Ok(a * (c - dec!(1.0)) / r / c)
-}
-
-// https://en.wikipedia.org/wiki/Compound_interest#Compounding_basis
-// r2 = ((1 + r1/n1) ** (n1/n2) - 1) * n2
-// where r1 is the interest rate with compounding frequency n1, and r2 is the interest rate with compounding frequency n2
-fn convert_compounding_basis(
-    rate: Decimal,
-    compounding_frequency1: u64,
-    compounding_frequency2: u64,
-) -> anyhow::Result<Decimal> {
-    let n1 = Decimal::from_u64(compounding_frequency1).ok_or_else(|| {
-        anyhow::anyhow!(
-            "could not convert u64 to Decimal: {}",
-            compounding_frequency1
-        )
-    })?;
-
-    let n2 = Decimal::from_u64(compounding_frequency2).ok_or_else(|| {
-        anyhow::anyhow!(
-            "could not convert u64 to Decimal: {}",
-            compounding_frequency2
-        )
-    })?;
-
-    Ok((fractional_exponent(dec!(1) + (rate / n1), n1 / n2)? - dec!(1)) * n2)
+fn affordability(a: Decimal, r: Decimal, n: u64) -> Result<Decimal> {
+    numeric::present_value(a, r, n).ok_or(MortgageError::NumericOverflow("affordability"))
 }
 
-fn fractional_exponent(base: Decimal, exponent: Decimal) -> anyhow::Result<Decimal> {
-    let base = base
-        .to_f64()
-        .ok_or_else(|| anyhow::anyhow!("could not convert Decimal to f64: {}", base))?;
-
-    let exponent = exponent
-        .to_f64()
-        .ok_or_else(|| anyhow::anyhow!("could not convert Decimal to f64: {}", exponent))?;
-
-    Ok(Decimal::from_f64(base.powf(exponent))
-        .ok_or_else(|| anyhow::anyhow!("could not convert from f64 to Decimal"))?)
+fn decimal_from_u64(n: u64) -> Result<Decimal> {
+    Decimal::from_u64(n)
+        .ok_or_else(|| MortgageError::ConversionFailure(format!("u64 to Decimal: {}", n)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::string::ToString;
     #[test]
-    fn check_compounding_basis() {
-        assert_eq!(
-            convert_compounding_basis(dec!(0.06), 2, 1).unwrap(),
-            dec!(0.0609),
-            "Rate for Canadian mortages"
-        );
+    fn zero_rate_splits_the_principal() {
         assert_eq!(
-            convert_compounding_basis(dec!(0.06), 2, 12).unwrap(),
-            dec!(0.059263464374364),
-            "equivalent rate compounded monthly"
+            mortgage_payment(dec!(300000), dec!(0), 300).unwrap(),
+            dec!(1000)
         );
     }
 
@@ -152,73 +504,179 @@ mod tests {
     fn mortgage_is_valid() {
         assert_eq!(
             mortgage_payment(dec!(10000000.0), dec!(0.105) / dec!(12), 10 * 12).unwrap(),
-            dec!(134934.99677554698793630975554),
+            dec!(134934.99677554698793630975556),
             "big mortgage"
         );
         assert_eq!(
             mortgage_payment(dec!(200000), dec!(0.065) / dec!(12), 30 * 12).unwrap(),
-            dec!(1264.136046985927464091663357),
+            dec!(1264.1360469859274640916633562),
             "big mortgage"
         );
     }
 
     #[test]
+    #[cfg_attr(feature = "fast-math", ignore = "exact to Decimal precision")]
     fn canadian_mortgage_payments_are_valid() {
         assert_eq!(
-            CanadianMortgage::new(dec!(4.59), 25, PaymentFrequency::AcceleratedWeekly,)
-                .unwrap()
-                .payment(dec!(430000.0))
-                .unwrap(),
-            dec!(600.37384132280845354662242562),
+            CanadianMortgage::new(
+                dec!(430000.0),
+                dec!(4.59),
+                25,
+                PaymentFrequency::AcceleratedWeekly
+            )
+            .unwrap()
+            .payment()
+            .unwrap(),
+            dec!(600.3738413227897614395900342),
             "old Canadian mortgage, accelerated weekly payments"
         );
 
         assert_eq!(
-            CanadianMortgage::new(dec!(4.59), 25, PaymentFrequency::AcceleratedBiWeekly,)
-                .unwrap()
-                .payment(dec!(430000.0))
-                .unwrap(),
-            dec!(1200.7476826456169070932448512),
+            CanadianMortgage::new(
+                dec!(430000.0),
+                dec!(4.59),
+                25,
+                PaymentFrequency::AcceleratedBiWeekly
+            )
+            .unwrap()
+            .payment()
+            .unwrap(),
+            dec!(1200.7476826455795228791800684),
             "old Canadian mortgage, accelerated weekly payments"
         );
 
         assert_eq!(
-            CanadianMortgage::new(dec!(4.59), 25, PaymentFrequency::Monthly)
+            CanadianMortgage::new(dec!(430000.0), dec!(4.59), 25, PaymentFrequency::Monthly)
                 .unwrap()
-                .payment(dec!(430000.0))
+                .payment()
                 .unwrap(),
-            dec!(2401.4953652912338141864897025),
+            dec!(2401.4953652911590457583601368),
             "old Canadian mortgage"
         );
 
         assert_eq!(
-            CanadianMortgage::new(dec!(6), 25, PaymentFrequency::Monthly)
+            CanadianMortgage::new(dec!(100000.0), dec!(6), 25, PaymentFrequency::Monthly)
                 .unwrap()
-                .payment(dec!(100000.0))
+                .payment()
                 .unwrap(),
-            dec!(639.80662367674280200695111231),
+            dec!(639.80662367674122365802332589),
             "tiny Canadian mortgage"
         );
 
         assert_eq!(
-            CanadianMortgage::new(dec!(5), 25, PaymentFrequency::Monthly)
+            CanadianMortgage::new(dec!(100000.0), dec!(5), 25, PaymentFrequency::Monthly)
                 .unwrap()
-                .payment(dec!(100000.0))
+                .payment()
                 .unwrap(),
-            dec!(581.60498503699913800017437566),
+            dec!(581.60498503701806350860773241),
             "small Canadian mortgage"
         );
     }
 
     #[test]
+    fn invalid_mortgages_are_rejected() {
+        assert_eq!(
+            CanadianMortgage::new(dec!(100000), dec!(-1), 25, PaymentFrequency::Monthly).err(),
+            Some(MortgageError::InvalidRate(dec!(-1)))
+        );
+        assert_eq!(
+            CanadianMortgage::new(dec!(100000), dec!(101), 25, PaymentFrequency::Monthly).err(),
+            Some(MortgageError::InvalidRate(dec!(101)))
+        );
+        assert_eq!(
+            CanadianMortgage::new(dec!(100000), dec!(5), 0, PaymentFrequency::Monthly).err(),
+            Some(MortgageError::InvalidAmortization(0))
+        );
+        assert_eq!(
+            CanadianMortgage::new(dec!(0), dec!(5), 25, PaymentFrequency::Monthly).err(),
+            Some(MortgageError::InvalidPrincipal(dec!(0)))
+        );
+    }
+
+    #[test]
+    fn mortgage_can_be_inspected() {
+        let mortgage = CanadianMortgage::new(
+            dec!(500000),
+            dec!(6),
+            25,
+            PaymentFrequency::AcceleratedBiWeekly,
+        )
+        .unwrap()
+        .with_term(5)
+        .unwrap();
+
+        assert_eq!(mortgage.principal(), dec!(500000));
+        assert_eq!(mortgage.nominal_rate(), dec!(6));
+        assert_eq!(mortgage.effective_rate().unwrap(), dec!(6.09));
+        assert_eq!(mortgage.amortization_period(), 25);
+        assert_eq!(
+            mortgage.payment_frequency(),
+            PaymentFrequency::AcceleratedBiWeekly
+        );
+        assert_eq!(
+            mortgage.periodic_rate().unwrap(),
+            rates::semi_annual_to_periodic(dec!(6), PaymentFrequency::AcceleratedBiWeekly).unwrap()
+        );
+        assert_eq!(
+            mortgage.to_string(),
+            "500000.00 at 6% for a 5 year term, amortized over 25 years with accelerated biweekly payments"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "fast-math", ignore = "exact to Decimal precision")]
     fn affordability_is_valid() {
-        let mortgage = CanadianMortgage::new(dec!(1.79), 30, PaymentFrequency::Monthly).unwrap();
+        let mortgage =
+            CanadianMortgage::new(dec!(850000), dec!(1.79), 30, PaymentFrequency::Monthly).unwrap();
         assert_eq!(
             mortgage
-                .affordability(dec!(3050.4832853902146098484828448))
+                .affordability(dec!(3050.4832853900661002815562788))
                 .unwrap(),
             dec!(850000),
             "madness"
         );
     }
+
+    #[test]
+    fn overflow_is_an_error() {
+        let mortgage =
+            CanadianMortgage::new(dec!(500000), dec!(99), 100, PaymentFrequency::Weekly).unwrap();
+        assert_eq!(
+            mortgage.payment(),
+            Err(MortgageError::NumericOverflow("payment"))
+        );
+        assert!(mortgage.amortization_schedule().is_err());
+        assert!(mortgage.balance_at(5200).is_err());
+        assert!(mortgage.affordability(dec!(1000)).is_err());
+        assert_eq!(
+            CanadianMortgage::new(
+                dec!(500000),
+                dec!(5),
+                u64::MAX / 12,
+                PaymentFrequency::Weekly
+            )
+            .unwrap_err(),
+            MortgageError::NumericOverflow("number of payments")
+        );
+    }
+
+    #[test]
+    fn frequencies_are_parsed() {
+        assert_eq!(
+            "accelerated-biweekly".parse(),
+            Ok(PaymentFrequency::AcceleratedBiWeekly)
+        );
+        assert_eq!(
+            PaymentFrequency::try_from("Semi_Monthly"),
+            Ok(PaymentFrequency::SemiMonthly)
+        );
+        assert_eq!("bi-weekly".parse(), Ok(PaymentFrequency::BiWeekly));
+        for frequency in PaymentFrequency::ALL.iter() {
+            assert_eq!(frequency.to_string().parse(), Ok(*frequency));
+        }
+        assert_eq!(
+            "fortnightly".parse::<PaymentFrequency>(),
+            Err(MortgageError::UnknownPaymentFrequency("fortnightly".into()))
+        );
+    }
 }
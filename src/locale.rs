@@ -0,0 +1,217 @@
+// Canadian English and French conventions for the text output in table.rs. In French amounts are
+// written 430 000,00 $, with non-breaking spaces grouping the digits and before the dollar sign,
+// and rates 4,59 %.
+use crate::{MortgageError, PaymentFrequency, Result};
+use alloc::format;
+use alloc::string::String;
+use core::str::FromStr;
+use rust_decimal::prelude::*;
+
+const NO_BREAK_SPACE: char = '\u{a0}';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Locale {
+    #[default]
+    EnCa,
+    FrCa,
+}
+
+// A value to be displayed in locale, from localized on the value.
+#[derive(Debug, Clone, Copy)]
+pub struct Localized<'a, T> {
+    pub(crate) value: &'a T,
+    pub(crate) locale: Locale,
+}
+
+impl Locale {
+    // amount rounded half to even to the cent: $430,000.00 or 430 000,00 $.
+    pub fn money(&self, amount: Decimal) -> String {
+        let cents = amount.round_dp_with_strategy(2, RoundingStrategy::MidpointNearestEven);
+        let digits = format!("{:.2}", cents.abs());
+        let (whole, fraction) = digits.split_at(digits.len() - 3);
+        let grouped = self.group(whole);
+        let sign = if cents < Decimal::ZERO { "-" } else { "" };
+
+        match self {
+            Locale::EnCa => format!("{}${}.{}", sign, grouped, &fraction[1..]),
+            Locale::FrCa => format!("{}{},{}{}$", sign, grouped, &fraction[1..], NO_BREAK_SPACE),
+        }
+    }
+
+    // value rounded to decimal_places, with the locale's decimal separator and no grouping.
+    pub fn number(&self, value: Decimal, decimal_places: u32) -> String {
+        let text = format!(
+            "{:.*}",
+            decimal_places as usize,
+            value.round_dp(decimal_places)
+        );
+        match self {
+            Locale::EnCa => text,
+            Locale::FrCa => text.replace('.', ","),
+        }
+    }
+
+    // A rate given as a percentage, without trailing zeros: 4.59% or 4,59 %.
+    pub fn percent(&self, rate: Decimal) -> String {
+        let rate = rate.normalize();
+        let places = rate.scale();
+        match self {
+            Locale::EnCa => format!("{}%", self.number(rate, places)),
+            Locale::FrCa => format!("{}{}%", self.number(rate, places), NO_BREAK_SPACE),
+        }
+    }
+
+    pub fn frequency(&self, frequency: PaymentFrequency) -> &'static str {
+        match (self, frequency) {
+            (Locale::EnCa, PaymentFrequency::Monthly) => "monthly",
+            (Locale::EnCa, PaymentFrequency::SemiMonthly) => "semi-monthly",
+            (Locale::EnCa, PaymentFrequency::BiWeekly) => "biweekly",
+            (Locale::EnCa, PaymentFrequency::AcceleratedBiWeekly) => "accelerated biweekly",
+            (Locale::EnCa, PaymentFrequency::Weekly) => "weekly",
+            (Locale::EnCa, PaymentFrequency::AcceleratedWeekly) => "accelerated weekly",
+            (Locale::FrCa, PaymentFrequency::Monthly) => "mensuelle",
+            (Locale::FrCa, PaymentFrequency::SemiMonthly) => "bimensuelle",
+            (Locale::FrCa, PaymentFrequency::BiWeekly) => "aux deux semaines",
+            (Locale::FrCa, PaymentFrequency::AcceleratedBiWeekly) => "aux deux semaines accélérée",
+            (Locale::FrCa, PaymentFrequency::Weekly) => "hebdomadaire",
+            (Locale::FrCa, PaymentFrequency::AcceleratedWeekly) => "hebdomadaire accélérée",
+        }
+    }
+
+    // A number of years: 25 years or 25 ans.
+    pub fn years(&self, years: u64) -> String {
+        match (self, years) {
+            (Locale::EnCa, 1) => format!("{} year", years),
+            (Locale::EnCa, _) => format!("{} years", years),
+            (Locale::FrCa, 1) => format!("{} an", years),
+            (Locale::FrCa, _) => format!("{} ans", years),
+        }
+    }
+
+    pub(crate) fn label(&self, label: Label) -> &'static str {
+        let (english, french) = match label {
+            Label::Payment => ("payment", "versement"),
+            Label::Interest => ("interest", "intérêts"),
+            Label::Principal => ("principal", "capital"),
+            Label::Prepayment => ("prepayment", "paiement anticipé"),
+            Label::Balance => ("balance", "solde"),
+            Label::Payments => ("payments", "versements"),
+            Label::TotalPayments => ("total payments", "total des versements"),
+            Label::TotalInterest => ("total interest", "total des intérêts"),
+            Label::InterestPerDollar => ("interest per dollar", "intérêts par dollar"),
+            Label::CapitalizedInterest => ("capitalized interest", "intérêts capitalisés"),
+            Label::PremiumInterest => ("interest on premium", "intérêts sur la prime"),
+            Label::InterestOnly => ("interest only", "intérêts seulement"),
+            Label::Yes => ("yes", "oui"),
+            Label::InterestRate => ("interest rate", "taux d'intérêt"),
+            Label::Apr => ("APR", "TAC"),
+            Label::Term => ("term", "durée"),
+            Label::Amortization => ("amortization", "amortissement"),
+            Label::PaymentFrequency => ("payment frequency", "fréquence des versements"),
+            Label::NonInterestCharges => ("non-interest charges", "frais autres que l'intérêt"),
+            Label::CostOfBorrowing => ("total cost of borrowing", "coût total d'emprunt"),
+            Label::BalanceAtTermEnd => ("balance at end of term", "solde à la fin de la durée"),
+        };
+        match self {
+            Locale::EnCa => english,
+            Locale::FrCa => french,
+        }
+    }
+
+    // whole, all digits, grouped in threes.
+    fn group(&self, whole: &str) -> String {
+        let separator = match self {
+            Locale::EnCa => ',',
+            Locale::FrCa => NO_BREAK_SPACE,
+        };
+        let mut grouped = String::new();
+        for (i, digit) in whole.chars().enumerate() {
+            if i > 0 && (whole.len() - i).is_multiple_of(3) {
+                grouped.push(separator);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+}
+
+// en-CA or fr-CA, or just en or fr. Case and an underscore in place of the hyphen don't matter.
+impl FromStr for Locale {
+    type Err = MortgageError;
+
+    fn from_str(locale: &str) -> Result<Locale> {
+        match locale.trim().to_lowercase().replace('_', "-").as_str() {
+            "en-ca" | "en" => Ok(Locale::EnCa),
+            "fr-ca" | "fr" => Ok(Locale::FrCa),
+            _ => Err(MortgageError::UnknownLocale(locale.into())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Label {
+    Payment,
+    Interest,
+    Principal,
+    Prepayment,
+    Balance,
+    Payments,
+    TotalPayments,
+    TotalInterest,
+    InterestPerDollar,
+    CapitalizedInterest,
+    PremiumInterest,
+    InterestOnly,
+    Yes,
+    InterestRate,
+    Apr,
+    Term,
+    Amortization,
+    PaymentFrequency,
+    NonInterestCharges,
+    CostOfBorrowing,
+    BalanceAtTermEnd,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::*;
+
+    #[test]
+    fn money_follows_the_locale() {
+        assert_eq!(Locale::EnCa.money(dec!(430000)), "$430,000.00");
+        assert_eq!(Locale::FrCa.money(dec!(430000)), "430\u{a0}000,00\u{a0}$");
+        assert_eq!(Locale::FrCa.money(dec!(-12.505)), "-12,50\u{a0}$");
+        assert_eq!(
+            crate::parse::money(&Locale::FrCa.money(dec!(1234567.89))),
+            Ok(dec!(1234567.89)),
+            "French amounts can be read back"
+        );
+    }
+
+    #[test]
+    fn rates_and_labels_follow_the_locale() {
+        assert_eq!(Locale::EnCa.percent(dec!(4.590)), "4.59%");
+        assert_eq!(Locale::FrCa.percent(dec!(4.59)), "4,59\u{a0}%");
+        assert_eq!(Locale::FrCa.percent(dec!(5)), "5\u{a0}%");
+        assert_eq!(Locale::FrCa.number(dec!(0.675), 2), "0,68");
+        assert_eq!(Locale::FrCa.years(25), "25 ans");
+        assert_eq!(
+            Locale::FrCa.frequency(PaymentFrequency::AcceleratedBiWeekly),
+            "aux deux semaines accélérée"
+        );
+        assert_eq!(Locale::FrCa.label(Label::Balance), "solde");
+    }
+
+    #[test]
+    fn locales_are_parsed() {
+        assert_eq!("fr-CA".parse(), Ok(Locale::FrCa));
+        assert_eq!("en_ca".parse(), Ok(Locale::EnCa));
+        assert_eq!(
+            "de-DE".parse::<Locale>(),
+            Err(MortgageError::UnknownLocale("de-DE".into()))
+        );
+    }
+}
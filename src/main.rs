@@ -0,0 +1,381 @@
+use canadian_mortgage::affordability::{DebtServiceLimits, Household};
+use canadian_mortgage::insurance::Insurer;
+use canadian_mortgage::{
+    closing_costs, parse, penalty, CanadianMortgage, Column, Locale, MortgageError,
+    PaymentFrequency, Province, ScheduleTable, ToJson,
+};
+use rust_decimal::prelude::*;
+use serde::Serialize;
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+#[structopt(name = "canadian-mortgage", about = "Canadian mortgage calculator")]
+struct Cli {
+    /// Print JSON instead of a table
+    #[structopt(long, global = true)]
+    json: bool,
+
+    /// en-CA or fr-CA, for the payment and schedule tables
+    #[structopt(long, global = true, default_value = "en-CA", parse(try_from_str = parse_locale))]
+    locale: Locale,
+
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+#[derive(StructOpt)]
+enum Command {
+    /// Regular payment for a mortgage
+    Payment(MortgageArgs),
+    /// Full amortization schedule
+    Schedule {
+        #[structopt(flatten)]
+        mortgage: MortgageArgs,
+        /// Columns to show, separated by commas: number, payment, interest, principal,
+        /// prepayment and balance. All of them by default
+        #[structopt(long, use_delimiter = true, parse(try_from_str = parse_column))]
+        columns: Vec<Column>,
+    },
+    /// Payments and lifetime interest at every payment frequency
+    Compare(MortgageArgs),
+    /// Penalty for breaking a closed mortgage
+    Penalty {
+        /// Balance owing
+        #[structopt(long, parse(try_from_str = parse_money))]
+        balance: Decimal,
+        /// Contract rate as a percentage
+        #[structopt(long, parse(try_from_str = parse_rate))]
+        rate: Decimal,
+        /// Lender's current rate for the remaining term, as a percentage
+        #[structopt(long, parse(try_from_str = parse_rate))]
+        comparison_rate: Decimal,
+        /// Months left in the term
+        #[structopt(long)]
+        months_remaining: u64,
+        /// Lender's posted rate when the mortgage was signed. The comparison rate is then the
+        /// current posted rate, and the original discount is taken off it, as the big banks do
+        #[structopt(long, parse(try_from_str = parse_rate))]
+        posted_rate: Option<Decimal>,
+    },
+    /// GDS and TDS ratios at the stress-tested qualifying payment
+    Affordability {
+        #[structopt(flatten)]
+        mortgage: MortgageArgs,
+        /// Gross annual household income
+        #[structopt(long, parse(try_from_str = parse_money))]
+        income: Decimal,
+        /// Monthly heating costs
+        #[structopt(long, default_value = "0", parse(try_from_str = parse_money))]
+        heating: Decimal,
+        /// Monthly property tax
+        #[structopt(long, default_value = "0", parse(try_from_str = parse_money))]
+        property_tax: Decimal,
+        /// Monthly condo fees
+        #[structopt(long, default_value = "0", parse(try_from_str = parse_money))]
+        condo_fees: Decimal,
+        /// Monthly payments on other debts
+        #[structopt(long, default_value = "0", parse(try_from_str = parse_money))]
+        other_debts: Decimal,
+    },
+    /// Cash needed at closing, including land transfer tax and sales tax on insurance premiums
+    ClosingCosts {
+        /// Two-letter province or territory code, e.g. ON
+        #[structopt(long, parse(try_from_str = parse_province))]
+        province: Province,
+        /// Purchase price
+        #[structopt(long, parse(try_from_str = parse_money))]
+        price: Decimal,
+        /// Down payment
+        #[structopt(long, parse(try_from_str = parse_money))]
+        down_payment: Decimal,
+        /// Amortization period in years
+        #[structopt(long, default_value = "25")]
+        amortization: u64,
+    },
+}
+
+#[derive(StructOpt)]
+struct MortgageArgs {
+    /// Mortgage amount
+    #[structopt(long, parse(try_from_str = parse_money))]
+    principal: Decimal,
+    /// Annual interest rate as a percentage, compounded semi-annually
+    #[structopt(long, parse(try_from_str = parse_rate))]
+    rate: Decimal,
+    /// Amortization period in years
+    #[structopt(long, default_value = "25")]
+    amortization: u64,
+    /// monthly, semi-monthly, biweekly, accelerated-biweekly, weekly or accelerated-weekly
+    #[structopt(long, default_value = "monthly", parse(try_from_str = parse_frequency))]
+    frequency: PaymentFrequency,
+    /// Pay only the interest, for the whole amortization period
+    #[structopt(long)]
+    interest_only: bool,
+}
+
+impl MortgageArgs {
+    fn mortgage(&self) -> canadian_mortgage::Result<CanadianMortgage> {
+        Ok(
+            CanadianMortgage::new(self.principal, self.rate, self.amortization, self.frequency)?
+                .with_interest_only(self.interest_only),
+        )
+    }
+}
+
+fn parse_frequency(frequency: &str) -> Result<PaymentFrequency, String> {
+    frequency.parse().map_err(|e: MortgageError| e.to_string())
+}
+
+fn parse_money(amount: &str) -> Result<Decimal, String> {
+    parse::money(amount).map_err(|e| e.to_string())
+}
+
+fn parse_rate(rate: &str) -> Result<Decimal, String> {
+    parse::rate(rate).map_err(|e| e.to_string())
+}
+
+fn parse_column(column: &str) -> Result<Column, String> {
+    column.parse().map_err(|e: MortgageError| e.to_string())
+}
+
+fn parse_locale(locale: &str) -> Result<Locale, String> {
+    locale.parse().map_err(|e: MortgageError| e.to_string())
+}
+
+fn parse_province(province: &str) -> Result<Province, String> {
+    match province.to_uppercase().as_str() {
+        "AB" => Ok(Province::Alberta),
+        "BC" => Ok(Province::BritishColumbia),
+        "MB" => Ok(Province::Manitoba),
+        "NB" => Ok(Province::NewBrunswick),
+        "NL" => Ok(Province::NewfoundlandAndLabrador),
+        "NT" => Ok(Province::NorthwestTerritories),
+        "NS" => Ok(Province::NovaScotia),
+        "NU" => Ok(Province::Nunavut),
+        "ON" => Ok(Province::Ontario),
+        "PE" => Ok(Province::PrinceEdwardIsland),
+        "QC" => Ok(Province::Quebec),
+        "SK" => Ok(Province::Saskatchewan),
+        "YT" => Ok(Province::Yukon),
+        _ => Err(format!("unknown province: {}", province)),
+    }
+}
+
+#[derive(Serialize)]
+struct Comparison {
+    payment_frequency: PaymentFrequency,
+    payment: Decimal,
+    number_of_payments: u64,
+    total_interest: Decimal,
+}
+
+#[derive(Serialize)]
+struct Penalty {
+    three_months_interest: Decimal,
+    interest_rate_differential: Decimal,
+    penalty: Decimal,
+}
+
+// Decimal's precision formatting truncates, so amounts are rounded to the cent first.
+fn cents(amount: Decimal) -> Decimal {
+    amount.round_dp(2)
+}
+
+fn main() {
+    if let Err(e) = run(Cli::from_args()) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    match cli.command {
+        Command::Payment(args) => {
+            let payment = args.mortgage()?.payment()?;
+            if cli.json {
+                println!("{}", serde_json::json!({ "payment": payment }));
+            } else {
+                println!("{}", cli.locale.money(payment));
+            }
+        }
+        Command::Schedule { mortgage, columns } => {
+            let schedule = mortgage.mortgage()?.amortization_schedule()?;
+            if cli.json {
+                println!("{}", schedule.to_json()?);
+            } else {
+                let mut table = ScheduleTable::new(&schedule).with_locale(cli.locale);
+                if !columns.is_empty() {
+                    table = table.with_columns(&columns);
+                }
+                println!("{}", table);
+            }
+        }
+        Command::Compare(args) => {
+            let mut comparisons = Vec::new();
+            for payment_frequency in PaymentFrequency::ALL.iter() {
+                let mortgage = MortgageArgs {
+                    frequency: *payment_frequency,
+                    ..args
+                }
+                .mortgage()?;
+                let summary = mortgage.summary()?;
+                comparisons.push(Comparison {
+                    payment_frequency: *payment_frequency,
+                    payment: mortgage.payment()?,
+                    number_of_payments: summary.number_of_payments,
+                    total_interest: summary.total_interest,
+                });
+            }
+
+            if cli.json {
+                println!("{}", serde_json::to_string(&comparisons)?);
+            } else {
+                println!(
+                    "{:<22} {:>12} {:>10} {:>16}",
+                    "frequency", "payment", "payments", "total interest"
+                );
+                for comparison in comparisons {
+                    println!(
+                        "{:<22} {:>12.2} {:>10} {:>16.2}",
+                        format!("{:?}", comparison.payment_frequency),
+                        cents(comparison.payment),
+                        comparison.number_of_payments,
+                        cents(comparison.total_interest)
+                    );
+                }
+            }
+        }
+        Command::Penalty {
+            balance,
+            rate,
+            comparison_rate,
+            months_remaining,
+            posted_rate,
+        } => {
+            let comparison_rate = match posted_rate {
+                Some(posted_rate) => {
+                    penalty::posted_rate_comparison(rate, posted_rate, comparison_rate)?
+                }
+                None => comparison_rate,
+            };
+            let result = Penalty {
+                three_months_interest: penalty::three_months_interest(balance, rate)?,
+                interest_rate_differential: penalty::interest_rate_differential(
+                    balance,
+                    rate,
+                    comparison_rate,
+                    months_remaining,
+                )?,
+                penalty: penalty::break_penalty(balance, rate, comparison_rate, months_remaining)?,
+            };
+
+            if cli.json {
+                println!("{}", serde_json::to_string(&result)?);
+            } else {
+                println!(
+                    "three months' interest: {:.2}",
+                    cents(result.three_months_interest)
+                );
+                println!(
+                    "interest rate differential: {:.2}",
+                    cents(result.interest_rate_differential)
+                );
+                println!("penalty: {:.2}", cents(result.penalty));
+            }
+        }
+        Command::Affordability {
+            mortgage,
+            income,
+            heating,
+            property_tax,
+            condo_fees,
+            other_debts,
+        } => {
+            let household = Household {
+                gross_annual_income: income,
+                heating,
+                property_tax,
+                condo_fees,
+                other_debts,
+            };
+            let result = mortgage
+                .mortgage()?
+                .debt_service(&household, &DebtServiceLimits::default())?;
+
+            if cli.json {
+                println!("{}", serde_json::to_string(&result)?);
+            } else {
+                println!(
+                    "GDS: {:.2}% (headroom {:.2})",
+                    result.gds.round_dp(2),
+                    cents(result.gds_headroom)
+                );
+                println!(
+                    "TDS: {:.2}% (headroom {:.2})",
+                    result.tds.round_dp(2),
+                    cents(result.tds_headroom)
+                );
+                println!("{}", if result.passes { "pass" } else { "fail" });
+            }
+        }
+        Command::ClosingCosts {
+            province,
+            price,
+            down_payment,
+            amortization,
+        } => {
+            let costs = closing_costs::closing_costs(
+                province,
+                price,
+                down_payment,
+                amortization,
+                Insurer::default(),
+            )?;
+
+            if cli.json {
+                println!("{}", serde_json::to_string(&costs)?);
+            } else {
+                println!("down payment: {:.2}", cents(costs.down_payment));
+                println!("land transfer tax: {:.2}", cents(costs.land_transfer_tax));
+                println!(
+                    "insurance premium (financed): {:.2}",
+                    cents(costs.insurance_premium)
+                );
+                println!(
+                    "sales tax on premium: {:.2}",
+                    cents(costs.premium_sales_tax)
+                );
+                println!("cash to close: {:.2}", cents(costs.cash_to_close));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frequencies_are_parsed() {
+        assert_eq!(
+            parse_frequency("accelerated-biweekly"),
+            Ok(PaymentFrequency::AcceleratedBiWeekly)
+        );
+        assert!(parse_frequency("fortnightly").is_err());
+    }
+
+    #[test]
+    fn amounts_and_rates_are_parsed() {
+        assert_eq!(parse_money("430k"), Ok(Decimal::from(430000)));
+        assert_eq!(parse_rate("4.59%"), Ok(Decimal::new(459, 2)));
+        assert!(parse_rate("120%").is_err());
+    }
+
+    #[test]
+    fn provinces_are_parsed() {
+        assert_eq!(parse_province("on"), Ok(Province::Ontario));
+        assert!(parse_province("Ontario").is_err());
+    }
+}
@@ -0,0 +1,284 @@
+// Markdown tables, to paste into issues, wikis and emails. Amounts are formatted as in table.rs
+// and right-aligned.
+use crate::payoff::PayoffPeriod;
+use crate::{
+    FixedVsVariable, Locale, Money, ScenarioComparison, ScenarioReport, ScheduleRow, Summary,
+};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use rust_decimal::prelude::*;
+
+pub trait ToMarkdown {
+    fn to_markdown(&self) -> String;
+}
+
+impl ToMarkdown for [ScheduleRow] {
+    fn to_markdown(&self) -> String {
+        let rows: Vec<Vec<String>> = self
+            .iter()
+            .map(|row| {
+                vec![
+                    row.payment_number.to_string(),
+                    Money(row.payment).to_string(),
+                    Money(row.interest).to_string(),
+                    Money(row.principal).to_string(),
+                    Money(row.prepayment).to_string(),
+                    Money(row.balance).to_string(),
+                ]
+            })
+            .collect();
+        table(
+            &[
+                "#",
+                "payment",
+                "interest",
+                "principal",
+                "prepayment",
+                "balance",
+            ],
+            &rows,
+        )
+    }
+}
+
+impl ToMarkdown for Summary {
+    fn to_markdown(&self) -> String {
+        table(
+            &["", ""],
+            &[
+                vec!["payments".into(), self.number_of_payments.to_string()],
+                vec![
+                    "total payments".into(),
+                    Money(self.total_payments).to_string(),
+                ],
+                vec![
+                    "total interest".into(),
+                    Money(self.total_interest).to_string(),
+                ],
+                vec![
+                    "interest per dollar".into(),
+                    format!("{:.2}", self.interest_to_principal.round_dp(2)),
+                ],
+            ],
+        )
+    }
+}
+
+// The baseline and the scenario side by side, with the scenario's difference from the baseline.
+impl ToMarkdown for ScenarioReport {
+    fn to_markdown(&self) -> String {
+        let (baseline, scenario) = (&self.baseline, &self.scenario);
+        table(
+            &["", "baseline", "scenario", "difference"],
+            &[
+                vec![
+                    "payments".into(),
+                    baseline.number_of_payments.to_string(),
+                    scenario.number_of_payments.to_string(),
+                    format!("-{}", self.payments_eliminated),
+                ],
+                vec![
+                    "paid off in".into(),
+                    period(&self.baseline_payoff),
+                    period(&self.scenario_payoff),
+                    String::new(),
+                ],
+                compared(
+                    "total payments",
+                    baseline.total_payments,
+                    scenario.total_payments,
+                ),
+                compared(
+                    "total interest",
+                    baseline.total_interest,
+                    scenario.total_interest,
+                ),
+            ],
+        )
+    }
+}
+
+// A column for each scenario, in order.
+impl ToMarkdown for ScenarioComparison {
+    fn to_markdown(&self) -> String {
+        let mut headings = vec![""];
+        headings.extend(self.outcomes.iter().map(|outcome| outcome.name.as_str()));
+        let row = |label: &str, cell: &dyn Fn(&crate::ScenarioOutcome) -> String| {
+            let mut row = vec![String::from(label)];
+            row.extend(self.outcomes.iter().map(cell));
+            row
+        };
+        table(
+            &headings,
+            &[
+                row("payment", &|outcome| {
+                    format!(
+                        "{} {}",
+                        Money(outcome.payment),
+                        Locale::EnCa.frequency(outcome.payment_frequency)
+                    )
+                }),
+                row("total interest", &|outcome| {
+                    Money(outcome.total_interest).to_string()
+                }),
+                row("balance at end of term", &|outcome| {
+                    Money(outcome.balance_at_term_end).to_string()
+                }),
+                row("paid off in", &|outcome| period(&outcome.payoff)),
+            ],
+        )
+    }
+}
+
+// Over the term, with the break-even below the table.
+impl ToMarkdown for FixedVsVariable {
+    fn to_markdown(&self) -> String {
+        let mut markdown = table(
+            &["", "fixed", "variable", "difference"],
+            &[
+                compared("interest", self.fixed_interest, self.variable_interest),
+                compared(
+                    "balance at renewal",
+                    self.fixed_balance_at_renewal,
+                    self.variable_balance_at_renewal,
+                ),
+            ],
+        );
+        markdown.push_str(&format!(
+            "\nOver a {}-year term. The variable rate costs the same as the fixed if prime moves {:+.2} percentage points.\n",
+            self.term_years,
+            self.break_even_prime_change.round_dp(2)
+        ));
+        markdown
+    }
+}
+
+fn compared(label: &str, first: Decimal, second: Decimal) -> Vec<String> {
+    vec![
+        label.into(),
+        Money(first).to_string(),
+        Money(second).to_string(),
+        Money(second - first).to_string(),
+    ]
+}
+
+fn period(period: &PayoffPeriod) -> String {
+    let months = if period.months == 1 {
+        "month"
+    } else {
+        "months"
+    };
+    format!(
+        "{} {} {}",
+        Locale::EnCa.years(period.years),
+        period.months,
+        months
+    )
+}
+
+// The first column is left-aligned and the others right-aligned. Every line ends in a newline.
+fn table(headings: &[&str], rows: &[Vec<String>]) -> String {
+    let mut markdown = String::new();
+    markdown.push_str(&line(headings.iter().copied()));
+    markdown.push_str(&line((0..headings.len()).map(|i| {
+        if i == 0 {
+            "---"
+        } else {
+            "---:"
+        }
+    })));
+    for row in rows {
+        markdown.push_str(&line(row.iter().map(String::as_str)));
+    }
+    markdown
+}
+
+fn line<'a>(cells: impl Iterator<Item = &'a str>) -> String {
+    let mut line = String::from("|");
+    for cell in cells {
+        line.push(' ');
+        line.push_str(cell);
+        line.push_str(" |");
+    }
+    line.push('\n');
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CanadianMortgage, PaymentFrequency, PrepaymentScenario};
+    use rust_decimal_macros::*;
+
+    fn mortgage() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap()
+    }
+
+    #[test]
+    fn schedule_to_markdown() {
+        let schedule = mortgage().amortization_schedule().unwrap();
+        let markdown = schedule[..2].to_markdown();
+
+        assert_eq!(
+            markdown,
+            "| # | payment | interest | principal | prepayment | balance |\n\
+             | --- | ---: | ---: | ---: | ---: | ---: |\n\
+             | 1 | $2,401.50 | $1,629.24 | $772.26 | $0.00 | $429,227.74 |\n\
+             | 2 | $2,401.50 | $1,626.31 | $775.18 | $0.00 | $428,452.56 |\n"
+        );
+        assert_eq!(schedule.to_markdown().lines().count(), 302);
+    }
+
+    #[test]
+    fn scenario_comparison_to_markdown() {
+        let scenarios = [
+            crate::Scenario {
+                name: "as is".into(),
+                mortgage: mortgage(),
+            },
+            crate::Scenario {
+                name: "lump sum".into(),
+                mortgage: mortgage().with_lump_sum(12, dec!(20000)).unwrap(),
+            },
+        ];
+        let markdown = crate::compare(&scenarios).unwrap().to_markdown();
+        let lines: Vec<&str> = markdown.lines().collect();
+
+        assert_eq!(lines[0], "|  | as is | lump sum |");
+        assert_eq!(lines[1], "| --- | ---: | ---: |");
+        assert_eq!(
+            lines[2],
+            "| payment | $2,401.50 monthly | $2,401.50 monthly |"
+        );
+        assert_eq!(
+            lines[5],
+            "| paid off in | 25 years 0 months | 23 years 1 month |"
+        );
+    }
+
+    #[test]
+    fn scenario_report_to_markdown() {
+        let report = mortgage()
+            .compare_scenario(&PrepaymentScenario::new().with_lump_sum(12, dec!(20000)))
+            .unwrap();
+        let markdown = report.to_markdown();
+        let lines: Vec<&str> = markdown.lines().collect();
+
+        assert_eq!(lines[0], "|  | baseline | scenario | difference |");
+        assert_eq!(
+            lines[2],
+            format!(
+                "| payments | 300 | {} | -{} |",
+                report.scenario.number_of_payments, report.payments_eliminated
+            )
+        );
+        assert_eq!(
+            lines[3],
+            "| paid off in | 25 years 0 months | 23 years 1 month |  |"
+        );
+        assert!(lines[5].starts_with("| total interest | $290,448.61 |"));
+        assert!(lines[5].ends_with(&format!("| -{} |", Money(report.interest_saved))[..]));
+    }
+}
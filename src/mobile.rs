@@ -0,0 +1,127 @@
+// Kotlin and Swift bindings generated by UniFFI. Amounts are passed and returned as decimal
+// strings, as in wasm.rs, so mobile apps get exactly the same results as the backend.
+// Generate the bindings with
+// cargo run --features uniffi --bin uniffi-bindgen generate --library <cdylib> --language swift
+use crate::{insurance, CanadianMortgage, MortgageError, PaymentFrequency, Result, ScheduleRow};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+// A ScheduleRow with its amounts as decimal strings.
+#[derive(Debug, Clone, PartialEq, uniffi::Record)]
+pub struct MobileScheduleRow {
+    pub payment_number: u64,
+    pub payment: String,
+    pub interest: String,
+    pub principal: String,
+    pub prepayment: String,
+    pub balance: String,
+}
+
+impl From<ScheduleRow> for MobileScheduleRow {
+    fn from(row: ScheduleRow) -> MobileScheduleRow {
+        MobileScheduleRow {
+            payment_number: row.payment_number,
+            payment: row.payment.to_string(),
+            interest: row.interest.to_string(),
+            principal: row.principal.to_string(),
+            prepayment: row.prepayment.to_string(),
+            balance: row.balance.to_string(),
+        }
+    }
+}
+
+#[uniffi::export]
+pub fn payment(
+    principal: String,
+    rate: String,
+    amortization: u32,
+    frequency: PaymentFrequency,
+) -> Result<String> {
+    Ok(mortgage(&principal, &rate, amortization, frequency)?
+        .payment()?
+        .to_string())
+}
+
+#[uniffi::export]
+pub fn amortization_schedule(
+    principal: String,
+    rate: String,
+    amortization: u32,
+    frequency: PaymentFrequency,
+) -> Result<Vec<MobileScheduleRow>> {
+    Ok(mortgage(&principal, &rate, amortization, frequency)?
+        .amortization_schedule()?
+        .into_iter()
+        .map(MobileScheduleRow::from)
+        .collect())
+}
+
+#[uniffi::export]
+pub fn insurance_premium(
+    loan: String,
+    property_value: String,
+    amortization: u32,
+) -> Result<String> {
+    Ok(insurance::premium(
+        decimal(&loan)?,
+        decimal(&property_value)?,
+        amortization.into(),
+    )?
+    .to_string())
+}
+
+fn mortgage(
+    principal: &str,
+    rate: &str,
+    amortization: u32,
+    frequency: PaymentFrequency,
+) -> Result<CanadianMortgage> {
+    CanadianMortgage::new(
+        decimal(principal)?,
+        decimal(rate)?,
+        amortization.into(),
+        frequency,
+    )
+}
+
+fn decimal(value: &str) -> Result<Decimal> {
+    Decimal::from_str(value)
+        .map_err(|e| MortgageError::ConversionFailure(format!("{}: {}", value, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amounts_are_decimal_strings() {
+        assert_eq!(
+            insurance_premium("475000".into(), "500000".into(), 25).unwrap(),
+            "19000.00"
+        );
+        assert!(matches!(
+            payment("430000".into(), "120".into(), 25, PaymentFrequency::Monthly),
+            Err(MortgageError::InvalidRate(_))
+        ));
+
+        let schedule = amortization_schedule(
+            "430000".into(),
+            "4.59".into(),
+            25,
+            PaymentFrequency::Monthly,
+        )
+        .unwrap();
+        assert_eq!(schedule.len(), 300);
+        assert_eq!(
+            schedule[0].payment,
+            payment(
+                "430000".into(),
+                "4.59".into(),
+                25,
+                PaymentFrequency::Monthly
+            )
+            .unwrap()
+        );
+        assert_eq!(schedule[299].balance, "0");
+    }
+}
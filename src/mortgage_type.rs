@@ -0,0 +1,366 @@
+// Closed mortgages limit how much can be prepaid each year and charge a penalty to be paid off
+// early. Open mortgages can be prepaid or paid off at any time without penalty, for a higher rate.
+// Convertible mortgages are short closed terms that can be converted to a longer closed term
+// at no cost; breaking one costs three months' interest.
+use crate::{penalty, CanadianMortgage, MortgageError, Result};
+use alloc::vec::Vec;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MortgageType {
+    #[default]
+    Closed,
+    Open,
+    Convertible,
+}
+
+impl MortgageType {
+    // A typical premium, in percentage points, over the closed rate for the same term.
+    // Lenders set their own; this is a starting point for comparisons.
+    pub fn rate_premium(&self) -> Decimal {
+        match self {
+            MortgageType::Closed => dec!(0),
+            MortgageType::Open => dec!(2),
+            MortgageType::Convertible => dec!(0.25),
+        }
+    }
+}
+
+// How much a closed or convertible mortgage can be prepaid without penalty in each year of the term.
+// lump_sum_percentage is of the original principal; payment_increase_percentage is of the original payment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrepaymentPrivileges {
+    pub lump_sum_percentage: Decimal,
+    pub payment_increase_percentage: Decimal,
+}
+
+// Lump sums and double-ups prepaid in one anniversary year of the mortgage against the lump sum
+// privilege. Lenders charge three months' interest on the excess as excess_penalty.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrepaymentYear {
+    pub year: u64,
+    pub prepaid: Decimal,
+    pub limit: Decimal,
+    pub excess: Decimal,
+    pub excess_penalty: Decimal,
+}
+
+// 15% is the most common privilege at the big banks; 10% and 20% are also common.
+impl Default for PrepaymentPrivileges {
+    fn default() -> Self {
+        PrepaymentPrivileges {
+            lump_sum_percentage: dec!(15),
+            payment_increase_percentage: dec!(15),
+        }
+    }
+}
+
+impl CanadianMortgage {
+    // Closed unless set otherwise. The interest rate is used as given, so it should already
+    // include any premium for an open or convertible mortgage; see MortgageType::rate_premium.
+    pub fn with_mortgage_type(mut self, mortgage_type: MortgageType) -> CanadianMortgage {
+        self.mortgage_type = mortgage_type;
+        self
+    }
+
+    pub fn mortgage_type(&self) -> MortgageType {
+        self.mortgage_type
+    }
+
+    pub fn with_prepayment_privileges(
+        mut self,
+        privileges: PrepaymentPrivileges,
+    ) -> Result<CanadianMortgage> {
+        for percentage in [
+            privileges.lump_sum_percentage,
+            privileges.payment_increase_percentage,
+        ] {
+            if percentage < dec!(0) {
+                return Err(MortgageError::InvalidPrepayment {
+                    payment_number: 0,
+                    amount: percentage,
+                });
+            }
+        }
+
+        self.prepayment_privileges = privileges;
+        Ok(self)
+    }
+
+    // Reject lump sums, double-ups and payment increases beyond the prepayment privileges as they
+    // are added, rather than just reporting them. Prepayments already added are checked now.
+    pub fn with_enforced_privileges(mut self) -> Result<CanadianMortgage> {
+        self.check_prepayment_privileges()?;
+        self.enforce_privileges = true;
+        Ok(self)
+    }
+
+    pub fn enforces_privileges(&self) -> bool {
+        self.enforce_privileges
+    }
+
+    // None for an open mortgage, which can be prepaid without limit.
+    pub fn prepayment_privileges(&self) -> Option<PrepaymentPrivileges> {
+        match self.mortgage_type {
+            MortgageType::Open => None,
+            MortgageType::Closed | MortgageType::Convertible => Some(self.prepayment_privileges),
+        }
+    }
+
+    // Check that the lump sums and payment increases in each year of the mortgage stay within
+    // the prepayment privileges. Double-ups count towards the lump sum privilege.
+    // Prepayments beyond them would be charged a penalty.
+    pub fn check_prepayment_privileges(&self) -> Result<()> {
+        let privileges = match self.prepayment_privileges() {
+            Some(privileges) => privileges,
+            None => return Ok(()),
+        };
+        let year_of = |payment_number: u64| self.anniversary_year(payment_number);
+
+        if let Some(year) = self
+            .prepayment_years()?
+            .into_iter()
+            .find(|year| year.excess > dec!(0))
+        {
+            return Err(MortgageError::LumpSumLimitExceeded {
+                year: year.year,
+                amount: year.prepaid,
+                limit: year.limit,
+            });
+        }
+
+        // A payment override is an increase from the first payment, which can't be measured
+        // against a first payment of zero.
+        let payment = self.payment()?;
+        let excess = self.payment_override_excess(payment);
+        let override_percentage = if excess == dec!(0) {
+            dec!(0)
+        } else {
+            excess
+                .checked_div(payment)
+                .ok_or(MortgageError::InvalidPayment(payment))?
+                * dec!(100)
+        };
+        let override_year = Some(1).filter(|_| override_percentage > dec!(0));
+        for year in self
+            .payment_increases
+            .iter()
+            .map(|i| year_of(i.payment_number))
+            .chain(override_year)
+        {
+            let increases: Decimal = self
+                .payment_increases
+                .iter()
+                .filter(|i| year_of(i.payment_number) == year)
+                .map(|i| i.percentage)
+                .sum();
+            let percentage = if year == 1 {
+                increases + override_percentage
+            } else {
+                increases
+            };
+            if percentage > privileges.payment_increase_percentage {
+                return Err(MortgageError::PaymentIncreaseLimitExceeded {
+                    year,
+                    percentage,
+                    limit: privileges.payment_increase_percentage,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // The lump sums and double-ups prepaid in each anniversary year with any, and how far over
+    // the privilege they go. Empty for an open mortgage.
+    pub fn prepayment_years(&self) -> Result<Vec<PrepaymentYear>> {
+        let privileges = match self.prepayment_privileges() {
+            Some(privileges) => privileges,
+            None => return Ok(Vec::new()),
+        };
+        let limit = self.principal * privileges.lump_sum_percentage / dec!(100);
+
+        let payment = self.payment()?;
+        let prepayments: Vec<(u64, Decimal)> = self
+            .lump_sums
+            .iter()
+            .map(|l| Ok((self.anniversary_year(l.payment_number), l.amount)))
+            .chain(
+                self.double_ups
+                    .iter()
+                    .map(|n| Ok((self.anniversary_year(*n), self.payment_at(*n, payment)?))),
+            )
+            .collect::<Result<_>>()?;
+
+        let mut years: Vec<u64> = prepayments.iter().map(|(year, _)| *year).collect();
+        years.sort_unstable();
+        years.dedup();
+
+        years
+            .into_iter()
+            .map(|year| {
+                let prepaid: Decimal = prepayments
+                    .iter()
+                    .filter(|(y, _)| *y == year)
+                    .map(|(_, amount)| *amount)
+                    .sum();
+                let excess = (prepaid - limit).max(dec!(0));
+                Ok(PrepaymentYear {
+                    year,
+                    prepaid,
+                    limit,
+                    excess,
+                    excess_penalty: penalty::three_months_interest(excess, self.annual_rate)?,
+                })
+            })
+            .collect()
+    }
+
+    // Called after a prepayment is added, so it can be rejected if privileges are enforced.
+    pub(crate) fn check_enforced_privileges(self) -> Result<CanadianMortgage> {
+        if self.enforce_privileges {
+            self.check_prepayment_privileges()?;
+        }
+        Ok(self)
+    }
+
+    fn anniversary_year(&self, payment_number: u64) -> u64 {
+        (payment_number - 1) / self.payment_frequency.payments_per_year() + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+
+    fn mortgage() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(400000), dec!(5), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_term(5)
+            .unwrap()
+    }
+
+    #[test]
+    fn closed_lump_sums_are_limited_each_year() {
+        let within = mortgage()
+            .with_lump_sum(6, dec!(30000))
+            .unwrap()
+            .with_lump_sum(12, dec!(30000))
+            .unwrap()
+            .with_lump_sum(13, dec!(60000))
+            .unwrap();
+        assert_eq!(within.check_prepayment_privileges(), Ok(()));
+
+        let over = within.with_lump_sum(24, dec!(0.01)).unwrap();
+        assert_eq!(
+            over.check_prepayment_privileges(),
+            Err(MortgageError::LumpSumLimitExceeded {
+                year: 2,
+                amount: dec!(60000.01),
+                limit: dec!(60000),
+            })
+        );
+        assert_eq!(
+            over.with_mortgage_type(MortgageType::Open)
+                .check_prepayment_privileges(),
+            Ok(()),
+            "open mortgages have no limit"
+        );
+    }
+
+    #[test]
+    fn prepayments_are_tracked_by_anniversary_year() {
+        let years = mortgage()
+            .with_lump_sum(6, dec!(50000))
+            .unwrap()
+            .with_lump_sum(30, dec!(50000))
+            .unwrap()
+            .with_lump_sum(36, dec!(20000))
+            .unwrap()
+            .prepayment_years()
+            .unwrap();
+
+        assert_eq!(years.len(), 2);
+        assert_eq!(years[0].year, 1);
+        assert_eq!(years[0].excess, dec!(0));
+        assert_eq!(years[1].year, 3);
+        assert_eq!(years[1].prepaid, dec!(70000));
+        assert_eq!(years[1].excess, dec!(10000));
+        assert_eq!(
+            years[1].excess_penalty,
+            penalty::three_months_interest(dec!(10000), dec!(5)).unwrap()
+        );
+    }
+
+    #[test]
+    fn enforced_privileges_reject_prepayments() {
+        let enforced = mortgage()
+            .with_prepayment_privileges(PrepaymentPrivileges {
+                lump_sum_percentage: dec!(10),
+                payment_increase_percentage: dec!(10),
+            })
+            .unwrap()
+            .with_enforced_privileges()
+            .unwrap()
+            .with_lump_sum(6, dec!(30000))
+            .unwrap();
+
+        assert!(enforced.clone().with_lump_sum(7, dec!(10001)).is_err());
+        assert!(enforced.clone().with_lump_sum(13, dec!(40000)).is_ok());
+        assert!(enforced.clone().with_payment_increase(2, dec!(11)).is_err());
+        assert!(mortgage()
+            .with_lump_sum(6, dec!(70000))
+            .unwrap()
+            .with_enforced_privileges()
+            .is_err());
+    }
+
+    #[test]
+    fn double_ups_count_as_lump_sums() {
+        let mortgage = mortgage().with_lump_sum(6, dec!(58000)).unwrap();
+        assert_eq!(mortgage.check_prepayment_privileges(), Ok(()));
+        assert!(mortgage
+            .with_double_up(12)
+            .unwrap()
+            .check_prepayment_privileges()
+            .is_err());
+    }
+
+    #[test]
+    fn closed_payment_increases_are_limited_each_year() {
+        let mortgage = mortgage()
+            .with_payment_increase(1, dec!(10))
+            .unwrap()
+            .with_payment_increase(12, dec!(10))
+            .unwrap();
+        assert!(mortgage.check_prepayment_privileges().is_err());
+
+        let mortgage = mortgage
+            .with_prepayment_privileges(PrepaymentPrivileges {
+                lump_sum_percentage: dec!(20),
+                payment_increase_percentage: dec!(20),
+            })
+            .unwrap();
+        assert_eq!(mortgage.check_prepayment_privileges(), Ok(()));
+    }
+
+    #[test]
+    fn open_mortgages_have_no_privileges() {
+        assert_eq!(mortgage().mortgage_type(), MortgageType::Closed);
+        assert_eq!(
+            mortgage().prepayment_privileges(),
+            Some(PrepaymentPrivileges::default())
+        );
+        assert_eq!(
+            mortgage()
+                .with_mortgage_type(MortgageType::Open)
+                .prepayment_privileges(),
+            None
+        );
+    }
+}
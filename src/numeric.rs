@@ -0,0 +1,226 @@
+// The closed-form amortization math, generic over the number type: Decimal, f64, or BigDecimal
+// with the bigdecimal feature. The mortgage types themselves are in Decimal, but the formulas can
+// be used directly with whichever representation a caller needs. Every operation is checked, so
+// overflow, or a result that isn't finite in f64, is None rather than a panic.
+use core::ops::{Add, Sub};
+use rust_decimal::prelude::*;
+
+pub trait Numeric: Clone + PartialEq + Add<Output = Self> + Sub<Output = Self> {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn from_u64(n: u64) -> Option<Self>;
+    fn is_zero(&self) -> bool;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+    fn checked_div(&self, other: &Self) -> Option<Self>;
+    fn checked_powu(&self, exponent: u64) -> Option<Self>;
+}
+
+impl Numeric for Decimal {
+    fn zero() -> Self {
+        Decimal::ZERO
+    }
+
+    fn one() -> Self {
+        Decimal::ONE
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        <Decimal as FromPrimitive>::from_u64(n)
+    }
+
+    fn is_zero(&self) -> bool {
+        Decimal::is_zero(self)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Decimal::checked_mul(*self, *other)
+    }
+
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        Decimal::checked_div(*self, *other)
+    }
+
+    fn checked_powu(&self, exponent: u64) -> Option<Self> {
+        MathematicalOps::checked_powu(self, exponent)
+    }
+}
+
+impl Numeric for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn one() -> Self {
+        1.0
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        Some(n as f64)
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == 0.0
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        finite(self * other)
+    }
+
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        finite(self / other)
+    }
+
+    #[cfg(feature = "std")]
+    fn checked_powu(&self, exponent: u64) -> Option<Self> {
+        finite(self.powf(exponent as f64))
+    }
+
+    // Without std there is no powf, so square and multiply.
+    #[cfg(not(feature = "std"))]
+    fn checked_powu(&self, exponent: u64) -> Option<Self> {
+        let (mut base, mut exponent, mut power) = (*self, exponent, 1.0);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                power = finite(power * base)?;
+            }
+            base = finite(base * base).unwrap_or(f64::INFINITY);
+            exponent >>= 1;
+        }
+        Some(power)
+    }
+}
+
+fn finite(x: f64) -> Option<f64> {
+    Some(x).filter(|x| x.is_finite())
+}
+
+// BigDecimal never overflows; division is to its default precision of 100 digits.
+#[cfg(feature = "bigdecimal")]
+impl Numeric for bigdecimal::BigDecimal {
+    fn zero() -> Self {
+        bigdecimal::BigDecimal::from(0)
+    }
+
+    fn one() -> Self {
+        bigdecimal::BigDecimal::from(1)
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        Some(bigdecimal::BigDecimal::from(n))
+    }
+
+    fn is_zero(&self) -> bool {
+        bigdecimal::Zero::is_zero(self)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        Some(self)
+            .filter(|_| !Numeric::is_zero(other))
+            .map(|x| x / other)
+    }
+
+    fn checked_powu(&self, exponent: u64) -> Option<Self> {
+        let (mut base, mut exponent, mut power) =
+            (self.clone(), exponent, <Self as Numeric>::one());
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                power = (&power * &base).with_prec(100);
+            }
+            base = (&base * &base).with_prec(100);
+            exponent >>= 1;
+        }
+        Some(power)
+    }
+}
+
+// a = p * r * (1 + r)**n / ((1 + r)**n - 1)
+// the payment a that pays off principal p over n payments at periodic rate r.
+// At a zero rate the principal is split evenly over the payments.
+pub fn payment<N: Numeric>(principal: N, rate: N, payments: u64) -> Option<N> {
+    if rate.is_zero() {
+        return principal.checked_div(&N::from_u64(payments)?);
+    }
+    let growth = (N::one() + rate.clone()).checked_powu(payments)?;
+    principal
+        .checked_mul(&rate)?
+        .checked_mul(&growth)?
+        .checked_div(&(growth - N::one()))
+}
+
+// p = a * ((1 + r)**n - 1) / r / (1 + r)**n
+// the principal p that n payments of a pay off at periodic rate r.
+pub fn present_value<N: Numeric>(payment: N, rate: N, payments: u64) -> Option<N> {
+    if rate.is_zero() {
+        return payment.checked_mul(&N::from_u64(payments)?);
+    }
+    let growth = (N::one() + rate.clone()).checked_powu(payments)?;
+    payment
+        .checked_mul(&(growth.clone() - N::one()))?
+        .checked_div(&rate)?
+        .checked_div(&growth)
+}
+
+// B = p * (1 + r)**n - a * ((1 + r)**n - 1) / r
+// the balance after n payments of a on principal p at periodic rate r.
+pub fn balance<N: Numeric>(principal: N, payment: N, rate: N, payments: u64) -> Option<N> {
+    if rate.is_zero() {
+        return Some(principal - payment.checked_mul(&N::from_u64(payments)?)?);
+    }
+    let growth = (N::one() + rate.clone()).checked_powu(payments)?;
+    let paid = payment
+        .checked_mul(&(growth.clone() - N::one()))?
+        .checked_div(&rate)?;
+    Some(principal.checked_mul(&growth)? - paid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::*;
+
+    #[test]
+    fn decimal_and_f64_agree() {
+        let rate = dec!(0.0379) / dec!(12);
+        let decimal = payment(dec!(430000), rate, 300).unwrap();
+        let float = payment(430000.0, rate.to_f64().unwrap(), 300).unwrap();
+        assert_eq!(
+            Decimal::from_f64(float).unwrap().round_dp(6),
+            decimal.round_dp(6)
+        );
+
+        assert_eq!(
+            present_value(decimal, rate, 300).unwrap().round_dp(10),
+            dec!(430000)
+        );
+        assert_eq!(
+            balance(dec!(430000), decimal, rate, 300)
+                .unwrap()
+                .round_dp(10),
+            dec!(0)
+        );
+        assert_eq!(payment(1200.0, 0.0, 12), Some(100.0));
+    }
+
+    #[test]
+    fn overflow_is_none() {
+        assert_eq!(payment(dec!(500000), dec!(0.5), 1000), None);
+        assert_eq!(payment(500000.0, 0.5, 10000), None);
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn big_decimal() {
+        use bigdecimal::BigDecimal;
+        use core::str::FromStr;
+
+        let rate = BigDecimal::from_str("0.0379").unwrap() / BigDecimal::from(12);
+        let big = payment(BigDecimal::from(430000), rate, 300).unwrap();
+        let decimal = payment(dec!(430000), dec!(0.0379) / dec!(12), 300).unwrap();
+        assert_eq!(big.round(10).to_string(), decimal.round_dp(10).to_string());
+        assert!(payment(BigDecimal::from(500000), BigDecimal::from(1), 1000).is_some());
+    }
+}
@@ -0,0 +1,159 @@
+// Comparing lenders' offers for the same mortgage by what they cost over the term rather than by
+// the headline rate. An offer's effective cost is the interest over its term plus its charges,
+// less any cash back, and its effective rate is that cost as an annual percentage of the average
+// principal outstanding, the same way the APR is worked out, so offers with different terms can
+// be ranked together.
+use crate::{BorrowingCharges, CanadianMortgage, PrepaymentPrivileges, Result};
+use alloc::string::String;
+use alloc::vec::Vec;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// cash_back is a percentage of the principal, zero for none.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Offer {
+    pub lender: String,
+    pub interest_rate: Decimal,
+    pub term_years: u64,
+    pub charges: BorrowingCharges,
+    pub cash_back: Decimal,
+    pub prepayment_privileges: PrepaymentPrivileges,
+}
+
+// interest and balance_at_term_end are over the offer's term.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OfferCost {
+    pub offer: Offer,
+    pub payment: Decimal,
+    pub interest: Decimal,
+    pub cash_back: Decimal,
+    pub effective_cost: Decimal,
+    pub effective_rate: Decimal,
+    pub balance_at_term_end: Decimal,
+}
+
+impl Offer {
+    // The mortgage on the offer's rate, term, cash back and privileges.
+    pub fn apply(&self, mortgage: &CanadianMortgage) -> Result<CanadianMortgage> {
+        let mut mortgage = mortgage
+            .with_annual_rate(self.interest_rate)?
+            .with_term(self.term_years)?
+            .with_prepayment_privileges(self.prepayment_privileges)?;
+        if self.cash_back > dec!(0) {
+            mortgage = mortgage.with_cash_back(self.cash_back)?;
+        }
+        Ok(mortgage)
+    }
+}
+
+impl CanadianMortgage {
+    // The offers, cheapest first by effective rate. Offers that cost the same are ranked by the
+    // larger lump sum privilege, and then in the order given.
+    pub fn compare_offers(&self, offers: &[Offer]) -> Result<Vec<OfferCost>> {
+        let mut costs = offers
+            .iter()
+            .map(|offer| {
+                let mortgage = offer.apply(self)?;
+                let interest = mortgage.interest_paid_in_term()?;
+                let cash_back = mortgage.cash_back();
+                let effective_cost = interest + offer.charges.total() - cash_back;
+                Ok(OfferCost {
+                    offer: offer.clone(),
+                    payment: mortgage.payment()?,
+                    interest,
+                    cash_back,
+                    effective_cost,
+                    effective_rate: mortgage.annualized_over_term(effective_cost)?,
+                    balance_at_term_end: mortgage.balance_at_term_end()?,
+                })
+            })
+            .collect::<Result<Vec<OfferCost>>>()?;
+
+        costs.sort_by(|a, b| {
+            a.effective_rate.cmp(&b.effective_rate).then(
+                b.offer
+                    .prepayment_privileges
+                    .lump_sum_percentage
+                    .cmp(&a.offer.prepayment_privileges.lump_sum_percentage),
+            )
+        });
+        Ok(costs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+    use alloc::vec;
+
+    fn offer(lender: &str, interest_rate: Decimal) -> Offer {
+        Offer {
+            lender: lender.into(),
+            interest_rate,
+            term_years: 5,
+            charges: BorrowingCharges::default(),
+            cash_back: dec!(0),
+            prepayment_privileges: PrepaymentPrivileges::default(),
+        }
+    }
+
+    #[test]
+    fn offers_are_ranked_by_effective_rate() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        let plain = offer("plain", dec!(4.59));
+        let fees = Offer {
+            charges: BorrowingCharges {
+                lender_fees: dec!(3000),
+                ..BorrowingCharges::default()
+            },
+            ..offer("low rate with fees", dec!(4.49))
+        };
+        let cash_back = Offer {
+            cash_back: dec!(3),
+            ..offer("cash back", dec!(4.99))
+        };
+        let costs = mortgage
+            .compare_offers(&[cash_back, plain.clone(), fees])
+            .unwrap();
+        let lenders: Vec<&str> = costs
+            .iter()
+            .map(|cost| cost.offer.lender.as_str())
+            .collect();
+
+        assert_eq!(lenders, vec!["cash back", "plain", "low rate with fees"]);
+        let plain_cost = &costs[1];
+        let term = mortgage.clone().with_term(5).unwrap();
+        assert_eq!(plain_cost.interest, term.interest_paid_in_term().unwrap());
+        assert_eq!(plain_cost.effective_cost, plain_cost.interest);
+        assert_eq!(
+            plain_cost.effective_rate,
+            term.apr(&BorrowingCharges::default()).unwrap()
+        );
+        assert_eq!(costs[0].cash_back, dec!(12900));
+        assert_eq!(costs[0].effective_cost, costs[0].interest - dec!(12900));
+        assert!(costs[0].offer.interest_rate > plain.interest_rate);
+    }
+
+    #[test]
+    fn ties_go_to_the_larger_privilege() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        let generous = Offer {
+            prepayment_privileges: PrepaymentPrivileges {
+                lump_sum_percentage: dec!(20),
+                payment_increase_percentage: dec!(20),
+            },
+            ..offer("generous", dec!(4.59))
+        };
+        let costs = mortgage
+            .compare_offers(&[offer("plain", dec!(4.59)), generous])
+            .unwrap();
+
+        assert_eq!(costs[0].offer.lender, "generous");
+        assert_eq!(costs[0].effective_rate, costs[1].effective_rate);
+    }
+}
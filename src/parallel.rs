@@ -0,0 +1,25 @@
+// Evaluating many independent scenarios, like the cells of a sensitivity grid or the paths of a
+// simulation. With the rayon feature they are spread across all cores; results are in the same
+// order either way.
+use crate::Result;
+use alloc::vec::Vec;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+#[cfg(feature = "rayon")]
+pub(crate) fn try_map<T, U, F>(items: &[T], f: F) -> Result<Vec<U>>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> Result<U> + Sync + Send,
+{
+    items.par_iter().map(f).collect()
+}
+
+#[cfg(not(feature = "rayon"))]
+pub(crate) fn try_map<T, U, F>(items: &[T], f: F) -> Result<Vec<U>>
+where
+    F: Fn(&T) -> Result<U>,
+{
+    items.iter().map(f).collect()
+}
@@ -0,0 +1,108 @@
+// Amounts and rates as people write them, for front ends that take text: "$430,000", "430k",
+// "4.59%", and French-Canadian "430 000,00 $" and "4,59 %".
+// Spaces, including non-breaking ones, always group digits. A comma or period is the decimal
+// separator if it is the last of them and followed by one or two digits; otherwise it groups.
+// A lone period is always the decimal separator.
+use crate::{MortgageError, Result};
+use alloc::string::String;
+use core::str::FromStr;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// An amount of money, which can't be negative. A k or M suffix multiplies by a thousand or a million.
+pub fn money(text: &str) -> Result<Decimal> {
+    let unparsable = || MortgageError::UnparsableAmount(text.into());
+
+    let mut amount = text.trim();
+    amount = amount.strip_prefix('$').unwrap_or(amount);
+    amount = amount.strip_suffix('$').unwrap_or(amount).trim();
+    let multiplier = match amount.chars().last() {
+        Some('k') | Some('K') => dec!(1000),
+        Some('m') | Some('M') => dec!(1000000),
+        _ => dec!(1),
+    };
+    if multiplier != dec!(1) {
+        amount = amount[..amount.len() - 1].trim_end();
+    }
+
+    number(amount)
+        .and_then(|amount| amount.checked_mul(multiplier))
+        .ok_or_else(unparsable)
+}
+
+// An annual interest rate as a percentage, with or without the percent sign, between 0% and 100%.
+pub fn rate(text: &str) -> Result<Decimal> {
+    let rate = text.trim();
+    let rate = rate.strip_suffix('%').unwrap_or(rate).trim_end();
+    let rate = number(rate).ok_or_else(|| MortgageError::UnparsableRate(text.into()))?;
+    crate::validate_rate(rate)?;
+    Ok(rate)
+}
+
+// Digits with grouping and at most one decimal separator, as described at the top.
+fn number(text: &str) -> Option<Decimal> {
+    let digits: String = text
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '\u{a0}' | '\u{202f}'))
+        .collect();
+    if digits.is_empty()
+        || !digits.starts_with(|c: char| c.is_ascii_digit())
+        || !digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == ',' || c == '.')
+    {
+        return None;
+    }
+
+    let decimal_separator = digits.rfind([',', '.']).filter(|&i| {
+        let fraction = digits.len() - i - 1;
+        let lone_period = digits.matches([',', '.']).count() == 1 && digits[i..].starts_with('.');
+        (1..=2).contains(&fraction) || (lone_period && fraction > 0)
+    });
+    let normalized: String = digits
+        .char_indices()
+        .filter_map(|(i, c)| match c {
+            _ if Some(i) == decimal_separator => Some('.'),
+            ',' | '.' => None,
+            c => Some(c),
+        })
+        .collect();
+
+    Decimal::from_str(&normalized).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn money_is_parsed() {
+        assert_eq!(money("$430,000"), Ok(dec!(430000)));
+        assert_eq!(money("430k"), Ok(dec!(430000)));
+        assert_eq!(money("1.2M"), Ok(dec!(1200000)));
+        assert_eq!(money("$2,401.50"), Ok(dec!(2401.50)));
+        assert_eq!(money("430 000,00 $"), Ok(dec!(430000)));
+        assert_eq!(money("430\u{a0}000,5\u{a0}$"), Ok(dec!(430000.5)));
+        assert_eq!(money("1.234.567,89"), Ok(dec!(1234567.89)));
+        assert_eq!(money("0"), Ok(dec!(0)));
+
+        for text in ["", "$", "-5", "12a", "k", "1,2,3.4.5x"].iter() {
+            assert_eq!(
+                money(text),
+                Err(MortgageError::UnparsableAmount((*text).into()))
+            );
+        }
+    }
+
+    #[test]
+    fn rates_are_parsed() {
+        assert_eq!(rate("4.59%"), Ok(dec!(4.59)));
+        assert_eq!(rate("4,59 %"), Ok(dec!(4.59)));
+        assert_eq!(rate(" 5 "), Ok(dec!(5)));
+        assert_eq!(rate("150%"), Err(MortgageError::InvalidRate(dec!(150))));
+        assert_eq!(
+            rate("prime"),
+            Err(MortgageError::UnparsableRate("prime".into()))
+        );
+    }
+}
@@ -0,0 +1,147 @@
+// Some borrowers pay more than the required payment, a round amount or the payment rounded up to
+// the next $10 or $100. Whatever is paid over the required payment goes entirely to principal,
+// as a prepayment with each payment. It counts as a payment increase from the first payment
+// against the prepayment privileges.
+use crate::{CanadianMortgage, MortgageError, Result};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PaymentOverride {
+    // Pay this amount each period, or the required payment if it is ever higher.
+    Amount(Decimal),
+    // Round the required payment up to a multiple of this amount.
+    RoundUpTo(Decimal),
+}
+
+impl CanadianMortgage {
+    // Pay amount each period, which can't be less than the required payment.
+    pub fn with_payment_override(mut self, amount: Decimal) -> Result<CanadianMortgage> {
+        let minimum = self.payment()?;
+        if amount < minimum {
+            return Err(MortgageError::PaymentBelowMinimum {
+                payment: amount,
+                minimum,
+            });
+        }
+        self.payment_override = Some(PaymentOverride::Amount(amount));
+        self.check_enforced_privileges()
+    }
+
+    // Round each payment up to a multiple of increment, e.g. 10 or 100.
+    pub fn with_payment_rounded_up_to(mut self, increment: Decimal) -> Result<CanadianMortgage> {
+        if increment <= dec!(0) {
+            return Err(MortgageError::InvalidPayment(increment));
+        }
+        self.payment_override = Some(PaymentOverride::RoundUpTo(increment));
+        self.check_enforced_privileges()
+    }
+
+    pub fn payment_override(&self) -> Option<PaymentOverride> {
+        self.payment_override
+    }
+
+    // What is paid over regular_payment, the required payment.
+    pub(crate) fn payment_override_excess(&self, regular_payment: Decimal) -> Decimal {
+        let payment = match self.payment_override {
+            Some(PaymentOverride::Amount(amount)) => amount,
+            Some(PaymentOverride::RoundUpTo(increment)) => {
+                (regular_payment / increment).ceil() * increment
+            }
+            None => return dec!(0),
+        };
+        (payment - regular_payment).max(dec!(0)).normalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+    use alloc::string::ToString;
+
+    fn mortgage() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap()
+    }
+
+    #[test]
+    fn excess_is_prepaid() {
+        let regular = mortgage().payment().unwrap();
+        let rounded = mortgage().with_payment_rounded_up_to(dec!(100)).unwrap();
+        let schedule = rounded.amortization_schedule().unwrap();
+
+        assert_eq!(
+            rounded.payment().unwrap(),
+            regular,
+            "still the required payment"
+        );
+        assert_eq!(schedule[0].payment, regular);
+        assert_eq!(schedule[0].prepayment, dec!(2500) - regular);
+        assert!(schedule.len() < 300);
+        assert!(rounded.prepayment_savings().unwrap().interest_saved > dec!(0));
+        assert_eq!(rounded.balance_at(12).unwrap(), schedule[11].balance);
+    }
+
+    #[test]
+    fn override_is_at_least_the_required_payment() {
+        let schedule = mortgage()
+            .with_payment_override(dec!(3000))
+            .unwrap()
+            .amortization_schedule()
+            .unwrap();
+        assert!(schedule
+            .iter()
+            .all(|row| row.payment + row.prepayment == dec!(3000) || row.balance == dec!(0)));
+
+        assert!(matches!(
+            mortgage().with_payment_override(dec!(2000)),
+            Err(MortgageError::PaymentBelowMinimum { .. })
+        ));
+        assert!(mortgage().with_payment_rounded_up_to(dec!(0)).is_err());
+    }
+
+    #[test]
+    fn override_counts_against_the_payment_increase_privilege() {
+        let enforced = mortgage().with_enforced_privileges().unwrap();
+        let regular = mortgage().payment().unwrap();
+
+        assert!(enforced
+            .clone()
+            .with_payment_override(regular * dec!(1.15))
+            .is_ok());
+        assert!(matches!(
+            enforced.clone().with_payment_override(regular * dec!(1.16)),
+            Err(MortgageError::PaymentIncreaseLimitExceeded { year: 1, .. })
+        ));
+        assert!(matches!(
+            enforced
+                .with_payment_rounded_up_to(dec!(100))
+                .unwrap()
+                .with_payment_increase(6, dec!(11)),
+            Err(MortgageError::PaymentIncreaseLimitExceeded { year: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn zero_payments_have_no_increase_to_measure() {
+        let free = CanadianMortgage::new(dec!(430000), dec!(0), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_interest_only(true);
+
+        assert!(free.check_prepayment_privileges().is_ok());
+        let enforced = free.with_enforced_privileges().unwrap();
+        assert_eq!(
+            enforced.with_payment_override(dec!(1000)).unwrap_err(),
+            MortgageError::InvalidPayment(dec!(0))
+        );
+    }
+
+    #[test]
+    fn no_override_prepays_exactly_zero() {
+        let schedule = mortgage().amortization_schedule().unwrap();
+
+        assert_eq!(schedule[0].prepayment.to_string(), "0");
+        assert_eq!(schedule[299].balance.to_string(), "0");
+    }
+}
@@ -0,0 +1,229 @@
+// How long it takes to pay off a mortgage with a chosen regular payment.
+use crate::{CanadianMortgage, Compounding, MortgageError, PaymentFrequency, Result};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// The number of regular payments, and the same time span in years and months, rounded up
+// to a whole month.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PayoffPeriod {
+    pub number_of_payments: u64,
+    pub years: u64,
+    pub months: u64,
+}
+
+impl PayoffPeriod {
+    pub(crate) fn new(
+        number_of_payments: u64,
+        payment_frequency: PaymentFrequency,
+    ) -> Result<PayoffPeriod> {
+        let payments_per_year = payment_frequency.payments_per_year();
+        let months = number_of_payments
+            .checked_mul(12)
+            .ok_or(MortgageError::NumericOverflow("payoff period"))?
+            .div_ceil(payments_per_year);
+
+        Ok(PayoffPeriod {
+            number_of_payments,
+            years: months / 12,
+            months: months % 12,
+        })
+    }
+}
+
+// n = ln(a / (a - p * r)) / ln(1 + r)
+// where p is the principal, a is the periodic payment and r is the periodic rate.
+// interest_rate is the annual rate as a percentage, compounded semi-annually.
+// The last payment is usually smaller than the others, but still counts as a payment.
+pub fn payoff_period(
+    principal: Decimal,
+    interest_rate: Decimal,
+    payment: Decimal,
+    payment_frequency: PaymentFrequency,
+) -> Result<PayoffPeriod> {
+    if principal <= dec!(0) {
+        return Err(MortgageError::InvalidPrincipal(principal));
+    }
+    if payment <= dec!(0) {
+        return Err(MortgageError::InvalidPayment(payment));
+    }
+
+    let rate = crate::rates::periodic_rate(
+        crate::rates::monthly_compounded_rate(interest_rate, Compounding::SemiAnnual)?,
+        payment_frequency,
+    )?;
+    let interest = principal
+        .checked_mul(rate)
+        .ok_or(MortgageError::NumericOverflow("interest"))?;
+    if payment <= interest {
+        return Err(MortgageError::PaymentBelowInterest { payment, interest });
+    }
+
+    let number_of_payments = if rate == dec!(0) {
+        principal / payment
+    } else {
+        let growth = (payment / (payment - interest))
+            .checked_ln()
+            .ok_or(MortgageError::NumericOverflow("payoff period"))?;
+        let per_payment = (dec!(1) + rate)
+            .checked_ln()
+            .ok_or(MortgageError::NumericOverflow("payoff period"))?;
+        growth / per_payment
+    };
+
+    // Rounding first stops a payment that pays off in exactly n payments from needing n + 1.
+    let number_of_payments = number_of_payments
+        .round_dp(6)
+        .ceil()
+        .to_u64()
+        .ok_or(MortgageError::NumericOverflow("payoff period"))?;
+
+    PayoffPeriod::new(number_of_payments, payment_frequency)
+}
+
+// When the mortgage is paid off with an extra amount added to every regular payment, compared
+// with the mortgage as it is.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PayoffTimeline {
+    pub payoff: PayoffPeriod,
+    pub payments_eliminated: u64,
+    pub interest_saved: Decimal,
+}
+
+impl CanadianMortgage {
+    // extra is paid with every regular payment, at the mortgage's payment frequency.
+    pub fn payoff_with_extra_payment(&self, extra: Decimal) -> Result<PayoffTimeline> {
+        if extra <= dec!(0) {
+            return Err(MortgageError::InvalidPrepayment {
+                payment_number: 1,
+                amount: extra,
+            });
+        }
+
+        // Payment increases are percentages of the original payment.
+        let percentage = extra / self.payment()? * dec!(100);
+        let baseline = self.summary()?;
+        let summary = self
+            .clone()
+            .with_payment_increase(1, percentage)?
+            .summary()?;
+
+        Ok(PayoffTimeline {
+            payoff: PayoffPeriod::new(summary.number_of_payments, self.payment_frequency)?,
+            payments_eliminated: baseline.number_of_payments - summary.number_of_payments,
+            interest_saved: baseline.total_interest - summary.total_interest,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CanadianMortgage;
+
+    #[test]
+    fn amortization_payment_matches_schedule() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        let period = payoff_period(
+            dec!(430000),
+            dec!(4.59),
+            mortgage.payment().unwrap(),
+            PaymentFrequency::Monthly,
+        )
+        .unwrap();
+        assert_eq!(period.number_of_payments, 300);
+        assert_eq!((period.years, period.months), (25, 0));
+
+        // Weekly payments compound weekly, so they finish a few weeks early.
+        let weekly =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Weekly).unwrap();
+        let period = payoff_period(
+            dec!(430000),
+            dec!(4.59),
+            weekly.payment().unwrap(),
+            PaymentFrequency::Weekly,
+        )
+        .unwrap();
+        assert_eq!(
+            period.number_of_payments,
+            weekly.amortization_schedule().unwrap().len() as u64
+        );
+        assert_eq!(
+            (period.years, period.months),
+            (25, 0),
+            "rounded up to a whole month"
+        );
+    }
+
+    #[test]
+    fn larger_payment_matches_schedule() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly)
+                .unwrap()
+                .with_payment_increase(1, dec!(25))
+                .unwrap();
+        let payment = mortgage.payment().unwrap() * dec!(1.25);
+        let period =
+            payoff_period(dec!(430000), dec!(4.59), payment, PaymentFrequency::Monthly).unwrap();
+
+        assert_eq!(
+            period.number_of_payments,
+            mortgage.amortization_schedule().unwrap().len() as u64
+        );
+        assert_eq!(period.years * 12 + period.months, period.number_of_payments);
+        assert!(period.years < 25);
+    }
+
+    #[test]
+    fn payment_must_cover_interest() {
+        assert!(matches!(
+            payoff_period(
+                dec!(430000),
+                dec!(4.59),
+                dec!(1000),
+                PaymentFrequency::Monthly
+            ),
+            Err(MortgageError::PaymentBelowInterest { .. })
+        ));
+    }
+
+    #[test]
+    fn too_many_payments_is_an_error() {
+        assert_eq!(
+            payoff_period(dec!(1e18), dec!(0), dec!(0.1), PaymentFrequency::Weekly),
+            Err(MortgageError::NumericOverflow("payoff period"))
+        );
+    }
+
+    #[test]
+    fn extra_payments_shorten_the_mortgage() {
+        let mortgage = CanadianMortgage::new(
+            dec!(430000),
+            dec!(4.59),
+            25,
+            PaymentFrequency::AcceleratedBiWeekly,
+        )
+        .unwrap();
+        let payment = mortgage.payment().unwrap();
+        let timeline = mortgage.payoff_with_extra_payment(dec!(100)).unwrap();
+
+        let expected = payoff_period(
+            dec!(430000),
+            dec!(4.59),
+            payment + dec!(100),
+            PaymentFrequency::AcceleratedBiWeekly,
+        )
+        .unwrap();
+        assert_eq!(timeline.payoff, expected);
+        assert_eq!(
+            timeline.payments_eliminated,
+            mortgage.amortization_schedule().unwrap().len() as u64 - expected.number_of_payments
+        );
+        assert!(timeline.interest_saved > dec!(0));
+
+        assert!(mortgage.payoff_with_extra_payment(dec!(0)).is_err());
+    }
+}
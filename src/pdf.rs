@@ -0,0 +1,102 @@
+// A printable amortization statement: the cost of borrowing disclosure on the first page, then
+// the schedule, on US Letter paper. Both are laid out as the plain-text tables in table.rs, in
+// Courier so the columns line up, with the schedule's heading repeated on every page.
+use crate::{BorrowingCharges, CanadianMortgage, MortgageError, Result, ScheduleTable};
+use printpdf::{BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference};
+
+const PAGE_WIDTH: Mm = Mm(215.9);
+const PAGE_HEIGHT: Mm = Mm(279.4);
+const MARGIN: f32 = 20.0;
+const TITLE_SIZE: f32 = 14.0;
+const TEXT_SIZE: f32 = 9.0;
+const LINE_HEIGHT: f32 = 4.0;
+
+impl CanadianMortgage {
+    // The statement as the bytes of a PDF file.
+    pub fn statement_pdf(&self, charges: &BorrowingCharges) -> Result<Vec<u8>> {
+        let disclosure = self.cost_of_borrowing_disclosure(charges)?.to_string();
+        let schedule = self.amortization_schedule()?;
+        let table = ScheduleTable::new(&schedule).to_string();
+        let mut table = table.lines();
+        let heading = table.next().unwrap_or_default();
+        let rows: Vec<&str> = table.collect();
+
+        let (document, page, layer) = PdfDocument::new(
+            "Amortization statement",
+            PAGE_WIDTH,
+            PAGE_HEIGHT,
+            "statement",
+        );
+        let title_font = font(&document, BuiltinFont::HelveticaBold)?;
+        let text_font = font(&document, BuiltinFont::Courier)?;
+
+        let layer = document.get_page(page).get_layer(layer);
+        let mut y = PAGE_HEIGHT.0 - MARGIN;
+        layer.use_text(
+            "Cost of borrowing disclosure",
+            TITLE_SIZE,
+            Mm(MARGIN),
+            Mm(y),
+            &title_font,
+        );
+        y -= 2.0 * LINE_HEIGHT;
+        for line in disclosure.lines() {
+            layer.use_text(line, TEXT_SIZE, Mm(MARGIN), Mm(y), &text_font);
+            y -= LINE_HEIGHT;
+        }
+
+        let rows_per_page = ((PAGE_HEIGHT.0 - 2.0 * MARGIN) / LINE_HEIGHT) as usize - 3;
+        for (i, page_rows) in rows.chunks(rows_per_page).enumerate() {
+            let (page, layer) = document.add_page(PAGE_WIDTH, PAGE_HEIGHT, "schedule");
+            let layer = document.get_page(page).get_layer(layer);
+            let mut y = PAGE_HEIGHT.0 - MARGIN;
+            if i == 0 {
+                layer.use_text(
+                    "Amortization schedule",
+                    TITLE_SIZE,
+                    Mm(MARGIN),
+                    Mm(y),
+                    &title_font,
+                );
+            }
+            y -= 2.0 * LINE_HEIGHT;
+            for line in core::iter::once(&heading).chain(page_rows) {
+                layer.use_text(*line, TEXT_SIZE, Mm(MARGIN), Mm(y), &text_font);
+                y -= LINE_HEIGHT;
+            }
+        }
+
+        document.save_to_bytes().map_err(pdf_error)
+    }
+}
+
+fn font(document: &PdfDocumentReference, font: BuiltinFont) -> Result<IndirectFontRef> {
+    document.add_builtin_font(font).map_err(pdf_error)
+}
+
+fn pdf_error(e: printpdf::Error) -> MortgageError {
+    MortgageError::Serialization(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+    use rust_decimal_macros::*;
+
+    #[test]
+    fn statement_is_a_pdf() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        let pdf = mortgage
+            .statement_pdf(&BorrowingCharges::default())
+            .unwrap();
+
+        assert!(pdf.starts_with(b"%PDF-"));
+        let weekly = CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Weekly)
+            .unwrap()
+            .statement_pdf(&BorrowingCharges::default())
+            .unwrap();
+        assert!(weekly.len() > pdf.len(), "more payments, more pages");
+    }
+}
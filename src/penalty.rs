@@ -0,0 +1,273 @@
+// Breaking a closed mortgage before the end of its term costs a prepayment penalty.
+// Fixed rate contracts usually charge the greater of three months' interest and the
+// interest rate differential (IRD); variable rate and convertible contracts usually charge three
+// months' interest. Open mortgages can be paid off at any time without a penalty.
+// The big banks calculate the IRD against their posted rates, which are well above the rates
+// they actually lend at, less only the discount given when the mortgage was signed. The smaller
+// the comparison rate, the bigger the IRD.
+// https://www.canada.ca/en/financial-consumer-agency/services/mortgages/break-mortgage-contract.html
+use crate::{CanadianMortgage, MortgageError, MortgageType, Result};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// Three months of interest on balance at the annual contract_rate percentage.
+pub fn three_months_interest(balance: Decimal, contract_rate: Decimal) -> Result<Decimal> {
+    crate::validate_rate(contract_rate)?;
+    balance
+        .checked_mul(contract_rate / dec!(100) * dec!(3) / dec!(12))
+        .ok_or(MortgageError::NumericOverflow("three months' interest"))
+}
+
+// The interest the lender loses by re-lending balance at comparison_rate instead of contract_rate
+// for the months_remaining in the term. comparison_rate is the lender's current rate for a term
+// closest to what is left of the contract. Zero when rates have gone up.
+pub fn interest_rate_differential(
+    balance: Decimal,
+    contract_rate: Decimal,
+    comparison_rate: Decimal,
+    months_remaining: u64,
+) -> Result<Decimal> {
+    crate::validate_rate(contract_rate)?;
+    crate::validate_rate(comparison_rate)?;
+
+    let years_remaining = crate::decimal_from_u64(months_remaining)? / dec!(12);
+    let differential = (contract_rate - comparison_rate).max(dec!(0)) / dec!(100);
+    Ok(balance * differential * years_remaining)
+}
+
+// The comparison rate for a posted rate IRD: current_posted_rate, the lender's posted rate for a
+// term closest to what is left of the contract, less the discount of contract_rate from
+// original_posted_rate, the posted rate when the mortgage was signed.
+pub fn posted_rate_comparison(
+    contract_rate: Decimal,
+    original_posted_rate: Decimal,
+    current_posted_rate: Decimal,
+) -> Result<Decimal> {
+    crate::validate_rate(contract_rate)?;
+    crate::validate_rate(original_posted_rate)?;
+    crate::validate_rate(current_posted_rate)?;
+    Ok((current_posted_rate - (original_posted_rate - contract_rate)).max(dec!(0)))
+}
+
+// The standard closed mortgage penalty: the greater of the IRD and three months' interest.
+pub fn break_penalty(
+    balance: Decimal,
+    contract_rate: Decimal,
+    comparison_rate: Decimal,
+    months_remaining: u64,
+) -> Result<Decimal> {
+    Ok(
+        three_months_interest(balance, contract_rate)?.max(interest_rate_differential(
+            balance,
+            contract_rate,
+            comparison_rate,
+            months_remaining,
+        )?),
+    )
+}
+
+// The penalty for breaking a mortgage of mortgage_type.
+pub fn break_penalty_for(
+    mortgage_type: MortgageType,
+    balance: Decimal,
+    contract_rate: Decimal,
+    comparison_rate: Decimal,
+    months_remaining: u64,
+) -> Result<Decimal> {
+    match mortgage_type {
+        MortgageType::Closed => {
+            break_penalty(balance, contract_rate, comparison_rate, months_remaining)
+        }
+        MortgageType::Convertible => three_months_interest(balance, contract_rate),
+        MortgageType::Open => {
+            crate::validate_rate(contract_rate)?;
+            Ok(dec!(0))
+        }
+    }
+}
+
+impl CanadianMortgage {
+    // The lender's posted rate for the term when the mortgage was signed, which the contract rate
+    // is discounted from. It can't be below the contract rate.
+    pub fn with_posted_rate(mut self, posted_rate: Decimal) -> Result<CanadianMortgage> {
+        crate::validate_rate(posted_rate)?;
+        if posted_rate < self.annual_rate {
+            return Err(MortgageError::InvalidRate(posted_rate));
+        }
+        self.posted_rate = Some(posted_rate);
+        Ok(self)
+    }
+
+    // The posted rate, or the contract rate when there was no discount.
+    pub fn posted_rate(&self) -> Decimal {
+        self.posted_rate.unwrap_or(self.annual_rate)
+    }
+
+    // How far the contract rate is below the posted rate, in percentage points.
+    pub fn rate_discount(&self) -> Decimal {
+        self.posted_rate() - self.annual_rate
+    }
+
+    // The rate the lender would offer at renewal if it kept the same discount off
+    // current_posted_rate.
+    pub fn renewal_rate(&self, current_posted_rate: Decimal) -> Result<Decimal> {
+        posted_rate_comparison(self.annual_rate, self.posted_rate(), current_posted_rate)
+    }
+
+    // The penalty as a big bank would quote it, comparing against current_posted_rate less the
+    // original discount; see posted_rate_comparison.
+    pub fn posted_rate_break_penalty(
+        &self,
+        payment_number: u64,
+        current_posted_rate: Decimal,
+    ) -> Result<Decimal> {
+        self.break_penalty(payment_number, self.renewal_rate(current_posted_rate)?)
+    }
+
+    // The penalty for breaking the mortgage right after payment_number, when the lender's
+    // rate for the remaining term is comparison_rate. Depends on the mortgage type.
+    pub fn break_penalty(&self, payment_number: u64, comparison_rate: Decimal) -> Result<Decimal> {
+        if payment_number > self.term_payments() {
+            return Err(MortgageError::BeyondTerm {
+                payment_number,
+                term_payments: self.term_payments(),
+            });
+        }
+
+        let months_elapsed = payment_number * 12 / self.payment_frequency.payments_per_year();
+        let months_remaining = self.term_years * 12 - months_elapsed;
+
+        break_penalty_for(
+            self.mortgage_type,
+            self.balance_at(payment_number)?,
+            self.annual_rate,
+            comparison_rate,
+            months_remaining,
+        )
+    }
+
+    // Everything owed for breaking the mortgage right after payment_number: the penalty and any
+    // cash back clawed back.
+    pub fn break_cost(&self, payment_number: u64, comparison_rate: Decimal) -> Result<Decimal> {
+        Ok(self.break_penalty(payment_number, comparison_rate)?
+            + self.cash_back_clawback(payment_number)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+
+    #[test]
+    fn three_months_of_interest() {
+        assert_eq!(
+            three_months_interest(dec!(400000), dec!(5)).unwrap(),
+            dec!(5000)
+        );
+    }
+
+    #[test]
+    fn interest_rate_differential_only_when_rates_fall() {
+        assert_eq!(
+            interest_rate_differential(dec!(400000), dec!(5), dec!(3), 36).unwrap(),
+            dec!(24000)
+        );
+        assert_eq!(
+            interest_rate_differential(dec!(400000), dec!(5), dec!(6), 36).unwrap(),
+            dec!(0)
+        );
+    }
+
+    #[test]
+    fn break_penalty_is_the_greater() {
+        assert_eq!(
+            break_penalty(dec!(400000), dec!(5), dec!(3), 36).unwrap(),
+            dec!(24000),
+            "rates fell, IRD applies"
+        );
+        assert_eq!(
+            break_penalty(dec!(400000), dec!(5), dec!(4.9), 12).unwrap(),
+            dec!(5000),
+            "three months' interest applies"
+        );
+    }
+
+    #[test]
+    fn breaking_a_mortgage_mid_term() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly)
+                .unwrap()
+                .with_term(5)
+                .unwrap();
+
+        let balance = mortgage.balance_at(24).unwrap();
+        assert_eq!(
+            mortgage.break_penalty(24, dec!(3.59)).unwrap(),
+            balance * dec!(0.01) * dec!(3)
+        );
+        assert!(mortgage.break_penalty(61, dec!(3.59)).is_err());
+    }
+
+    #[test]
+    fn posted_rate_discount_raises_the_ird() {
+        assert_eq!(
+            posted_rate_comparison(dec!(4.59), dec!(6.49), dec!(5.49)).unwrap(),
+            dec!(3.59)
+        );
+
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly)
+                .unwrap()
+                .with_term(5)
+                .unwrap()
+                .with_posted_rate(dec!(6.49))
+                .unwrap();
+        assert_eq!(mortgage.rate_discount(), dec!(1.90));
+        assert_eq!(mortgage.renewal_rate(dec!(5.49)).unwrap(), dec!(3.59));
+
+        let balance = mortgage.balance_at(24).unwrap();
+        assert_eq!(
+            mortgage.break_penalty(24, dec!(5.49)).unwrap(),
+            three_months_interest(balance, dec!(4.59)).unwrap(),
+            "against the posted rate itself, rates went up"
+        );
+        assert_eq!(
+            mortgage.posted_rate_break_penalty(24, dec!(5.49)).unwrap(),
+            balance * dec!(0.01) * dec!(3),
+            "less the discount, rates fell by 1%"
+        );
+        assert!(
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly)
+                .unwrap()
+                .with_posted_rate(dec!(4.49))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn penalty_depends_on_mortgage_type() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly)
+                .unwrap()
+                .with_term(5)
+                .unwrap();
+        let balance = mortgage.balance_at(24).unwrap();
+
+        assert_eq!(
+            mortgage
+                .clone()
+                .with_mortgage_type(MortgageType::Convertible)
+                .break_penalty(24, dec!(3.59))
+                .unwrap(),
+            three_months_interest(balance, dec!(4.59)).unwrap()
+        );
+        assert_eq!(
+            mortgage
+                .with_mortgage_type(MortgageType::Open)
+                .break_penalty(24, dec!(3.59))
+                .unwrap(),
+            dec!(0)
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use crate::{CanadianMortgage, MortgageError, Result};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// A one-off prepayment applied directly to the principal along with a regular payment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LumpSum {
+    pub payment_number: u64,
+    pub amount: Decimal,
+}
+
+// Increase the regular payment by percentage of the original payment from payment_number onwards.
+// Increases are cumulative: two 10% increases make the payment 120% of the original.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PaymentIncrease {
+    pub payment_number: u64,
+    pub percentage: Decimal,
+}
+
+// How much sooner and cheaper the mortgage is paid off with prepayments than without them.
+// payments_eliminated is zero when the prepayments don't shorten the mortgage.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrepaymentSavings {
+    pub payments_eliminated: u64,
+    pub interest_saved: Decimal,
+}
+
+impl CanadianMortgage {
+    // Pay amount towards the principal together with regular payment number payment_number.
+    // Lump sums on the same payment are added together.
+    // Prepayments beyond the privileges are an error if they are enforced; see with_enforced_privileges.
+    pub fn with_lump_sum(
+        mut self,
+        payment_number: u64,
+        amount: Decimal,
+    ) -> Result<CanadianMortgage> {
+        if payment_number == 0 || amount <= dec!(0) {
+            return Err(MortgageError::InvalidPrepayment {
+                payment_number,
+                amount,
+            });
+        }
+
+        self.lump_sums.push(LumpSum {
+            payment_number,
+            amount,
+        });
+        self.check_enforced_privileges()
+    }
+
+    pub fn lump_sums(&self) -> &[LumpSum] {
+        &self.lump_sums
+    }
+
+    // percentage is given as a percentage: 10 means the payment goes up by 10% of the original payment.
+    pub fn with_payment_increase(
+        mut self,
+        payment_number: u64,
+        percentage: Decimal,
+    ) -> Result<CanadianMortgage> {
+        if payment_number == 0 || percentage <= dec!(0) {
+            return Err(MortgageError::InvalidPrepayment {
+                payment_number,
+                amount: percentage,
+            });
+        }
+
+        self.payment_increases.push(PaymentIncrease {
+            payment_number,
+            percentage,
+        });
+        self.check_enforced_privileges()
+    }
+
+    pub fn payment_increases(&self) -> &[PaymentIncrease] {
+        &self.payment_increases
+    }
+
+    // Pay regular payment number payment_number twice, with the extra payment going entirely
+    // to principal. The extra payment is the regular payment as it stands, including any increases.
+    pub fn with_double_up(mut self, payment_number: u64) -> Result<CanadianMortgage> {
+        if payment_number == 0 {
+            return Err(MortgageError::InvalidPaymentNumber(payment_number));
+        }
+
+        if !self.double_ups.contains(&payment_number) {
+            self.double_ups.push(payment_number);
+        }
+        self.check_enforced_privileges()
+    }
+
+    pub fn double_ups(&self) -> &[u64] {
+        &self.double_ups
+    }
+
+    // Compare against the same mortgage with no prepayments.
+    pub fn prepayment_savings(&self) -> Result<PrepaymentSavings> {
+        let baseline = self.without_prepayments().summary()?;
+        let summary = self.summary()?;
+
+        Ok(PrepaymentSavings {
+            payments_eliminated: baseline
+                .number_of_payments
+                .saturating_sub(summary.number_of_payments),
+            interest_saved: baseline.total_interest - summary.total_interest,
+        })
+    }
+
+    pub(crate) fn has_prepayments(&self) -> bool {
+        !self.lump_sums.is_empty()
+            || !self.payment_increases.is_empty()
+            || !self.double_ups.is_empty()
+            || self.payment_override.is_some()
+    }
+
+    pub(crate) fn without_prepayments(&self) -> CanadianMortgage {
+        let mut mortgage = self.clone();
+        mortgage.lump_sums.clear();
+        mortgage.payment_increases.clear();
+        mortgage.double_ups.clear();
+        mortgage.payment_override = None;
+        mortgage
+    }
+
+    // The regular payment due on payment_number once any payment increases are applied.
+    pub(crate) fn payment_at(&self, payment_number: u64, payment: Decimal) -> Result<Decimal> {
+        let percentage: Decimal = self
+            .payment_increases
+            .iter()
+            .filter(|increase| increase.payment_number <= payment_number)
+            .map(|increase| increase.percentage)
+            .sum();
+
+        if percentage == dec!(0) {
+            return Ok(payment);
+        }
+        payment
+            .checked_mul(dec!(1) + percentage / dec!(100))
+            .map(|increased| self.rounding.round_payment(increased))
+            .ok_or(MortgageError::NumericOverflow("increased payment"))
+    }
+
+    // The total prepaid alongside regular payment number payment_number, which was regular_payment.
+    pub(crate) fn prepayment_at(&self, payment_number: u64, regular_payment: Decimal) -> Decimal {
+        let excess = self.payment_override_excess(regular_payment);
+        let lump_sums: Decimal = self
+            .lump_sums
+            .iter()
+            .filter(|lump_sum| lump_sum.payment_number == payment_number)
+            .map(|lump_sum| lump_sum.amount)
+            .sum();
+
+        if self.double_ups.contains(&payment_number) {
+            lump_sums + excess + regular_payment
+        } else {
+            lump_sums + excess
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+
+    fn mortgage() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap()
+    }
+
+    #[test]
+    fn invalid_lump_sums_are_rejected() {
+        assert!(mortgage().with_lump_sum(0, dec!(10000)).is_err());
+        assert!(mortgage().with_lump_sum(24, dec!(0)).is_err());
+    }
+
+    #[test]
+    fn lump_sum_reduces_balance_and_interest() {
+        let baseline = mortgage();
+        let prepaid = mortgage().with_lump_sum(24, dec!(10000)).unwrap();
+
+        let schedule = prepaid.amortization_schedule().unwrap();
+        assert_eq!(schedule[23].prepayment, dec!(10000));
+        assert_eq!(
+            schedule[23].balance.round_dp(6),
+            (baseline.balance_at(24).unwrap() - dec!(10000)).round_dp(6),
+            "lump sum comes straight off the principal"
+        );
+        assert_eq!(
+            prepaid.balance_at(24).unwrap(),
+            schedule[23].balance,
+            "balance_at reflects the lump sum"
+        );
+
+        let baseline = baseline.summary().unwrap();
+        let summary = prepaid.summary().unwrap();
+        assert!(summary.number_of_payments < baseline.number_of_payments);
+        assert!(summary.total_interest < baseline.total_interest);
+        assert_eq!(
+            (summary.total_payments - summary.total_interest).round_dp(10),
+            dec!(430000),
+            "lump sums are counted as payments"
+        );
+    }
+
+    #[test]
+    fn payment_increase_shortens_amortization() {
+        let mortgage = mortgage().with_payment_increase(13, dec!(10)).unwrap();
+        let payment = mortgage.payment().unwrap();
+        let schedule = mortgage.amortization_schedule().unwrap();
+
+        assert_eq!(schedule[11].payment, payment);
+        assert_eq!(schedule[12].payment, payment * dec!(1.1));
+
+        let savings = mortgage.prepayment_savings().unwrap();
+        assert!(savings.payments_eliminated > 0);
+        assert!(savings.interest_saved > dec!(0));
+    }
+
+    #[test]
+    fn payment_increases_compose_with_lump_sums() {
+        let increased = mortgage().with_payment_increase(13, dec!(10)).unwrap();
+        let both = increased
+            .clone()
+            .with_payment_increase(25, dec!(10))
+            .unwrap()
+            .with_lump_sum(24, dec!(10000))
+            .unwrap();
+
+        let schedule = both.amortization_schedule().unwrap();
+        assert_eq!(schedule[24].payment, both.payment().unwrap() * dec!(1.2));
+        assert_eq!(schedule[23].prepayment, dec!(10000));
+        assert!(
+            both.prepayment_savings().unwrap().interest_saved
+                > increased.prepayment_savings().unwrap().interest_saved
+        );
+    }
+
+    #[test]
+    fn double_up_prepays_a_regular_payment() {
+        let doubled = mortgage()
+            .with_payment_increase(13, dec!(10))
+            .unwrap()
+            .with_double_up(24)
+            .unwrap()
+            .with_lump_sum(24, dec!(10000))
+            .unwrap();
+        let schedule = doubled.amortization_schedule().unwrap();
+
+        assert_eq!(
+            schedule[23].prepayment,
+            schedule[23].payment + dec!(10000),
+            "the double-up composes with the lump sum"
+        );
+        assert_eq!(doubled.double_ups(), &[24]);
+        assert!(mortgage().with_double_up(0).is_err());
+    }
+
+    #[test]
+    fn lump_sum_can_pay_off_the_mortgage() {
+        let schedule = mortgage()
+            .with_lump_sum(12, dec!(1000000))
+            .unwrap()
+            .amortization_schedule()
+            .unwrap();
+
+        assert_eq!(schedule.len(), 12);
+        let last = schedule.last().unwrap();
+        assert_eq!(last.balance, dec!(0));
+        assert!(
+            last.prepayment < dec!(430000),
+            "only the balance owing is prepaid"
+        );
+    }
+}
@@ -0,0 +1,187 @@
+// Python bindings. Amounts are accepted as anything whose str() is a decimal number
+// (int, str or decimal.Decimal) and returned as decimal.Decimal so no precision is lost.
+// The pymethods and pyfunction macros expand to conversions clippy considers useless.
+#![allow(clippy::useless_conversion)]
+
+use crate::affordability::{self, DebtServiceLimits, Household};
+use crate::{CanadianMortgage, MortgageError, PaymentFrequency, ScheduleRow};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+impl From<MortgageError> for PyErr {
+    fn from(e: MortgageError) -> PyErr {
+        PyValueError::new_err(e.to_string())
+    }
+}
+
+#[pyclass(name = "CanadianMortgage")]
+struct PyCanadianMortgage {
+    mortgage: CanadianMortgage,
+}
+
+#[pymethods]
+impl PyCanadianMortgage {
+    #[new]
+    #[pyo3(signature = (principal, rate, amortization, frequency = "monthly"))]
+    fn new(
+        principal: &Bound<'_, PyAny>,
+        rate: &Bound<'_, PyAny>,
+        amortization: u64,
+        frequency: &str,
+    ) -> PyResult<Self> {
+        Ok(PyCanadianMortgage {
+            mortgage: CanadianMortgage::new(
+                to_decimal(principal)?,
+                to_decimal(rate)?,
+                amortization,
+                parse_frequency(frequency)?,
+            )?,
+        })
+    }
+
+    fn payment(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_py_decimal(py, self.mortgage.payment()?)
+    }
+
+    // A list of dicts with the same keys as ScheduleRow.
+    fn amortization_schedule<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.mortgage
+            .amortization_schedule()?
+            .iter()
+            .map(|row| schedule_row(py, row))
+            .collect()
+    }
+
+    fn summary<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let summary = self.mortgage.summary()?;
+        let dict = PyDict::new_bound(py);
+        dict.set_item("number_of_payments", summary.number_of_payments)?;
+        dict.set_item("total_payments", to_py_decimal(py, summary.total_payments)?)?;
+        dict.set_item("total_interest", to_py_decimal(py, summary.total_interest)?)?;
+        dict.set_item(
+            "interest_to_principal",
+            to_py_decimal(py, summary.interest_to_principal)?,
+        )?;
+        Ok(dict)
+    }
+}
+
+// GDS and TDS for a monthly mortgage payment; see affordability.rs.
+#[pyfunction]
+#[pyo3(signature = (monthly_payment, gross_annual_income, heating = None, property_tax = None, condo_fees = None, other_debts = None))]
+fn debt_service<'py>(
+    py: Python<'py>,
+    monthly_payment: &Bound<'py, PyAny>,
+    gross_annual_income: &Bound<'py, PyAny>,
+    heating: Option<&Bound<'py, PyAny>>,
+    property_tax: Option<&Bound<'py, PyAny>>,
+    condo_fees: Option<&Bound<'py, PyAny>>,
+    other_debts: Option<&Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let household = Household {
+        gross_annual_income: to_decimal(gross_annual_income)?,
+        heating: optional_decimal(heating)?,
+        property_tax: optional_decimal(property_tax)?,
+        condo_fees: optional_decimal(condo_fees)?,
+        other_debts: optional_decimal(other_debts)?,
+    };
+    let result = affordability::debt_service(
+        to_decimal(monthly_payment)?,
+        &household,
+        &DebtServiceLimits::default(),
+    )?;
+
+    let dict = PyDict::new_bound(py);
+    dict.set_item("gds", to_py_decimal(py, result.gds)?)?;
+    dict.set_item("tds", to_py_decimal(py, result.tds)?)?;
+    dict.set_item("gds_headroom", to_py_decimal(py, result.gds_headroom)?)?;
+    dict.set_item("tds_headroom", to_py_decimal(py, result.tds_headroom)?)?;
+    dict.set_item("passes", result.passes)?;
+    Ok(dict)
+}
+
+#[pymodule]
+fn canadian_mortgage(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyCanadianMortgage>()?;
+    m.add_function(wrap_pyfunction!(debt_service, m)?)?;
+    Ok(())
+}
+
+fn schedule_row<'py>(py: Python<'py>, row: &ScheduleRow) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    dict.set_item("payment_number", row.payment_number)?;
+    dict.set_item("payment", to_py_decimal(py, row.payment)?)?;
+    dict.set_item("interest", to_py_decimal(py, row.interest)?)?;
+    dict.set_item("principal", to_py_decimal(py, row.principal)?)?;
+    dict.set_item("prepayment", to_py_decimal(py, row.prepayment)?)?;
+    dict.set_item("balance", to_py_decimal(py, row.balance)?)?;
+    Ok(dict)
+}
+
+fn parse_frequency(frequency: &str) -> PyResult<PaymentFrequency> {
+    frequency
+        .parse()
+        .map_err(|e: MortgageError| PyValueError::new_err(e.to_string()))
+}
+
+fn to_decimal(value: &Bound<'_, PyAny>) -> PyResult<Decimal> {
+    let text = value.str()?.to_string();
+    Decimal::from_str(&text)
+        .or_else(|_| Decimal::from_scientific(&text))
+        .map_err(|e| PyValueError::new_err(format!("{}: {}", text, e)))
+}
+
+fn optional_decimal(value: Option<&Bound<'_, PyAny>>) -> PyResult<Decimal> {
+    value.map_or(Ok(Decimal::ZERO), to_decimal)
+}
+
+fn to_py_decimal(py: Python<'_>, value: Decimal) -> PyResult<PyObject> {
+    let decimal = py.import_bound("decimal")?.getattr("Decimal")?;
+    Ok(decimal.call1((value.to_string(),))?.unbind())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mortgage_from_python() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let module = PyModule::new_bound(py, "canadian_mortgage").unwrap();
+            canadian_mortgage(&module).unwrap();
+            let locals = PyDict::new_bound(py);
+            locals.set_item("cm", module).unwrap();
+
+            py.run_bound(
+                r#"
+from decimal import Decimal
+mortgage = cm.CanadianMortgage(430000, Decimal("4.59"), 25, "accelerated-biweekly")
+payment = mortgage.payment()
+assert isinstance(payment, Decimal)
+assert round(payment, 2) == Decimal("1200.75")
+
+schedule = mortgage.amortization_schedule()
+assert schedule[0]["payment_number"] == 1
+assert schedule[-1]["balance"] == 0
+assert mortgage.summary()["number_of_payments"] == len(schedule)
+
+result = cm.debt_service(2500, 120000, heating=100, property_tax=400, condo_fees=400, other_debts=500)
+assert result["gds"] == 32 and result["passes"]
+
+try:
+    cm.CanadianMortgage(430000, 120, 25)
+    assert False
+except ValueError:
+    pass
+"#,
+                None,
+                Some(&locals),
+            )
+            .unwrap();
+        });
+    }
+}
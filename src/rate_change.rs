@@ -0,0 +1,153 @@
+// A rate change applies a new rate from a payment onwards, without building a new mortgage.
+// The payment is recalculated at the new rate so the balance is still paid off by the end of the
+// amortization, as with an adjustable-payment variable mortgage when prime changes, or a renewal
+// at the end of the term.
+use crate::{CanadianMortgage, MortgageError, Result};
+#[cfg(feature = "chrono")]
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+
+// A new annual rate, as a percentage compounded like the original rate, that applies from
+// payment_number onwards.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RateChange {
+    pub payment_number: u64,
+    pub rate: Decimal,
+}
+
+impl CanadianMortgage {
+    // When several changes apply from the same payment, the last one applied wins.
+    pub fn apply_rate_change(
+        mut self,
+        payment_number: u64,
+        rate: Decimal,
+    ) -> Result<CanadianMortgage> {
+        if payment_number == 0 {
+            return Err(MortgageError::InvalidPaymentNumber(payment_number));
+        }
+        crate::validate_rate(rate)?;
+
+        self.rate_changes.push(RateChange {
+            payment_number,
+            rate,
+        });
+        self.rate_changes
+            .sort_by_key(|change| change.payment_number);
+        Ok(self)
+    }
+
+    // Apply rate from the first payment on or after date, with payments starting on
+    // first_payment_date. It is an error if date is after the last payment of the amortization.
+    #[cfg(feature = "chrono")]
+    pub fn apply_rate_change_on(
+        self,
+        first_payment_date: NaiveDate,
+        date: NaiveDate,
+        rate: Decimal,
+    ) -> Result<CanadianMortgage> {
+        let payments = self.amortization_period * self.payment_frequency.payments_per_year();
+        for payment_number in 1..=payments {
+            if self
+                .payment_frequency
+                .payment_date(first_payment_date, payment_number)?
+                >= date
+            {
+                return self.apply_rate_change(payment_number, rate);
+            }
+        }
+        Err(MortgageError::InvalidPaymentDate(date))
+    }
+
+    pub fn rate_changes(&self) -> &[RateChange] {
+        &self.rate_changes
+    }
+
+    pub(crate) fn rate_changes_at(&self, payment_number: u64) -> impl Iterator<Item = &RateChange> {
+        self.rate_changes
+            .iter()
+            .filter(move |change| change.payment_number == payment_number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+    use alloc::vec::Vec;
+    use rust_decimal_macros::*;
+
+    fn mortgage() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_term(5)
+            .unwrap()
+    }
+
+    #[test]
+    fn renewal_at_a_new_rate() {
+        let original = mortgage().amortization_schedule().unwrap();
+        let renewed = mortgage().apply_rate_change(61, dec!(5.79)).unwrap();
+        let schedule = renewed.amortization_schedule().unwrap();
+
+        assert_eq!(schedule[..60], original[..60]);
+        let balance = original[59].balance;
+        let renewal =
+            CanadianMortgage::new(balance, dec!(5.79), 20, PaymentFrequency::Monthly).unwrap();
+        assert_eq!(
+            schedule[60].payment.round_dp(10),
+            renewal.payment().unwrap().round_dp(10)
+        );
+        assert_eq!(
+            schedule[60].interest.round_dp(10),
+            (balance * renewal.periodic_rate().unwrap()).round_dp(10)
+        );
+        assert_eq!(schedule.len(), 300);
+        assert_eq!(renewed.balance_at(60).unwrap(), balance);
+        assert_eq!(renewed.payment().unwrap(), mortgage().payment().unwrap());
+    }
+
+    #[test]
+    fn changes_apply_in_order() {
+        let changed = mortgage()
+            .apply_rate_change(25, dec!(3.99))
+            .unwrap()
+            .apply_rate_change(13, dec!(5.29))
+            .unwrap();
+        let schedule = changed.amortization_schedule().unwrap();
+
+        assert_eq!(
+            changed
+                .rate_changes()
+                .iter()
+                .map(|c| c.payment_number)
+                .collect::<Vec<_>>(),
+            [13, 25]
+        );
+        assert!(schedule[12].payment > schedule[11].payment);
+        assert!(schedule[24].payment < schedule[23].payment);
+        assert!(mortgage().apply_rate_change(0, dec!(5)).is_err());
+        assert!(mortgage().apply_rate_change(12, dec!(101)).is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn change_on_a_date() {
+        let first_payment_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let changed = mortgage()
+            .apply_rate_change_on(
+                first_payment_date,
+                NaiveDate::from_ymd_opt(2024, 6, 15).unwrap(),
+                dec!(5.29),
+            )
+            .unwrap();
+        assert_eq!(changed.rate_changes()[0].payment_number, 7);
+        assert!(mortgage()
+            .apply_rate_change_on(
+                first_payment_date,
+                NaiveDate::from_ymd_opt(2049, 1, 2).unwrap(),
+                dec!(5.29)
+            )
+            .is_err());
+    }
+}
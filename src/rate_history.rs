@@ -0,0 +1,391 @@
+// Historical rates, to replay a variable rate mortgage over an actual window and see what it
+// would have cost. A history is a list of dated rates, each in effect until the next one.
+//
+// The built-in history is the Canadian chartered banks' prime rate from 2000, dated when the
+// change was announced; the banks' changes usually took effect the next day.
+use crate::{
+    MortgageError, PaymentFrequency, PrimeRateChange, Result, ScheduleRow, VariableRateMortgage,
+};
+use alloc::vec::Vec;
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// rate is a percentage, in effect from date.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HistoricalRate {
+    pub date: NaiveDate,
+    pub rate: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RateHistory {
+    rates: Vec<HistoricalRate>,
+}
+
+// The payments made from first_payment_date through the end of the window, with prime as it
+// was. interest_paid and balance are over those payments.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HistoricalReplay {
+    pub initial_prime_rate: Decimal,
+    pub prime_rate_changes: Vec<PrimeRateChange>,
+    pub schedule: Vec<ScheduleRow>,
+    pub interest_paid: Decimal,
+    pub balance: Decimal,
+}
+
+const CANADIAN_PRIME_RATE: &[(i32, u32, u32, Decimal)] = &[
+    (2000, 1, 1, dec!(6.50)),
+    (2000, 2, 2, dec!(6.75)),
+    (2000, 3, 21, dec!(7.00)),
+    (2000, 5, 17, dec!(7.50)),
+    (2001, 1, 23, dec!(7.25)),
+    (2001, 3, 6, dec!(6.75)),
+    (2001, 4, 17, dec!(6.50)),
+    (2001, 5, 29, dec!(6.25)),
+    (2001, 7, 17, dec!(6.00)),
+    (2001, 8, 28, dec!(5.75)),
+    (2001, 9, 17, dec!(5.25)),
+    (2001, 10, 23, dec!(4.50)),
+    (2001, 11, 27, dec!(4.00)),
+    (2002, 1, 15, dec!(3.75)),
+    (2002, 4, 16, dec!(4.00)),
+    (2002, 6, 4, dec!(4.25)),
+    (2002, 7, 16, dec!(4.50)),
+    (2003, 3, 4, dec!(4.75)),
+    (2003, 4, 15, dec!(5.00)),
+    (2003, 7, 15, dec!(4.75)),
+    (2003, 9, 3, dec!(4.50)),
+    (2004, 1, 20, dec!(4.25)),
+    (2004, 3, 2, dec!(4.00)),
+    (2004, 4, 13, dec!(3.75)),
+    (2004, 9, 8, dec!(4.00)),
+    (2004, 10, 19, dec!(4.25)),
+    (2005, 9, 7, dec!(4.50)),
+    (2005, 10, 18, dec!(4.75)),
+    (2005, 12, 6, dec!(5.00)),
+    (2006, 1, 24, dec!(5.25)),
+    (2006, 3, 7, dec!(5.50)),
+    (2006, 4, 25, dec!(5.75)),
+    (2006, 5, 24, dec!(6.00)),
+    (2007, 7, 10, dec!(6.25)),
+    (2007, 12, 4, dec!(6.00)),
+    (2008, 1, 22, dec!(5.75)),
+    (2008, 3, 4, dec!(5.25)),
+    (2008, 4, 22, dec!(4.75)),
+    (2008, 10, 8, dec!(4.25)),
+    (2008, 10, 21, dec!(4.00)),
+    (2008, 12, 9, dec!(3.50)),
+    (2009, 1, 20, dec!(3.00)),
+    (2009, 3, 3, dec!(2.50)),
+    (2009, 4, 21, dec!(2.25)),
+    (2010, 6, 1, dec!(2.50)),
+    (2010, 7, 20, dec!(2.75)),
+    (2010, 9, 8, dec!(3.00)),
+    (2015, 1, 28, dec!(2.85)),
+    (2015, 7, 15, dec!(2.70)),
+    (2017, 7, 12, dec!(2.95)),
+    (2017, 9, 6, dec!(3.20)),
+    (2018, 1, 17, dec!(3.45)),
+    (2018, 7, 11, dec!(3.70)),
+    (2018, 10, 24, dec!(3.95)),
+    (2020, 3, 4, dec!(3.45)),
+    (2020, 3, 13, dec!(2.95)),
+    (2020, 3, 27, dec!(2.45)),
+    (2022, 3, 2, dec!(2.70)),
+    (2022, 4, 13, dec!(3.20)),
+    (2022, 6, 1, dec!(3.70)),
+    (2022, 7, 13, dec!(4.70)),
+    (2022, 9, 7, dec!(5.45)),
+    (2022, 10, 26, dec!(5.95)),
+    (2022, 12, 7, dec!(6.45)),
+    (2023, 1, 25, dec!(6.70)),
+    (2023, 6, 7, dec!(6.95)),
+    (2023, 7, 12, dec!(7.20)),
+    (2024, 6, 5, dec!(6.95)),
+    (2024, 7, 24, dec!(6.70)),
+    (2024, 9, 4, dec!(6.45)),
+    (2024, 10, 23, dec!(5.95)),
+    (2024, 12, 11, dec!(5.45)),
+    (2025, 1, 29, dec!(5.20)),
+    (2025, 3, 12, dec!(4.95)),
+];
+
+impl RateHistory {
+    // rates in any order. It is an error if there are none, or two on the same date.
+    pub fn new(mut rates: Vec<HistoricalRate>) -> Result<RateHistory> {
+        if rates.is_empty() {
+            return Err(MortgageError::MissingField("rates"));
+        }
+        rates.sort_by_key(|rate| rate.date);
+        for rate in &rates {
+            crate::validate_rate(rate.rate)?;
+        }
+        if let Some(pair) = rates.windows(2).find(|pair| pair[0].date == pair[1].date) {
+            return Err(MortgageError::DuplicateRateDate(pair[0].date));
+        }
+        Ok(RateHistory { rates })
+    }
+
+    // The Canadian prime rate from January 2000 to March 2025.
+    pub fn canadian_prime_rate() -> RateHistory {
+        let rates = CANADIAN_PRIME_RATE
+            .iter()
+            .map(|&(year, month, day, rate)| HistoricalRate {
+                date: NaiveDate::from_ymd_opt(year, month, day).expect("valid date"),
+                rate,
+            })
+            .collect();
+        RateHistory { rates }
+    }
+
+    // Rows of date (as YYYY-MM-DD) and rate, after a header row. Other columns are ignored.
+    #[cfg(feature = "csv")]
+    pub fn from_csv(text: &str) -> Result<RateHistory> {
+        let mut reader = csv::Reader::from_reader(text.as_bytes());
+        let mut rates = Vec::new();
+        for record in reader.records() {
+            let record = record.map_err(|e| MortgageError::Serialization(e.to_string()))?;
+            let field = |i: usize| record.get(i).unwrap_or_default().trim();
+            let date = field(0)
+                .parse()
+                .map_err(|_| MortgageError::UnparsableDate(field(0).into()))?;
+            let rate = crate::parse::rate(field(1))?;
+            rates.push(HistoricalRate { date, rate });
+        }
+        RateHistory::new(rates)
+    }
+
+    pub fn rates(&self) -> &[HistoricalRate] {
+        &self.rates
+    }
+
+    // The rate in effect on date. It is an error if date is before the history starts.
+    pub fn rate_on(&self, date: NaiveDate) -> Result<Decimal> {
+        self.rates
+            .iter()
+            .rev()
+            .find(|rate| rate.date <= date)
+            .map(|rate| rate.rate)
+            .ok_or(MortgageError::NoRateOn(date))
+    }
+
+    // The changes after first_payment_date, each applying from the first payment on or after it,
+    // up to payment number payments.
+    pub fn prime_rate_changes(
+        &self,
+        first_payment_date: NaiveDate,
+        payment_frequency: PaymentFrequency,
+        payments: u64,
+    ) -> Result<Vec<PrimeRateChange>> {
+        let mut changes = Vec::new();
+        let mut payment_number = 1;
+        for rate in self
+            .rates
+            .iter()
+            .filter(|rate| rate.date > first_payment_date)
+        {
+            while payment_number <= payments
+                && payment_frequency.payment_date(first_payment_date, payment_number)? < rate.date
+            {
+                payment_number += 1;
+            }
+            if payment_number > payments {
+                break;
+            }
+            match changes.last_mut() {
+                Some(PrimeRateChange {
+                    payment_number: last,
+                    prime_rate,
+                }) if *last == payment_number => *prime_rate = rate.rate,
+                _ => changes.push(PrimeRateChange {
+                    payment_number,
+                    prime_rate: rate.rate,
+                }),
+            }
+        }
+        Ok(changes)
+    }
+}
+
+impl VariableRateMortgage {
+    // The mortgage replayed with prime as it was in history, from the prime rate on
+    // first_payment_date rather than the mortgage's own, for the payments through last_date.
+    // After the history ends, prime stays at its last rate.
+    pub fn replay(
+        &self,
+        history: &RateHistory,
+        first_payment_date: NaiveDate,
+        last_date: NaiveDate,
+    ) -> Result<HistoricalReplay> {
+        let initial_prime_rate = history.rate_on(first_payment_date)?;
+        let mortgage = self.with_prime_shift(initial_prime_rate - self.prime_rate())?;
+
+        let payments = self.amortization_period() * self.payment_frequency().payments_per_year();
+        let prime_rate_changes =
+            history.prime_rate_changes(first_payment_date, self.payment_frequency(), payments)?;
+        let mut schedule = mortgage.amortization_schedule(&prime_rate_changes)?;
+        let mut in_window = 0;
+        for row in &schedule {
+            if self
+                .payment_frequency()
+                .payment_date(first_payment_date, row.payment_number)?
+                > last_date
+            {
+                break;
+            }
+            in_window += 1;
+        }
+        schedule.truncate(in_window);
+
+        Ok(HistoricalReplay {
+            initial_prime_rate,
+            interest_paid: schedule.iter().map(|row| row.interest).sum(),
+            balance: schedule.last().map_or(self.principal(), |row| row.balance),
+            prime_rate_changes,
+            schedule,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn rates_are_looked_up_by_date() {
+        let history = RateHistory::canadian_prime_rate();
+
+        assert_eq!(history.rate_on(date(2010, 1, 1)), Ok(dec!(2.25)));
+        assert_eq!(history.rate_on(date(2022, 7, 13)), Ok(dec!(4.70)));
+        assert_eq!(history.rate_on(date(2030, 1, 1)), Ok(dec!(4.95)));
+        assert_eq!(
+            history.rate_on(date(1999, 12, 31)),
+            Err(MortgageError::NoRateOn(date(1999, 12, 31)))
+        );
+        assert!(history
+            .rates()
+            .windows(2)
+            .all(|pair| pair[0].date < pair[1].date && pair[0].rate != pair[1].rate));
+    }
+
+    #[test]
+    fn changes_apply_from_the_next_payment() {
+        let history = RateHistory::new(vec![
+            HistoricalRate {
+                date: date(2022, 1, 1),
+                rate: dec!(2.45),
+            },
+            HistoricalRate {
+                date: date(2022, 3, 2),
+                rate: dec!(2.70),
+            },
+            HistoricalRate {
+                date: date(2022, 4, 13),
+                rate: dec!(3.20),
+            },
+            HistoricalRate {
+                date: date(2022, 4, 20),
+                rate: dec!(3.45),
+            },
+        ])
+        .unwrap();
+        let changes = history
+            .prime_rate_changes(date(2022, 1, 1), PaymentFrequency::Monthly, 300)
+            .unwrap();
+
+        assert_eq!(
+            changes,
+            vec![
+                PrimeRateChange {
+                    payment_number: 4,
+                    prime_rate: dec!(2.70),
+                },
+                PrimeRateChange {
+                    payment_number: 5,
+                    prime_rate: dec!(3.45),
+                },
+            ]
+        );
+        assert_eq!(
+            history
+                .prime_rate_changes(date(2022, 1, 1), PaymentFrequency::Monthly, 3)
+                .unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn variable_mortgage_is_replayed_over_the_window() {
+        let mortgage = VariableRateMortgage::new(
+            dec!(430000),
+            dec!(6.00),
+            dec!(-0.50),
+            25,
+            PaymentFrequency::Monthly,
+        )
+        .unwrap();
+        let history = RateHistory::canadian_prime_rate();
+        let replay = mortgage
+            .replay(&history, date(2021, 1, 1), date(2024, 12, 31))
+            .unwrap();
+
+        assert_eq!(replay.initial_prime_rate, dec!(2.45));
+        assert_eq!(replay.schedule.len(), 48);
+        assert_eq!(replay.prime_rate_changes[0].payment_number, 16);
+        let at_prime = |prime_rate| {
+            VariableRateMortgage::new(
+                dec!(430000),
+                prime_rate,
+                dec!(-0.50),
+                25,
+                PaymentFrequency::Monthly,
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            replay.schedule[..15],
+            at_prime(dec!(2.45)).amortization_schedule(&[]).unwrap()[..15]
+        );
+        assert_eq!(replay.balance, replay.schedule[47].balance);
+        assert!(replay.interest_paid > dec!(0));
+    }
+
+    #[test]
+    fn histories_are_validated() {
+        assert_eq!(
+            RateHistory::new(vec![]),
+            Err(MortgageError::MissingField("rates"))
+        );
+        let rate = HistoricalRate {
+            date: date(2022, 1, 1),
+            rate: dec!(2.45),
+        };
+        assert_eq!(
+            RateHistory::new(vec![rate, rate]),
+            Err(MortgageError::DuplicateRateDate(date(2022, 1, 1)))
+        );
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn history_is_read_from_csv() {
+        let history =
+            RateHistory::from_csv("date,prime\n2022-03-02,2.70%\n2022-01-01,2.45\n").unwrap();
+
+        assert_eq!(history.rate_on(date(2022, 3, 1)), Ok(dec!(2.45)));
+        assert_eq!(history.rate_on(date(2022, 3, 2)), Ok(dec!(2.70)));
+        assert_eq!(
+            RateHistory::from_csv("date,prime\n2022-13-01,2.70\n"),
+            Err(MortgageError::UnparsableDate("2022-13-01".into()))
+        );
+    }
+}
@@ -0,0 +1,154 @@
+// Whether to rush a closing to keep a rate hold. A lender holds a rate until an expiry date; a
+// buyer whose closing falls after it can pay to close sooner (bridge financing, moving costs) and
+// keep the held rate, or close as planned at whatever the market rate is then. Today's market
+// rate is the best estimate of that, and the break-even is how far the market would have to move
+// by closing for the two to cost the same.
+use crate::{CanadianMortgage, MortgageError, Result};
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// Bisection steps for the break-even, enough for well under a hundredth of a basis point.
+const BREAK_EVEN_ITERATIONS: u32 = 40;
+const HIGHEST_RATE: Decimal = dec!(99);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RateHold {
+    pub rate: Decimal,
+    pub expiry: NaiveDate,
+}
+
+// value is the interest saved over the term at the held rate rather than the market rate,
+// negative when the market is lower. net_value takes off what rushing costs. The hold is worth
+// rushing for unless the market falls by more than break_even_market_move percentage points by
+// closing, to break_even_market_rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RateHoldValue {
+    pub days_to_expiry: i64,
+    pub held_payment: Decimal,
+    pub market_payment: Decimal,
+    pub value: Decimal,
+    pub net_value: Decimal,
+    pub break_even_market_rate: Decimal,
+    pub break_even_market_move: Decimal,
+}
+
+impl CanadianMortgage {
+    // The mortgage's own rate is replaced by the held and market rates. rush_cost is what closing
+    // before the hold expires costs over closing as planned.
+    pub fn rate_hold_value(
+        &self,
+        hold: &RateHold,
+        today: NaiveDate,
+        market_rate: Decimal,
+        rush_cost: Decimal,
+    ) -> Result<RateHoldValue> {
+        if today > hold.expiry {
+            return Err(MortgageError::RateHoldExpired(hold.expiry));
+        }
+        crate::validate_rate(hold.rate)?;
+        crate::validate_rate(market_rate)?;
+        let held = self.with_annual_rate(hold.rate)?;
+        let market = self.with_annual_rate(market_rate)?;
+        let held_interest = held.interest_paid_in_term()?;
+        let value = market.interest_paid_in_term()? - held_interest;
+
+        let mut low = dec!(0);
+        let mut high = HIGHEST_RATE;
+        for _ in 0..BREAK_EVEN_ITERATIONS {
+            let middle = (low + high) / dec!(2);
+            let saved = self.with_annual_rate(middle)?.interest_paid_in_term()? - held_interest;
+            if saved < rush_cost {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        let break_even_market_rate = (low + high) / dec!(2);
+
+        Ok(RateHoldValue {
+            days_to_expiry: (hold.expiry - today).num_days(),
+            held_payment: held.payment()?,
+            market_payment: market.payment()?,
+            value,
+            net_value: value - rush_cost,
+            break_even_market_rate,
+            break_even_market_move: break_even_market_rate - market_rate,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+
+    fn mortgage() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_term(5)
+            .unwrap()
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn hold_is_worth_the_interest_saved_over_the_term() {
+        let hold = RateHold {
+            rate: dec!(4.59),
+            expiry: date(2025, 3, 31),
+        };
+        let value = mortgage()
+            .rate_hold_value(&hold, date(2025, 3, 1), dec!(5.09), dec!(2000))
+            .unwrap();
+        let at_market =
+            CanadianMortgage::new(dec!(430000), dec!(5.09), 25, PaymentFrequency::Monthly)
+                .unwrap()
+                .with_term(5)
+                .unwrap();
+
+        assert_eq!(value.days_to_expiry, 30);
+        assert_eq!(value.held_payment, mortgage().payment().unwrap());
+        assert_eq!(value.market_payment, at_market.payment().unwrap());
+        assert_eq!(
+            value.value,
+            at_market.interest_paid_in_term().unwrap()
+                - mortgage().interest_paid_in_term().unwrap()
+        );
+        assert_eq!(value.net_value, value.value - dec!(2000));
+        assert!(value.net_value > dec!(0));
+
+        assert!(value.break_even_market_rate > dec!(4.59));
+        assert!(value.break_even_market_move < dec!(0));
+        let at_break_even = mortgage()
+            .rate_hold_value(
+                &hold,
+                date(2025, 3, 1),
+                value.break_even_market_rate,
+                dec!(2000),
+            )
+            .unwrap();
+        assert!(at_break_even.net_value.abs() < dec!(0.01));
+    }
+
+    #[test]
+    fn expired_holds_are_rejected() {
+        let hold = RateHold {
+            rate: dec!(4.59),
+            expiry: date(2025, 3, 31),
+        };
+
+        assert_eq!(
+            mortgage().rate_hold_value(&hold, date(2025, 4, 1), dec!(5.09), dec!(0)),
+            Err(MortgageError::RateHoldExpired(date(2025, 3, 31)))
+        );
+        let free = mortgage()
+            .rate_hold_value(&hold, date(2025, 3, 31), dec!(5.09), dec!(0))
+            .unwrap();
+        assert!((free.break_even_market_rate - dec!(4.59)).abs() < dec!(0.0001));
+    }
+}
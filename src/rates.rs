@@ -0,0 +1,209 @@
+// Converting annual interest rates between compounding frequencies, using the Canadian
+// conventions the rest of the crate is built on. A nominal rate compounded n times a year charges
+// rate / n each period; the effective annual rate is what that works out to once compounded over
+// a year. Rates are annual percentages, e.g. 6 for 6%, except in convert_compounding_basis and
+// periodic rates, which are decimal fractions.
+// https://en.wikipedia.org/wiki/Effective_interest_rate
+use crate::{decimal_from_u64, Compounding, MortgageError, PaymentFrequency, Result};
+#[cfg(feature = "fast-math")]
+use alloc::format;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// The effective annual rate of nominal_rate compounded periods_per_year times a year.
+pub fn effective_annual_rate(nominal_rate: Decimal, periods_per_year: u64) -> Result<Decimal> {
+    validate_periods(periods_per_year)?;
+    effective(nominal_rate, periods_per_year)
+}
+
+// The nominal rate compounded periods_per_year times a year that has effective_rate as its
+// effective annual rate.
+pub fn nominal_annual_rate(effective_rate: Decimal, periods_per_year: u64) -> Result<Decimal> {
+    validate_periods(periods_per_year)?;
+    Ok(convert_compounding_basis(effective_rate / dec!(100), 1, periods_per_year)? * dec!(100))
+}
+
+// The nominal rate compounded to_periods times a year that is equivalent to nominal_rate
+// compounded from_periods times a year, e.g. from 2 to 12 for a fixed rate paid monthly.
+pub fn convert_nominal_rate(
+    nominal_rate: Decimal,
+    from_periods: u64,
+    to_periods: u64,
+) -> Result<Decimal> {
+    Ok(convert_compounding_basis(nominal_rate / dec!(100), from_periods, to_periods)? * dec!(100))
+}
+
+// The interest charged each payment period, as a decimal fraction, on a fixed rate quoted as an
+// annual percentage compounded semi-annually, as Canadian fixed rate mortgages are.
+pub fn semi_annual_to_periodic(
+    rate: Decimal,
+    payment_frequency: PaymentFrequency,
+) -> Result<Decimal> {
+    periodic_rate(
+        monthly_compounded_rate(rate, Compounding::SemiAnnual)?,
+        payment_frequency,
+    )
+}
+
+// https://en.wikipedia.org/wiki/Compound_interest#Compounding_basis
+// r2 = ((1 + r1/n1) ** (n1/n2) - 1) * n2
+// where r1 is the interest rate with compounding frequency n1, and r2 is the interest rate with compounding frequency n2
+// Rates are decimal fractions, e.g. 0.06 for 6%, and frequencies are times per year.
+pub fn convert_compounding_basis(
+    rate: Decimal,
+    compounding_frequency1: u64,
+    compounding_frequency2: u64,
+) -> Result<Decimal> {
+    validate_periods(compounding_frequency1)?;
+    validate_periods(compounding_frequency2)?;
+    let n1 = decimal_from_u64(compounding_frequency1)?;
+    let n2 = decimal_from_u64(compounding_frequency2)?;
+
+    Ok((fractional_exponent(dec!(1) + (rate / n1), n1 / n2)? - dec!(1)) * n2)
+}
+
+// Computed in Decimal, which is good to about 27 significant digits.
+#[cfg(not(feature = "fast-math"))]
+fn fractional_exponent(base: Decimal, exponent: Decimal) -> Result<Decimal> {
+    base.checked_powd(exponent)
+        .ok_or(MortgageError::NumericOverflow("fractional exponent"))
+}
+
+// Computed in f64, which is faster but only good to about 15 significant digits.
+#[cfg(feature = "fast-math")]
+fn fractional_exponent(base: Decimal, exponent: Decimal) -> Result<Decimal> {
+    let base = base
+        .to_f64()
+        .ok_or_else(|| MortgageError::ConversionFailure(format!("Decimal to f64: {}", base)))?;
+
+    let exponent = exponent
+        .to_f64()
+        .ok_or_else(|| MortgageError::ConversionFailure(format!("Decimal to f64: {}", exponent)))?;
+
+    let power = base.powf(exponent);
+    Decimal::from_f64(power)
+        .ok_or_else(|| MortgageError::ConversionFailure(format!("f64 to Decimal: {}", power)))
+}
+
+// Compounding a whole number of times is exact, unlike the fractional powers of the conversions.
+pub(crate) fn effective(nominal_rate: Decimal, periods_per_year: u64) -> Result<Decimal> {
+    let n = Decimal::from(periods_per_year);
+    (dec!(1) + nominal_rate / dec!(100) / n)
+        .checked_powu(periods_per_year)
+        .and_then(|growth| (growth - dec!(1)).checked_mul(dec!(100)))
+        .ok_or(MortgageError::NumericOverflow("effective rate"))
+}
+
+// monthly_rate is an annual rate as a decimal fraction, compounded monthly.
+pub(crate) fn periodic_rate(
+    monthly_rate: Decimal,
+    payment_frequency: PaymentFrequency,
+) -> Result<Decimal> {
+    let payments_per_year = payment_frequency.payments_per_year();
+    if payments_per_year == 12 {
+        return Ok(monthly_rate / dec!(12));
+    }
+
+    let n = decimal_from_u64(payments_per_year)?;
+
+    Ok(convert_compounding_basis(monthly_rate, 12, payments_per_year)? / n)
+}
+
+// interest_rate is the annual interest rate as a percentage, compounded as given.
+pub(crate) fn monthly_compounded_rate(
+    interest_rate: Decimal,
+    compounding: Compounding,
+) -> Result<Decimal> {
+    crate::validate_rate(interest_rate)?;
+
+    // Convert the interest rate percentage to a decimal fraction
+    let interest_rate = interest_rate / dec!(100);
+
+    // Convert to a rate compounded monthly. For fixed rates this is from semi-annual compounding,
+    // which is the strangeness of Canadian mortgages.
+    match compounding {
+        Compounding::Monthly => Ok(interest_rate),
+        _ => convert_compounding_basis(interest_rate, compounding.periods_per_year(), 12),
+    }
+}
+
+fn validate_periods(periods_per_year: u64) -> Result<()> {
+    if periods_per_year == 0 {
+        return Err(MortgageError::InvalidCompoundingFrequency(periods_per_year));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fractional_exponent_is_accurate() {
+        let places = if cfg!(feature = "fast-math") { 14 } else { 26 };
+        assert_eq!(
+            fractional_exponent(dec!(1.03), dec!(2) / dec!(12))
+                .unwrap()
+                .round_dp(places),
+            dec!(1.0049386220311969784108341661).round_dp(places),
+            "sixth root of 1.03"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "fast-math", ignore = "exact to Decimal precision")]
+    fn check_compounding_basis() {
+        assert_eq!(
+            convert_compounding_basis(dec!(0.06), 2, 1).unwrap(),
+            dec!(0.0609),
+            "Rate for Canadian mortages"
+        );
+        assert_eq!(
+            convert_compounding_basis(dec!(0.06), 2, 12).unwrap(),
+            dec!(0.0592634643743637409300099932),
+            "equivalent rate compounded monthly"
+        );
+    }
+
+    #[test]
+    fn semi_annual_rates_per_payment() {
+        assert_eq!(
+            semi_annual_to_periodic(dec!(6), PaymentFrequency::Monthly).unwrap(),
+            convert_compounding_basis(dec!(0.06), 2, 12).unwrap() / dec!(12)
+        );
+        assert_eq!(
+            semi_annual_to_periodic(dec!(6), PaymentFrequency::Weekly)
+                .unwrap()
+                .round_dp(20),
+            (convert_compounding_basis(dec!(0.06), 2, 52).unwrap() / dec!(52)).round_dp(20)
+        );
+        assert!(convert_compounding_basis(dec!(0.06), 0, 12).is_err());
+    }
+
+    #[test]
+    fn effective_and_nominal_rates_round_trip() {
+        assert_eq!(effective_annual_rate(dec!(6), 2).unwrap(), dec!(6.09));
+        assert_eq!(
+            effective_annual_rate(dec!(6), 12).unwrap().round_dp(6),
+            dec!(6.167781)
+        );
+        assert_eq!(
+            nominal_annual_rate(dec!(6.09), 2).unwrap().round_dp(10),
+            dec!(6)
+        );
+        assert_eq!(effective_annual_rate(dec!(6), 1).unwrap(), dec!(6));
+        assert!(effective_annual_rate(dec!(6), 0).is_err());
+    }
+
+    #[test]
+    fn nominal_rates_convert_between_frequencies() {
+        let monthly = convert_nominal_rate(dec!(6), 2, 12).unwrap();
+        assert_eq!(monthly.round_dp(6), dec!(5.926346));
+        assert_eq!(
+            effective_annual_rate(monthly, 12).unwrap().round_dp(10),
+            dec!(6.09),
+            "equivalent rates have the same effective rate"
+        );
+        assert!(convert_nominal_rate(dec!(6), 2, 0).is_err());
+    }
+}
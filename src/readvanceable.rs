@@ -0,0 +1,167 @@
+// A readvanceable mortgage combines an amortizing mortgage with a HELOC under one registered
+// charge. As the mortgage principal is repaid, the HELOC limit grows by the same amount.
+// OSFI caps the revolving portion at 65% of the property value, and the combined borrowing at 80%.
+use crate::insurance::{self, MortgageClass};
+use crate::{CanadianMortgage, MortgageError, Result, ScheduleRow};
+use alloc::vec::Vec;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+const MAX_REVOLVING_LOAN_TO_VALUE: Decimal = dec!(65);
+// The same limit as for a conventional mortgage.
+const MAX_COMBINED_LOAN_TO_VALUE: Decimal = dec!(80);
+
+// One payment on the mortgage, with the HELOC limit and how much of it is left to draw
+// once the payment is made.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReadvanceableRow {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub row: ScheduleRow,
+    pub credit_limit: Decimal,
+    pub available_credit: Decimal,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReadvanceableMortgage {
+    mortgage: CanadianMortgage,
+    property_value: Decimal,
+    heloc_balance: Decimal,
+}
+
+impl ReadvanceableMortgage {
+    // The mortgage must be within the 80% combined limit; insured mortgages can't be readvanceable.
+    pub fn new(
+        mortgage: CanadianMortgage,
+        property_value: Decimal,
+    ) -> Result<ReadvanceableMortgage> {
+        let loan_to_value = insurance::loan_to_value(mortgage.principal(), property_value)?;
+        if MortgageClass::from_loan_to_value(loan_to_value).requires_insurance() {
+            return Err(MortgageError::ReadvanceLimitExceeded {
+                amount: mortgage.principal(),
+                limit: property_value * MAX_COMBINED_LOAN_TO_VALUE / dec!(100),
+            });
+        }
+
+        Ok(ReadvanceableMortgage {
+            mortgage,
+            property_value,
+            heloc_balance: dec!(0),
+        })
+    }
+
+    // The amount already drawn on the HELOC, which must fit within the limit at the start.
+    pub fn with_heloc_balance(mut self, balance: Decimal) -> Result<ReadvanceableMortgage> {
+        if balance < dec!(0) {
+            return Err(MortgageError::InvalidPrincipal(balance));
+        }
+        let limit = self.credit_limit(self.mortgage.principal());
+        if balance > limit {
+            return Err(MortgageError::ReadvanceLimitExceeded {
+                amount: balance,
+                limit,
+            });
+        }
+
+        self.heloc_balance = balance;
+        Ok(self)
+    }
+
+    pub fn mortgage(&self) -> &CanadianMortgage {
+        &self.mortgage
+    }
+
+    pub fn property_value(&self) -> Decimal {
+        self.property_value
+    }
+
+    pub fn heloc_balance(&self) -> Decimal {
+        self.heloc_balance
+    }
+
+    // The HELOC limit while mortgage_balance is owing: what is left of the combined limit,
+    // up to the revolving limit.
+    pub fn credit_limit(&self, mortgage_balance: Decimal) -> Decimal {
+        let revolving = self.property_value * MAX_REVOLVING_LOAN_TO_VALUE / dec!(100);
+        let combined = self.property_value * MAX_COMBINED_LOAN_TO_VALUE / dec!(100);
+        revolving.min(combined - mortgage_balance).max(dec!(0))
+    }
+
+    // The mortgage's amortization schedule with the HELOC limit and available credit after each payment.
+    pub fn schedule(&self) -> Result<Vec<ReadvanceableRow>> {
+        Ok(self
+            .mortgage
+            .amortization_schedule()?
+            .into_iter()
+            .map(|row| {
+                let credit_limit = self.credit_limit(row.balance);
+                ReadvanceableRow {
+                    available_credit: (credit_limit - self.heloc_balance).max(dec!(0)),
+                    credit_limit,
+                    row,
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+
+    fn mortgage(principal: Decimal) -> CanadianMortgage {
+        CanadianMortgage::new(principal, dec!(4.59), 25, PaymentFrequency::Monthly).unwrap()
+    }
+
+    #[test]
+    fn limit_grows_as_principal_is_repaid() {
+        let readvanceable = ReadvanceableMortgage::new(mortgage(dec!(400000)), dec!(500000))
+            .unwrap()
+            .with_heloc_balance(dec!(0))
+            .unwrap();
+        let schedule = readvanceable.schedule().unwrap();
+
+        assert_eq!(readvanceable.credit_limit(dec!(400000)), dec!(0));
+        assert_eq!(
+            schedule[0].credit_limit,
+            dec!(400000) - schedule[0].row.balance,
+            "every dollar of principal repaid can be borrowed again"
+        );
+        assert_eq!(
+            schedule.last().unwrap().credit_limit,
+            dec!(325000),
+            "capped at 65% once the mortgage is small enough"
+        );
+        assert!(schedule
+            .windows(2)
+            .all(|rows| rows[1].credit_limit >= rows[0].credit_limit));
+    }
+
+    #[test]
+    fn available_credit_is_net_of_heloc_balance() {
+        let readvanceable = ReadvanceableMortgage::new(mortgage(dec!(200000)), dec!(500000))
+            .unwrap()
+            .with_heloc_balance(dec!(150000))
+            .unwrap();
+        let schedule = readvanceable.schedule().unwrap();
+
+        assert_eq!(readvanceable.credit_limit(dec!(200000)), dec!(200000));
+        assert_eq!(
+            schedule[0].available_credit,
+            schedule[0].credit_limit - dec!(150000)
+        );
+        assert!(readvanceable.with_heloc_balance(dec!(200000.01)).is_err());
+    }
+
+    #[test]
+    fn combined_limit_is_enforced() {
+        assert_eq!(
+            ReadvanceableMortgage::new(mortgage(dec!(450000)), dec!(500000)).unwrap_err(),
+            MortgageError::ReadvanceLimitExceeded {
+                amount: dec!(450000),
+                limit: dec!(400000),
+            }
+        );
+    }
+}
@@ -0,0 +1,112 @@
+// After a lump sum the payment normally stays the same, so the mortgage is paid off sooner.
+// Recasting instead re-amortizes what is left owing, lowering the payment.
+use crate::{CanadianMortgage, MortgageError, Result};
+
+impl CanadianMortgage {
+    // After each lump sum, recalculate the payment so the balance is paid off by the end of
+    // amortization_period years from the start of the mortgage. Recasting over the original
+    // amortization period keeps the payoff date, and it can't be pushed any later.
+    pub fn recast(mut self, amortization_period: u64) -> Result<CanadianMortgage> {
+        if amortization_period == 0 {
+            return Err(MortgageError::InvalidAmortization(amortization_period));
+        }
+        if amortization_period > self.amortization_period {
+            return Err(MortgageError::RecastBeyondAmortization {
+                recast: amortization_period,
+                amortization: self.amortization_period,
+            });
+        }
+        self.recast = Some(amortization_period);
+        Ok(self)
+    }
+
+    // Keep the payment after lump sums, which is the default.
+    pub fn recast_keep_payment(mut self) -> CanadianMortgage {
+        self.recast = None;
+        self
+    }
+
+    // The amortization period lump sums are recast over, if any.
+    pub fn recast_amortization(&self) -> Option<u64> {
+        self.recast
+    }
+
+    pub(crate) fn has_lump_sum_at(&self, payment_number: u64) -> bool {
+        self.lump_sums
+            .iter()
+            .any(|lump_sum| lump_sum.payment_number == payment_number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+    use rust_decimal_macros::*;
+
+    fn mortgage() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_lump_sum(24, dec!(50000))
+            .unwrap()
+    }
+
+    #[test]
+    fn recast_lowers_the_payment() {
+        let kept = mortgage().amortization_schedule().unwrap();
+        let recast = mortgage().recast(25).unwrap();
+        let schedule = recast.amortization_schedule().unwrap();
+
+        assert_eq!(schedule[..24], kept[..24]);
+        assert!(schedule[24].payment < schedule[23].payment);
+        assert_eq!(schedule.len(), 300, "paid off on the original date");
+        assert!(kept.len() < 300, "keeping the payment pays off sooner");
+
+        let remaining = CanadianMortgage::new(
+            schedule[23].balance,
+            dec!(4.59),
+            23,
+            PaymentFrequency::Monthly,
+        )
+        .unwrap();
+        assert_eq!(
+            schedule[24].payment.round_dp(10),
+            remaining.payment().unwrap().round_dp(10)
+        );
+    }
+
+    #[test]
+    fn recast_over_a_new_amortization() {
+        let schedule = mortgage()
+            .recast(20)
+            .unwrap()
+            .amortization_schedule()
+            .unwrap();
+        assert_eq!(schedule.len(), 240);
+        assert_eq!(
+            mortgage()
+                .recast(20)
+                .unwrap()
+                .recast_keep_payment()
+                .amortization_schedule()
+                .unwrap(),
+            mortgage().amortization_schedule().unwrap()
+        );
+        assert!(mortgage().recast(0).is_err());
+    }
+
+    #[test]
+    fn recast_cannot_outlast_the_amortization() {
+        assert_eq!(
+            mortgage().recast(30).unwrap_err(),
+            MortgageError::RecastBeyondAmortization {
+                recast: 30,
+                amortization: 25,
+            }
+        );
+
+        let savings = mortgage().recast(25).unwrap().prepayment_savings().unwrap();
+        assert_eq!(savings.payments_eliminated, 0);
+        assert!(savings.interest_saved > dec!(0));
+    }
+}
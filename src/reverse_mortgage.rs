@@ -0,0 +1,162 @@
+// A reverse mortgage, like the CHIP reverse mortgage, lends against home equity with no payments.
+// Interest compounds semi-annually onto the balance, and everything is repaid when the home is
+// sold. The balance grows while the home appreciates, so the question is how much equity is left.
+// Lenders advance up to 55% of the home's value, less for younger borrowers.
+use crate::{insurance, MortgageError, Result};
+use alloc::vec::Vec;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+const MAX_LOAN_TO_VALUE: Decimal = dec!(55);
+
+// The position at the end of year. equity never goes below zero: the lender guarantees the
+// borrower never owes more than the home is worth when it is sold at fair market value.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReverseMortgageRow {
+    pub year: u64,
+    pub balance: Decimal,
+    pub home_value: Decimal,
+    pub equity: Decimal,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReverseMortgage {
+    advance: Decimal,
+    interest_rate: Decimal,
+    home_value: Decimal,
+}
+
+impl ReverseMortgage {
+    // advance is paid out as a lump sum. interest_rate is the annual rate as a percentage,
+    // compounded semi-annually.
+    pub fn new(
+        advance: Decimal,
+        interest_rate: Decimal,
+        home_value: Decimal,
+    ) -> Result<ReverseMortgage> {
+        if advance <= dec!(0) {
+            return Err(MortgageError::InvalidPrincipal(advance));
+        }
+        crate::validate_rate(interest_rate)?;
+        if insurance::loan_to_value(advance, home_value)? > MAX_LOAN_TO_VALUE {
+            return Err(MortgageError::ReverseMortgageLimitExceeded {
+                amount: advance,
+                limit: home_value * MAX_LOAN_TO_VALUE / dec!(100),
+            });
+        }
+
+        Ok(ReverseMortgage {
+            advance,
+            interest_rate,
+            home_value,
+        })
+    }
+
+    pub fn advance(&self) -> Decimal {
+        self.advance
+    }
+
+    pub fn interest_rate(&self) -> Decimal {
+        self.interest_rate
+    }
+
+    pub fn home_value(&self) -> Decimal {
+        self.home_value
+    }
+
+    // What is owing after years, with no payments made.
+    pub fn balance_at(&self, years: u64) -> Result<Decimal> {
+        let semi_annual_rate = self.interest_rate / dec!(100) / dec!(2);
+        years
+            .checked_mul(2)
+            .and_then(|periods| (dec!(1) + semi_annual_rate).checked_powu(periods))
+            .and_then(|growth| self.advance.checked_mul(growth))
+            .ok_or(MortgageError::NumericOverflow("reverse mortgage balance"))
+    }
+
+    // The balance, home value and equity at the end of each of years. appreciation is the
+    // annual change in the home's value as a percentage, and may be negative.
+    pub fn projection(&self, years: u64, appreciation: Decimal) -> Result<Vec<ReverseMortgageRow>> {
+        if appreciation <= dec!(-100) {
+            return Err(MortgageError::InvalidRate(appreciation));
+        }
+        let growth = dec!(1) + appreciation / dec!(100);
+        // The balance grows by the same factor every year, so it is carried forward rather than
+        // compounded from the start each year.
+        let interest_growth = self.balance_at(1)? / self.advance;
+
+        let mut rows = Vec::new();
+        let mut home_value = self.home_value;
+        let mut balance = self.advance;
+        for year in 1..=years {
+            home_value *= growth;
+            balance = balance
+                .checked_mul(interest_growth)
+                .ok_or(MortgageError::NumericOverflow("reverse mortgage balance"))?;
+            rows.push(ReverseMortgageRow {
+                year,
+                balance,
+                home_value,
+                equity: (home_value - balance).max(dec!(0)),
+            });
+        }
+
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reverse_mortgage() -> ReverseMortgage {
+        ReverseMortgage::new(dec!(200000), dec!(7), dec!(800000)).unwrap()
+    }
+
+    #[test]
+    fn interest_compounds_onto_the_balance() {
+        let reverse_mortgage = reverse_mortgage();
+        assert_eq!(reverse_mortgage.balance_at(0).unwrap(), dec!(200000));
+        assert_eq!(reverse_mortgage.balance_at(1).unwrap(), dec!(214245));
+        assert_eq!(
+            reverse_mortgage.balance_at(10).unwrap().round_dp(2),
+            dec!(397957.77),
+            "roughly doubles in ten years at 7%"
+        );
+    }
+
+    #[test]
+    fn equity_follows_appreciation() {
+        let projection = reverse_mortgage().projection(20, dec!(3)).unwrap();
+
+        assert_eq!(projection.len(), 20);
+        assert_eq!(projection[0].home_value, dec!(824000));
+        assert_eq!(projection[0].equity, dec!(824000) - dec!(214245));
+        assert!(projection
+            .windows(2)
+            .all(|rows| rows[1].balance > rows[0].balance));
+        assert_eq!(
+            projection[19].balance.round_dp(10),
+            reverse_mortgage().balance_at(20).unwrap().round_dp(10)
+        );
+
+        let falling = reverse_mortgage().projection(30, dec!(-5)).unwrap();
+        assert_eq!(
+            falling.last().unwrap().equity,
+            dec!(0),
+            "never owes more than the home is worth"
+        );
+    }
+
+    #[test]
+    fn advance_is_limited() {
+        assert!(ReverseMortgage::new(dec!(450000), dec!(7), dec!(800000)).is_err());
+        assert!(reverse_mortgage().projection(10, dec!(-100)).is_err());
+        assert_eq!(
+            reverse_mortgage().balance_at(1000),
+            Err(MortgageError::NumericOverflow("reverse mortgage balance"))
+        );
+    }
+}
@@ -0,0 +1,127 @@
+// Lenders round the regular payment, charge interest to the cent each period, and adjust the
+// final payment to whatever is left owing. A rounding policy reproduces their statements;
+// by default nothing is rounded.
+use crate::CanadianMortgage;
+use rust_decimal::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RoundingPolicy {
+    // Full Decimal precision.
+    #[default]
+    Unrounded,
+    // Payments and interest are rounded half to even to the cent.
+    HalfEvenToCent,
+    // Payments and interest are truncated to the cent.
+    TruncateToCent,
+    // Payments are rounded up to the next dollar, interest half to even to the cent.
+    UpToDollar,
+}
+
+impl RoundingPolicy {
+    pub fn round_payment(&self, payment: Decimal) -> Decimal {
+        match self {
+            RoundingPolicy::Unrounded => payment,
+            RoundingPolicy::HalfEvenToCent => {
+                payment.round_dp_with_strategy(2, RoundingStrategy::MidpointNearestEven)
+            }
+            RoundingPolicy::TruncateToCent => {
+                payment.round_dp_with_strategy(2, RoundingStrategy::ToZero)
+            }
+            RoundingPolicy::UpToDollar => payment.ceil(),
+        }
+    }
+
+    pub fn round_interest(&self, interest: Decimal) -> Decimal {
+        match self {
+            RoundingPolicy::Unrounded => interest,
+            RoundingPolicy::TruncateToCent => {
+                interest.round_dp_with_strategy(2, RoundingStrategy::ToZero)
+            }
+            RoundingPolicy::HalfEvenToCent | RoundingPolicy::UpToDollar => {
+                interest.round_dp_with_strategy(2, RoundingStrategy::MidpointNearestEven)
+            }
+        }
+    }
+}
+
+impl CanadianMortgage {
+    pub fn with_rounding(mut self, rounding: RoundingPolicy) -> CanadianMortgage {
+        self.rounding = rounding;
+        self
+    }
+
+    pub fn rounding(&self) -> RoundingPolicy {
+        self.rounding
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+    use rust_decimal_macros::*;
+
+    fn mortgage(rounding: RoundingPolicy) -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_rounding(rounding)
+    }
+
+    #[test]
+    fn policies_round_amounts() {
+        let amount = dec!(2401.495);
+        assert_eq!(RoundingPolicy::Unrounded.round_payment(amount), amount);
+        assert_eq!(
+            RoundingPolicy::HalfEvenToCent.round_payment(amount),
+            dec!(2401.50)
+        );
+        assert_eq!(
+            RoundingPolicy::HalfEvenToCent.round_payment(dec!(2401.485)),
+            dec!(2401.48)
+        );
+        assert_eq!(
+            RoundingPolicy::TruncateToCent.round_payment(amount),
+            dec!(2401.49)
+        );
+        assert_eq!(RoundingPolicy::UpToDollar.round_payment(amount), dec!(2402));
+        assert_eq!(
+            RoundingPolicy::UpToDollar.round_interest(amount),
+            dec!(2401.50)
+        );
+    }
+
+    #[test]
+    fn rounded_payment_with_adjusted_final_payment() {
+        let mortgage = mortgage(RoundingPolicy::HalfEvenToCent);
+        assert_eq!(mortgage.payment().unwrap(), dec!(2401.50));
+
+        let schedule = mortgage.amortization_schedule().unwrap();
+        let (last, rows) = schedule.split_last().unwrap();
+        assert!(rows.iter().all(|row| row.payment == dec!(2401.50)));
+        assert!(schedule
+            .iter()
+            .all(|row| row.interest == row.interest.round_dp(2)));
+        assert!(last.payment < dec!(2401.50), "final payment is adjusted");
+        assert_eq!(last.balance, dec!(0));
+
+        assert_eq!(
+            mortgage.balance_at(120).unwrap(),
+            schedule[119].balance,
+            "balance follows the rounded schedule"
+        );
+    }
+
+    #[test]
+    fn rounding_up_to_the_dollar_saves_interest() {
+        let rounded = mortgage(RoundingPolicy::UpToDollar);
+        assert_eq!(rounded.payment().unwrap(), dec!(2402));
+        assert!(
+            rounded.summary().unwrap().total_interest
+                < mortgage(RoundingPolicy::Unrounded)
+                    .summary()
+                    .unwrap()
+                    .total_interest
+        );
+    }
+}
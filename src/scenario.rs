@@ -0,0 +1,205 @@
+// A what-if of prepayments compared side by side with the mortgage as it is.
+use crate::payoff::PayoffPeriod;
+use crate::{CanadianMortgage, LumpSum, PaymentIncrease, Result, ScheduleRow, Summary};
+use alloc::string::String;
+use alloc::vec::Vec;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// A double-up pays an extra regular payment, as it stands on that payment, alongside it.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrepaymentScenario {
+    pub lump_sums: Vec<LumpSum>,
+    pub payment_increases: Vec<PaymentIncrease>,
+    pub double_ups: Vec<u64>,
+}
+
+// A named what-if: the mortgage with its prepayments, rate changes and renewals already applied,
+// as read from a scenario file by scenario_file.
+#[derive(Debug, Clone)]
+pub struct Scenario {
+    pub name: String,
+    pub mortgage: CanadianMortgage,
+}
+
+// Amounts are the scenario's minus the baseline's for the same payment number, counting
+// payments after either is paid off as zero, until both are.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScheduleDelta {
+    pub payment_number: u64,
+    pub payment: Decimal,
+    pub interest: Decimal,
+    pub principal: Decimal,
+    pub balance: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScenarioReport {
+    pub baseline: Summary,
+    pub scenario: Summary,
+    pub baseline_payoff: PayoffPeriod,
+    pub scenario_payoff: PayoffPeriod,
+    pub payments_eliminated: u64,
+    pub interest_saved: Decimal,
+    pub deltas: Vec<ScheduleDelta>,
+}
+
+impl PrepaymentScenario {
+    pub fn new() -> PrepaymentScenario {
+        PrepaymentScenario::default()
+    }
+
+    pub fn with_lump_sum(mut self, payment_number: u64, amount: Decimal) -> PrepaymentScenario {
+        self.lump_sums.push(LumpSum {
+            payment_number,
+            amount,
+        });
+        self
+    }
+
+    pub fn with_payment_increase(
+        mut self,
+        payment_number: u64,
+        percentage: Decimal,
+    ) -> PrepaymentScenario {
+        self.payment_increases.push(PaymentIncrease {
+            payment_number,
+            percentage,
+        });
+        self
+    }
+
+    pub fn with_double_up(mut self, payment_number: u64) -> PrepaymentScenario {
+        self.double_ups.push(payment_number);
+        self
+    }
+
+    // The mortgage with the scenario's prepayments added to any it already has.
+    pub fn apply(&self, mortgage: &CanadianMortgage) -> Result<CanadianMortgage> {
+        let mut mortgage = mortgage.clone();
+        for increase in &self.payment_increases {
+            mortgage =
+                mortgage.with_payment_increase(increase.payment_number, increase.percentage)?;
+        }
+        for lump_sum in &self.lump_sums {
+            mortgage = mortgage.with_lump_sum(lump_sum.payment_number, lump_sum.amount)?;
+        }
+
+        for payment_number in &self.double_ups {
+            mortgage = mortgage.with_double_up(*payment_number)?;
+        }
+
+        Ok(mortgage)
+    }
+}
+
+impl CanadianMortgage {
+    pub fn compare_scenario(&self, scenario: &PrepaymentScenario) -> Result<ScenarioReport> {
+        let baseline_schedule = self.amortization_schedule()?;
+        let scenario_schedule = scenario.apply(self)?.amortization_schedule()?;
+        let baseline = self.summarize(&baseline_schedule);
+        let summary = self.summarize(&scenario_schedule);
+
+        let paid_off = ScheduleRow {
+            payment_number: 0,
+            payment: dec!(0),
+            interest: dec!(0),
+            principal: dec!(0),
+            prepayment: dec!(0),
+            balance: dec!(0),
+        };
+        let deltas = (0..baseline_schedule.len().max(scenario_schedule.len()))
+            .map(|i| {
+                let base = baseline_schedule.get(i).unwrap_or(&paid_off);
+                let row = scenario_schedule.get(i).unwrap_or(&paid_off);
+                ScheduleDelta {
+                    payment_number: i as u64 + 1,
+                    payment: row.payment + row.prepayment - base.payment - base.prepayment,
+                    interest: row.interest - base.interest,
+                    principal: row.principal + row.prepayment - base.principal - base.prepayment,
+                    balance: row.balance - base.balance,
+                }
+            })
+            .collect();
+
+        Ok(ScenarioReport {
+            baseline_payoff: PayoffPeriod::new(
+                baseline.number_of_payments,
+                self.payment_frequency,
+            )?,
+            scenario_payoff: PayoffPeriod::new(summary.number_of_payments, self.payment_frequency)?,
+            payments_eliminated: baseline
+                .number_of_payments
+                .saturating_sub(summary.number_of_payments),
+            interest_saved: baseline.total_interest - summary.total_interest,
+            baseline,
+            scenario: summary,
+            deltas,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+
+    fn mortgage() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap()
+    }
+
+    #[test]
+    fn double_up_prepays_the_regular_payment() {
+        let scenario = PrepaymentScenario::new()
+            .with_payment_increase(13, dec!(10))
+            .with_double_up(24);
+        let schedule = scenario
+            .apply(&mortgage())
+            .unwrap()
+            .amortization_schedule()
+            .unwrap();
+
+        assert_eq!(schedule[23].prepayment, schedule[23].payment);
+        assert_eq!(
+            schedule[23].payment,
+            mortgage().payment().unwrap() * dec!(1.1)
+        );
+    }
+
+    #[test]
+    fn report_compares_scenario_with_baseline() {
+        let scenario = PrepaymentScenario::new()
+            .with_lump_sum(12, dec!(20000))
+            .with_double_up(24);
+        let report = mortgage().compare_scenario(&scenario).unwrap();
+
+        assert_eq!(report.baseline, mortgage().summary().unwrap());
+        assert_eq!(report.baseline_payoff.years, 25);
+        assert!(report.scenario_payoff.number_of_payments < 300);
+        assert_eq!(
+            report.payments_eliminated,
+            300 - report.scenario_payoff.number_of_payments
+        );
+        assert_eq!(
+            report.interest_saved,
+            report.baseline.total_interest - report.scenario.total_interest
+        );
+
+        assert_eq!(report.deltas.len(), 300);
+        assert_eq!(report.deltas[10].balance, dec!(0));
+        assert_eq!(report.deltas[11].principal, dec!(20000));
+        assert!(report.deltas[12].interest < dec!(0));
+        let saved: Decimal = report.deltas.iter().map(|delta| delta.interest).sum();
+        assert_eq!((-saved).round_dp(20), report.interest_saved.round_dp(20));
+    }
+
+    #[test]
+    fn invalid_prepayments_are_rejected() {
+        assert!(mortgage()
+            .compare_scenario(&PrepaymentScenario::new().with_lump_sum(0, dec!(1000)))
+            .is_err());
+    }
+}
@@ -0,0 +1,270 @@
+// Scenario files: what-ifs written down in TOML or YAML, so they can be kept under version
+// control and rerun. A file holds a list of scenarios, each a mortgage with its prepayments,
+// rate changes and renewals:
+//
+//     [[scenario]]
+//     name = "five-year fixed, renewing at 5.25%"
+//     principal = 430000
+//     interest_rate = 4.59
+//     amortization_period = 25
+//     term_years = 5
+//     payment_frequency = "accelerated biweekly"
+//     double_ups = [26]
+//
+//     [[scenario.lump_sums]]
+//     payment_number = 26
+//     amount = 10000
+//
+//     [[scenario.renewals]]
+//     rate = 5.25
+//
+// amortization_period defaults to 25 years, term_years to the amortization period and
+// payment_frequency to monthly. Each renewal starts when the previous term ends and lasts
+// term_years, by default as long as the first term. Unknown fields are an error, so a misspelt
+// one isn't silently ignored.
+use crate::{
+    CanadianMortgage, LumpSum, MortgageError, PaymentFrequency, PaymentIncrease,
+    PrepaymentScenario, RateChange, Result, Scenario,
+};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScenarioFile {
+    scenario: Vec<ScenarioDefinition>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScenarioDefinition {
+    name: String,
+    principal: Decimal,
+    interest_rate: Decimal,
+    #[serde(default = "default_amortization_period")]
+    amortization_period: u64,
+    #[serde(default)]
+    term_years: Option<u64>,
+    #[serde(default)]
+    payment_frequency: Option<String>,
+    #[serde(default)]
+    lump_sums: Vec<LumpSum>,
+    #[serde(default)]
+    payment_increases: Vec<PaymentIncrease>,
+    #[serde(default)]
+    double_ups: Vec<u64>,
+    #[serde(default)]
+    rate_changes: Vec<RateChange>,
+    #[serde(default)]
+    renewals: Vec<Renewal>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Renewal {
+    rate: Decimal,
+    #[serde(default)]
+    term_years: Option<u64>,
+}
+
+fn default_amortization_period() -> u64 {
+    25
+}
+
+#[cfg(feature = "toml")]
+pub fn from_toml(text: &str) -> Result<Vec<Scenario>> {
+    let file: ScenarioFile =
+        toml::from_str(text).map_err(|e| MortgageError::InvalidScenarioFile(e.to_string()))?;
+    file.scenarios()
+}
+
+#[cfg(feature = "yaml")]
+pub fn from_yaml(text: &str) -> Result<Vec<Scenario>> {
+    let file: ScenarioFile = serde_yaml::from_str(text)
+        .map_err(|e| MortgageError::InvalidScenarioFile(e.to_string()))?;
+    file.scenarios()
+}
+
+impl ScenarioFile {
+    fn scenarios(self) -> Result<Vec<Scenario>> {
+        self.scenario
+            .into_iter()
+            .map(ScenarioDefinition::into_scenario)
+            .collect()
+    }
+}
+
+impl ScenarioDefinition {
+    fn into_scenario(self) -> Result<Scenario> {
+        let payment_frequency = match &self.payment_frequency {
+            Some(frequency) => frequency.parse()?,
+            None => PaymentFrequency::Monthly,
+        };
+        let mut mortgage = CanadianMortgage::new(
+            self.principal,
+            self.interest_rate,
+            self.amortization_period,
+            payment_frequency,
+        )?;
+        if let Some(term_years) = self.term_years {
+            mortgage = mortgage.with_term(term_years)?;
+        }
+
+        for change in &self.rate_changes {
+            mortgage = mortgage.apply_rate_change(change.payment_number, change.rate)?;
+        }
+        let payments_per_year = payment_frequency.payments_per_year();
+        let mut years = mortgage.term_years();
+        for renewal in &self.renewals {
+            if years >= self.amortization_period {
+                return Err(MortgageError::InvalidTerm {
+                    term: years,
+                    amortization: self.amortization_period,
+                });
+            }
+            mortgage = mortgage.apply_rate_change(years * payments_per_year + 1, renewal.rate)?;
+            years += renewal.term_years.unwrap_or(mortgage.term_years());
+        }
+
+        let prepayments = PrepaymentScenario {
+            lump_sums: self.lump_sums,
+            payment_increases: self.payment_increases,
+            double_ups: self.double_ups,
+        };
+        Ok(Scenario {
+            name: self.name,
+            mortgage: prepayments.apply(&mortgage)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::*;
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn scenarios_are_read_from_toml() {
+        let scenarios = from_toml(
+            r#"
+            [[scenario]]
+            name = "as is"
+            principal = 430000
+            interest_rate = 4.59
+
+            [[scenario]]
+            name = "renewing higher with a lump sum"
+            principal = 430000
+            interest_rate = 4.59
+            term_years = 5
+            payment_frequency = "monthly"
+
+            [[scenario.lump_sums]]
+            payment_number = 12
+            amount = 20000
+
+            [[scenario.renewals]]
+            rate = 5.25
+            term_years = 3
+
+            [[scenario.renewals]]
+            rate = 4.75
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(scenarios.len(), 2);
+        let as_is = &scenarios[0].mortgage;
+        let expected =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        assert_eq!(as_is.payment(), expected.payment());
+        assert_eq!(as_is.term_years(), 25);
+
+        let renewing = &scenarios[1];
+        assert_eq!(renewing.name, "renewing higher with a lump sum");
+        let expected = expected
+            .with_term(5)
+            .unwrap()
+            .apply_rate_change(61, dec!(5.25))
+            .unwrap()
+            .apply_rate_change(97, dec!(4.75))
+            .unwrap()
+            .with_lump_sum(12, dec!(20000))
+            .unwrap();
+        assert_eq!(
+            renewing.mortgage.amortization_schedule(),
+            expected.amortization_schedule()
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn invalid_scenario_files_are_rejected() {
+        let misspelt = from_toml(
+            "[[scenario]]\nname = \"a\"\nprincipal = 430000\ninterest_rate = 4.59\nlump_sum = []\n",
+        );
+        assert!(matches!(
+            misspelt,
+            Err(MortgageError::InvalidScenarioFile(_))
+        ));
+
+        let frequency = from_toml(
+            "[[scenario]]\nname = \"a\"\nprincipal = 430000\ninterest_rate = 4.59\npayment_frequency = \"fortnightly\"\n",
+        );
+        assert_eq!(
+            frequency.unwrap_err(),
+            MortgageError::UnknownPaymentFrequency("fortnightly".into())
+        );
+
+        let renewal = from_toml(
+            "[[scenario]]\nname = \"a\"\nprincipal = 430000\ninterest_rate = 4.59\n\n[[scenario.renewals]]\nrate = 5\n",
+        );
+        assert_eq!(
+            renewal.unwrap_err(),
+            MortgageError::InvalidTerm {
+                term: 25,
+                amortization: 25
+            }
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn scenarios_are_read_from_yaml() {
+        let scenarios = from_yaml(
+            "
+scenario:
+  - name: weekly with increases
+    principal: 430000
+    interest_rate: 4.59
+    payment_frequency: accelerated weekly
+    payment_increases:
+      - payment_number: 53
+        percentage: 10
+    rate_changes:
+      - payment_number: 27
+        rate: 4.25
+",
+        )
+        .unwrap();
+
+        let expected = CanadianMortgage::new(
+            dec!(430000),
+            dec!(4.59),
+            25,
+            PaymentFrequency::AcceleratedWeekly,
+        )
+        .unwrap()
+        .apply_rate_change(27, dec!(4.25))
+        .unwrap()
+        .with_payment_increase(53, dec!(10))
+        .unwrap();
+        assert_eq!(
+            scenarios[0].mortgage.amortization_schedule(),
+            expected.amortization_schedule()
+        );
+    }
+}
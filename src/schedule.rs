@@ -0,0 +1,399 @@
+use crate::{CanadianMortgage, DeferralOutcome, MortgageError, Result, RoundingPolicy};
+use alloc::vec::Vec;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// A balance smaller than half a cent is considered paid off.
+pub(crate) const PAID_OFF_TOLERANCE: Decimal = dec!(0.005);
+
+// One row of an amortization schedule.
+// payment is the regular payment, which is split between interest and principal.
+// prepayment is any extra amount paid with it, which goes entirely to principal.
+// balance is the principal still owing after the payment and prepayment are applied.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScheduleRow {
+    pub payment_number: u64,
+    pub payment: Decimal,
+    pub interest: Decimal,
+    pub principal: Decimal,
+    pub prepayment: Decimal,
+    pub balance: Decimal,
+}
+
+// The last payment of the schedule, which pays off exactly what is left owing rather than the
+// regular payment before it. residual is what it differs from the regular payment by, negative
+// when it is smaller.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FinalPayment {
+    pub payment_number: u64,
+    pub payment: Decimal,
+    pub regular_payment: Decimal,
+    pub residual: Decimal,
+}
+
+impl CanadianMortgage {
+    // Every payment from the first until the mortgage is paid off.
+    // Interest is charged on the outstanding balance at the periodic rate for the payment frequency,
+    // so accelerated frequencies naturally pay off in fewer payments than the amortization period.
+    // The final payment is reduced to exactly what is left owing.
+    // Interest is rounded according to the rounding policy.
+    // Prepayments are applied after the regular payment and shorten the schedule.
+    // An interest-only mortgage pays just the interest on each payment, for the term.
+    // A balloon payment pays off whatever is left owing.
+    // Double-ups are counted as prepayments.
+    // The interest on a skipped payment is added to the balance.
+    // After a payment deferral the payment may be recalculated for the new balance.
+    // Payments at an introductory rate are recalculated at the regular rate when it ends,
+    // and recalculated again at the new rate whenever the rate changes.
+    // A recast recalculates the payment after each lump sum, over the recast amortization.
+    pub fn amortization_schedule(&self) -> Result<Vec<ScheduleRow>> {
+        self.schedule_iter()?.collect()
+    }
+
+    // The amortization schedule one row at a time, computed as it is iterated, for when only
+    // some of the rows or a total over them are needed. After an error the iterator ends.
+    pub fn schedule_iter(&self) -> Result<ScheduleIter<'_>> {
+        let interest_rate = self.initial_interest_rate()?;
+        Ok(ScheduleIter {
+            mortgage: self,
+            payment: self.payment()?,
+            amortization_period: self.amortization_period,
+            interest_rate,
+            rate: crate::rates::periodic_rate(interest_rate, self.payment_frequency)?,
+            balance: self.principal,
+            payment_number: 0,
+            failed: false,
+        })
+    }
+
+    // The principal still owing after payment_number payments, without building the schedule.
+    // B = p * (1 + r)**n - a * ((1 + r)**n - 1) / r
+    // where p is the principal, a is the periodic payment and r is the periodic rate.
+    // Once the mortgage is paid off the balance stays at zero.
+    // Prepayments, rounding, interest-only, balloon and skipped payments, introductory rates and
+    // rate changes break the closed form, so the schedule is walked instead. Past the end of the
+    // schedule the balance stays where the schedule left it.
+    pub fn balance_at(&self, payment_number: u64) -> Result<Decimal> {
+        if self.has_prepayments()
+            || self.rounding != RoundingPolicy::Unrounded
+            || self.interest_only
+            || self.balloon_payment.is_some()
+            || !self.skipped_payments.is_empty()
+            || self.deferral.is_some()
+            || self.introductory_rate.is_some()
+            || !self.rate_changes.is_empty()
+        {
+            let mut balance = self.principal;
+            for row in self.schedule_iter()?.take(payment_number as usize) {
+                balance = row?.balance;
+            }
+            return Ok(balance);
+        }
+
+        let payment = self.payment()?;
+        let rate = self.periodic_rate()?;
+
+        let balance = crate::numeric::balance(self.principal, payment, rate, payment_number)
+            .ok_or(MortgageError::NumericOverflow("balance"))?;
+
+        if balance < PAID_OFF_TOLERANCE {
+            Ok(dec!(0))
+        } else {
+            Ok(balance)
+        }
+    }
+
+    // The adjusted final payment. The regular payment is the last one made before it.
+    // Rounded payments leave the final payment short of the regular payment, so that the
+    // schedule pays off exactly the principal borrowed.
+    pub fn final_payment(&self) -> Result<FinalPayment> {
+        let schedule = self.amortization_schedule()?;
+        let regular_payment = match schedule
+            .iter()
+            .rev()
+            .skip(1)
+            .find(|row| row.payment > dec!(0))
+        {
+            Some(row) => row.payment,
+            None => self.payment()?,
+        };
+        let (payment_number, payment) = schedule
+            .last()
+            .map_or((0, dec!(0)), |row| (row.payment_number, row.payment));
+
+        Ok(FinalPayment {
+            payment_number,
+            payment,
+            regular_payment,
+            residual: payment - regular_payment,
+        })
+    }
+}
+
+// The state of the schedule between payments; see CanadianMortgage::schedule_iter.
+#[derive(Debug, Clone)]
+pub struct ScheduleIter<'a> {
+    mortgage: &'a CanadianMortgage,
+    payment: Decimal,
+    amortization_period: u64,
+    interest_rate: Decimal,
+    rate: Decimal,
+    balance: Decimal,
+    payment_number: u64,
+    failed: bool,
+}
+
+impl ScheduleIter<'_> {
+    fn next_row(&mut self) -> Result<ScheduleRow> {
+        let mortgage = self.mortgage;
+        self.payment_number += 1;
+        let payment_number = self.payment_number;
+
+        let mut rate_changed = false;
+        if mortgage.introductory_rate.is_some()
+            && payment_number == mortgage.introductory_payments() + 1
+        {
+            self.interest_rate = mortgage.interest_rate;
+            rate_changed = true;
+        }
+        for change in mortgage.rate_changes_at(payment_number) {
+            self.interest_rate =
+                crate::rates::monthly_compounded_rate(change.rate, mortgage.compounding)?;
+            rate_changed = true;
+        }
+        if rate_changed {
+            self.rate =
+                crate::rates::periodic_rate(self.interest_rate, mortgage.payment_frequency)?;
+        }
+        let deferral_ended = mortgage.deferral.is_some_and(|deferral| {
+            deferral.outcome == DeferralOutcome::RecalculatePayment
+                && payment_number == deferral.first_payment_number + deferral.payments
+        });
+        let recast = mortgage
+            .recast
+            .filter(|_| mortgage.has_lump_sum_at(payment_number - 1));
+        if let Some(recast) = recast {
+            self.amortization_period = recast;
+        }
+        if rate_changed || deferral_ended || recast.is_some() {
+            self.payment = mortgage.recalculated_payment(
+                self.balance,
+                self.interest_rate,
+                self.amortization_period,
+                payment_number - 1,
+            )?;
+        }
+
+        let interest = mortgage.rounding.round_interest(
+            self.balance
+                .checked_mul(self.rate)
+                .ok_or(MortgageError::NumericOverflow("interest"))?,
+        );
+        if mortgage.is_skipped(payment_number) {
+            self.balance += interest;
+            return Ok(ScheduleRow {
+                payment_number,
+                payment: dec!(0),
+                interest,
+                principal: -interest,
+                prepayment: dec!(0),
+                balance: self.balance,
+            });
+        }
+        let payment = if mortgage.interest_only {
+            interest
+        } else if self.payment <= interest {
+            return Err(MortgageError::PaymentBelowInterest {
+                payment: self.payment,
+                interest,
+            });
+        } else {
+            self.payment
+        };
+
+        let regular_payment = mortgage.payment_at(payment_number, payment)?;
+        let principal = if self.balance + interest - regular_payment < PAID_OFF_TOLERANCE
+            || mortgage.balloon_payment == Some(payment_number)
+        {
+            self.balance
+        } else {
+            regular_payment - interest
+        };
+        self.balance -= principal;
+
+        let prepayment = mortgage
+            .prepayment_at(payment_number, regular_payment)
+            .min(self.balance);
+        self.balance -= prepayment;
+
+        Ok(ScheduleRow {
+            payment_number,
+            payment: interest + principal,
+            interest,
+            principal,
+            prepayment,
+            balance: self.balance,
+        })
+    }
+}
+
+impl Iterator for ScheduleIter<'_> {
+    type Item = Result<ScheduleRow>;
+
+    fn next(&mut self) -> Option<Result<ScheduleRow>> {
+        let mortgage = self.mortgage;
+        if self.failed
+            || self.balance <= dec!(0)
+            || (mortgage.interest_only && self.payment_number >= mortgage.term_payments())
+        {
+            return None;
+        }
+
+        let row = self.next_row();
+        self.failed = row.is_err();
+        Some(row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+
+    #[test]
+    fn monthly_schedule_pays_off_over_amortization() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        let schedule = mortgage.amortization_schedule().unwrap();
+
+        assert_eq!(schedule.len(), 300, "25 years of monthly payments");
+        assert_eq!(schedule.last().unwrap().balance, dec!(0), "fully paid off");
+
+        let first = &schedule[0];
+        assert_eq!(first.payment_number, 1);
+        assert_eq!(
+            first.interest.round_dp(2),
+            dec!(1629.24),
+            "first month's interest"
+        );
+        assert_eq!(first.interest + first.principal, first.payment);
+        assert_eq!(first.balance, dec!(430000) - first.principal);
+
+        let total_principal: Decimal = schedule.iter().map(|row| row.principal).sum();
+        assert_eq!(
+            total_principal.round_dp(10),
+            dec!(430000),
+            "principal portions add up to the loan"
+        );
+    }
+
+    #[test]
+    fn accelerated_schedule_pays_off_early() {
+        let mortgage = CanadianMortgage::new(
+            dec!(430000),
+            dec!(4.59),
+            25,
+            PaymentFrequency::AcceleratedBiWeekly,
+        )
+        .unwrap();
+        let schedule = mortgage.amortization_schedule().unwrap();
+
+        assert!(
+            schedule.len() < 25 * 26,
+            "accelerated payments shorten the amortization"
+        );
+        assert_eq!(schedule.last().unwrap().balance, dec!(0));
+        assert!(schedule
+            .windows(2)
+            .all(|rows| rows[1].balance < rows[0].balance));
+    }
+
+    #[test]
+    fn final_payment_reconciles_to_zero() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Weekly)
+                .unwrap()
+                .with_rounding(RoundingPolicy::HalfEvenToCent);
+        let schedule = mortgage.amortization_schedule().unwrap();
+        let last = schedule.last().unwrap();
+        let final_payment = mortgage.final_payment().unwrap();
+
+        assert_eq!(final_payment.payment_number, last.payment_number);
+        assert_eq!(final_payment.payment, last.payment);
+        assert_eq!(final_payment.regular_payment, mortgage.payment().unwrap());
+        assert_eq!(
+            final_payment.residual,
+            last.payment - mortgage.payment().unwrap()
+        );
+        assert!(final_payment.residual < dec!(0));
+        assert_eq!(final_payment.payment, final_payment.payment.round_dp(2));
+
+        let principal: Decimal = schedule.iter().map(|row| row.principal).sum();
+        assert_eq!(principal, dec!(430000), "not a fraction of a cent out");
+        let paid: Decimal = schedule.iter().map(|row| row.payment).sum();
+        let interest: Decimal = schedule.iter().map(|row| row.interest).sum();
+        assert_eq!(paid - interest, dec!(430000));
+    }
+
+    #[test]
+    fn rows_are_computed_as_they_are_iterated() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 40, PaymentFrequency::Weekly).unwrap();
+        let schedule = mortgage.amortization_schedule().unwrap();
+
+        let first = mortgage
+            .schedule_iter()
+            .unwrap()
+            .take(3)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(first, schedule[..3]);
+
+        let mut interest = dec!(0);
+        for row in mortgage.schedule_iter().unwrap() {
+            interest += row.unwrap().interest;
+        }
+        assert_eq!(interest, schedule.iter().map(|row| row.interest).sum());
+        assert_eq!(mortgage.schedule_iter().unwrap().count(), schedule.len());
+    }
+
+    #[test]
+    fn iteration_ends_after_an_error() {
+        let mortgage = CanadianMortgage::new(dec!(100000), dec!(50), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_skipped_payment(1)
+            .unwrap();
+        let mut rows = mortgage.schedule_iter().unwrap();
+
+        assert!(rows.next().unwrap().is_ok());
+        assert!(matches!(
+            rows.next(),
+            Some(Err(MortgageError::PaymentBelowInterest { .. }))
+        ));
+        assert!(rows.next().is_none());
+    }
+
+    #[test]
+    fn balance_at_matches_schedule() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Weekly).unwrap();
+        let schedule = mortgage.amortization_schedule().unwrap();
+
+        assert_eq!(mortgage.balance_at(0).unwrap(), dec!(430000));
+        for payment_number in &[1, 52, 260, 1000] {
+            assert_eq!(
+                mortgage.balance_at(*payment_number).unwrap().round_dp(6),
+                schedule[*payment_number as usize - 1].balance.round_dp(6),
+                "balance after payment {}",
+                payment_number
+            );
+        }
+        assert_eq!(
+            mortgage.balance_at(schedule.len() as u64 + 10).unwrap(),
+            dec!(0),
+            "paid off"
+        );
+    }
+}
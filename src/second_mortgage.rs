@@ -0,0 +1,113 @@
+// A second mortgage, or a private loan, registered on the same property behind the first mortgage.
+// The first mortgage is paid first if the property is sold, so lenders look at the combined
+// loan-to-value, and both payments count towards the debt service ratios.
+use crate::affordability::{self, DebtService, DebtServiceLimits, Household};
+use crate::{insurance, CanadianMortgage, PaymentFrequency, Result};
+use rust_decimal::prelude::*;
+
+#[derive(Debug, Clone)]
+pub struct StackedMortgage {
+    first: CanadianMortgage,
+    second: CanadianMortgage,
+}
+
+impl StackedMortgage {
+    pub fn new(first: CanadianMortgage, second: CanadianMortgage) -> StackedMortgage {
+        StackedMortgage { first, second }
+    }
+
+    pub fn first(&self) -> &CanadianMortgage {
+        &self.first
+    }
+
+    pub fn second(&self) -> &CanadianMortgage {
+        &self.second
+    }
+
+    // Both principals as a percentage of property_value.
+    pub fn combined_loan_to_value(&self, property_value: Decimal) -> Result<Decimal> {
+        insurance::loan_to_value(
+            self.first.principal() + self.second.principal(),
+            property_value,
+        )
+    }
+
+    // The two regular payments as a combined monthly amount.
+    pub fn monthly_payment(&self) -> Result<Decimal> {
+        Ok(monthly(&self.first)?.payment()? + monthly(&self.second)?.payment()?)
+    }
+
+    // Debt service ratios counting both payments as housing costs. The first mortgage is
+    // stress tested at its qualifying rate; a second mortgage or private loan is not, so its
+    // contract payment is used.
+    pub fn debt_service(
+        &self,
+        household: &Household,
+        limits: &DebtServiceLimits,
+    ) -> Result<DebtService> {
+        let payment =
+            monthly(&self.first)?.qualifying_payment()? + monthly(&self.second)?.payment()?;
+        affordability::debt_service(payment, household, limits)
+    }
+}
+
+// The same mortgage paid monthly, as debt service ratios are monthly.
+fn monthly(mortgage: &CanadianMortgage) -> Result<CanadianMortgage> {
+    let mut monthly = mortgage.clone();
+    monthly.payment_frequency = PaymentFrequency::Monthly;
+    Ok(monthly)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::*;
+
+    fn stacked() -> StackedMortgage {
+        let first = CanadianMortgage::new(dec!(400000), dec!(4.59), 25, PaymentFrequency::BiWeekly)
+            .unwrap();
+        let second = CanadianMortgage::new(dec!(50000), dec!(12), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_term(1)
+            .unwrap()
+            .with_interest_only(true);
+        StackedMortgage::new(first, second)
+    }
+
+    #[test]
+    fn combined_loan_to_value() {
+        assert_eq!(
+            stacked().combined_loan_to_value(dec!(500000)).unwrap(),
+            dec!(90)
+        );
+    }
+
+    #[test]
+    fn both_payments_count() {
+        let stacked = stacked();
+        let first_monthly =
+            CanadianMortgage::new(dec!(400000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        let second_payment = stacked.second().payment().unwrap();
+        assert_eq!(
+            stacked.monthly_payment().unwrap(),
+            first_monthly.payment().unwrap() + second_payment
+        );
+
+        let household = Household {
+            gross_annual_income: dec!(110000),
+            heating: dec!(100),
+            property_tax: dec!(400),
+            condo_fees: dec!(0),
+            other_debts: dec!(500),
+        };
+        let limits = DebtServiceLimits::default();
+        let combined = stacked.debt_service(&household, &limits).unwrap();
+        let first_only = first_monthly.debt_service(&household, &limits).unwrap();
+        assert_eq!(
+            combined.gds_headroom,
+            first_only.gds_headroom - second_payment
+        );
+        assert!(first_only.passes);
+        assert!(!combined.passes);
+    }
+}
@@ -0,0 +1,157 @@
+// How payments and lifetime interest respond to the rate, amortization and payment frequency,
+// evaluated over every combination at once. Each table is a matrix for one payment frequency,
+// with a row per amortization period and a column per rate, ready to render as a heat map.
+// The cells are evaluated in parallel with the rayon feature.
+use crate::{parallel, CanadianMortgage, PaymentFrequency, Result};
+use alloc::vec::Vec;
+use rust_decimal::prelude::*;
+
+// rates are annual percentages, compounded semi-annually.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SensitivityGrid {
+    pub principal: Decimal,
+    pub rates: Vec<Decimal>,
+    pub amortization_periods: Vec<u64>,
+    pub payment_frequencies: Vec<PaymentFrequency>,
+}
+
+// payments[i][j] and total_interest[i][j] are for amortization_periods[i] and rates[j].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SensitivityTable {
+    pub payment_frequency: PaymentFrequency,
+    pub rates: Vec<Decimal>,
+    pub amortization_periods: Vec<u64>,
+    pub payments: Vec<Vec<Decimal>>,
+    pub total_interest: Vec<Vec<Decimal>>,
+}
+
+impl SensitivityGrid {
+    // One table per payment frequency, in the order given.
+    pub fn evaluate(&self) -> Result<Vec<SensitivityTable>> {
+        let mut combinations = Vec::new();
+        for payment_frequency in &self.payment_frequencies {
+            for amortization_period in &self.amortization_periods {
+                for rate in &self.rates {
+                    combinations.push((*payment_frequency, *amortization_period, *rate));
+                }
+            }
+        }
+        let cells = parallel::try_map(
+            &combinations,
+            |(payment_frequency, amortization_period, rate)| {
+                self.cell(*rate, *amortization_period, *payment_frequency)
+            },
+        )?;
+
+        let mut cells = cells.into_iter();
+        let mut tables = Vec::new();
+        for payment_frequency in &self.payment_frequencies {
+            let mut payments = Vec::new();
+            let mut total_interest = Vec::new();
+            for _ in &self.amortization_periods {
+                let (payment_row, interest_row) = cells.by_ref().take(self.rates.len()).unzip();
+                payments.push(payment_row);
+                total_interest.push(interest_row);
+            }
+
+            tables.push(SensitivityTable {
+                payment_frequency: *payment_frequency,
+                rates: self.rates.clone(),
+                amortization_periods: self.amortization_periods.clone(),
+                payments,
+                total_interest,
+            });
+        }
+
+        Ok(tables)
+    }
+
+    fn cell(
+        &self,
+        rate: Decimal,
+        amortization_period: u64,
+        payment_frequency: PaymentFrequency,
+    ) -> Result<(Decimal, Decimal)> {
+        let mortgage =
+            CanadianMortgage::new(self.principal, rate, amortization_period, payment_frequency)?;
+        Ok((mortgage.payment()?, total_interest(&mortgage)?))
+    }
+}
+
+#[cfg(not(feature = "fast-math"))]
+fn total_interest(mortgage: &CanadianMortgage) -> Result<Decimal> {
+    Ok(mortgage.summary()?.total_interest)
+}
+
+// The payments are made in f64; see fast_math.
+#[cfg(feature = "fast-math")]
+fn total_interest(mortgage: &CanadianMortgage) -> Result<Decimal> {
+    use crate::fast_math;
+
+    let amortization = fast_math::amortize(
+        fast_math::to_f64(mortgage.principal())?,
+        fast_math::to_f64(mortgage.periodic_rate()?)?,
+        fast_math::to_f64(mortgage.payment()?)?,
+        u64::MAX,
+        false,
+    )?;
+    fast_math::from_f64(amortization.interest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use rust_decimal_macros::*;
+
+    fn grid() -> SensitivityGrid {
+        SensitivityGrid {
+            principal: dec!(430000),
+            rates: vec![dec!(3), dec!(4.59), dec!(6), dec!(8)],
+            amortization_periods: vec![15, 25, 30],
+            payment_frequencies: vec![PaymentFrequency::Monthly, PaymentFrequency::Weekly],
+        }
+    }
+
+    #[test]
+    fn every_combination_is_evaluated() {
+        let tables = grid().evaluate().unwrap();
+
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[1].payment_frequency, PaymentFrequency::Weekly);
+        assert_eq!(tables[0].payments.len(), 3);
+        assert!(tables[0].payments.iter().all(|row| row.len() == 4));
+
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        assert_eq!(tables[0].payments[1][1], mortgage.payment().unwrap());
+        assert_eq!(
+            tables[0].total_interest[1][1].round_dp(2),
+            mortgage.summary().unwrap().total_interest.round_dp(2)
+        );
+    }
+
+    #[test]
+    fn payments_rise_with_rates_and_fall_with_amortization() {
+        let monthly = &grid().evaluate().unwrap()[0];
+
+        for row in &monthly.payments {
+            assert!(row.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+        for column in 0..monthly.rates.len() {
+            assert!(monthly.payments[0][column] > monthly.payments[2][column]);
+            assert!(monthly.total_interest[0][column] < monthly.total_interest[2][column]);
+        }
+    }
+
+    #[test]
+    fn invalid_cells_are_errors() {
+        let grid = SensitivityGrid {
+            amortization_periods: vec![0],
+            ..grid()
+        };
+        assert!(grid.evaluate().is_err());
+    }
+}
@@ -0,0 +1,234 @@
+// CanadianMortgage is serialized as the parameters it was built from. The converted monthly rate
+// is recomputed, and the parameters validated, when it is deserialized.
+use crate::{
+    CanadianMortgage, Compounding, DayCount, IntroductoryRate, LumpSum, MortgageError,
+    MortgageType, PaymentDeferral, PaymentFrequency, PaymentIncrease, PaymentOverride,
+    PrepaymentPrivileges, RateChange, RoundingPolicy,
+};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct MortgageParameters {
+    principal: Decimal,
+    interest_rate: Decimal,
+    amortization_period: u64,
+    term_years: u64,
+    payment_frequency: PaymentFrequency,
+    qualifying_benchmark: Decimal,
+    insurance_premium: Decimal,
+    lump_sums: Vec<LumpSum>,
+    payment_increases: Vec<PaymentIncrease>,
+    #[serde(default)]
+    double_ups: Vec<u64>,
+    #[serde(default)]
+    rounding: RoundingPolicy,
+    #[serde(default)]
+    day_count: DayCount,
+    #[serde(default)]
+    compounding: Compounding,
+    #[serde(default)]
+    mortgage_type: MortgageType,
+    #[serde(default)]
+    prepayment_privileges: PrepaymentPrivileges,
+    #[serde(default)]
+    enforce_privileges: bool,
+    #[serde(default)]
+    interest_only: bool,
+    #[serde(default)]
+    balloon_payment: Option<u64>,
+    #[serde(default)]
+    skipped_payments: Vec<u64>,
+    #[serde(default)]
+    deferral: Option<PaymentDeferral>,
+    #[serde(default)]
+    posted_rate: Option<Decimal>,
+    #[serde(default)]
+    introductory_rate: Option<IntroductoryRate>,
+    #[serde(default)]
+    cash_back: Option<Decimal>,
+    #[serde(default)]
+    rate_changes: Vec<RateChange>,
+    #[serde(default)]
+    recast: Option<u64>,
+    #[serde(default)]
+    payment_override: Option<PaymentOverride>,
+}
+
+impl From<CanadianMortgage> for MortgageParameters {
+    fn from(mortgage: CanadianMortgage) -> Self {
+        MortgageParameters {
+            principal: mortgage.principal,
+            interest_rate: mortgage.annual_rate,
+            amortization_period: mortgage.amortization_period,
+            term_years: mortgage.term_years,
+            payment_frequency: mortgage.payment_frequency,
+            qualifying_benchmark: mortgage.qualifying_benchmark,
+            insurance_premium: mortgage.insurance_premium,
+            lump_sums: mortgage.lump_sums,
+            payment_increases: mortgage.payment_increases,
+            double_ups: mortgage.double_ups,
+            rounding: mortgage.rounding,
+            day_count: mortgage.day_count,
+            compounding: mortgage.compounding,
+            mortgage_type: mortgage.mortgage_type,
+            prepayment_privileges: mortgage.prepayment_privileges,
+            enforce_privileges: mortgage.enforce_privileges,
+            interest_only: mortgage.interest_only,
+            balloon_payment: mortgage.balloon_payment,
+            skipped_payments: mortgage.skipped_payments,
+            deferral: mortgage.deferral,
+            posted_rate: mortgage.posted_rate,
+            introductory_rate: mortgage.introductory_rate,
+            cash_back: mortgage.cash_back,
+            rate_changes: mortgage.rate_changes,
+            recast: mortgage.recast,
+            payment_override: mortgage.payment_override,
+        }
+    }
+}
+
+impl TryFrom<MortgageParameters> for CanadianMortgage {
+    type Error = MortgageError;
+
+    fn try_from(parameters: MortgageParameters) -> Result<Self, Self::Error> {
+        let mut mortgage = CanadianMortgage::new(
+            parameters.principal,
+            parameters.interest_rate,
+            parameters.amortization_period,
+            parameters.payment_frequency,
+        )?
+        .with_term(parameters.term_years)?
+        .with_qualifying_benchmark(parameters.qualifying_benchmark)?
+        .with_rounding(parameters.rounding)
+        .with_day_count(parameters.day_count)
+        .with_compounding(parameters.compounding)?
+        .with_mortgage_type(parameters.mortgage_type)
+        .with_prepayment_privileges(parameters.prepayment_privileges)?
+        .with_interest_only(parameters.interest_only);
+
+        if let Some(introductory) = parameters.introductory_rate {
+            mortgage = mortgage.with_introductory_rate(introductory.rate, introductory.months)?;
+        }
+        if let Some(amortization_period) = parameters.recast {
+            mortgage = mortgage.recast(amortization_period)?;
+        }
+        for change in parameters.rate_changes {
+            mortgage = mortgage.apply_rate_change(change.payment_number, change.rate)?;
+        }
+        if let Some(cash_back) = parameters.cash_back {
+            mortgage = mortgage.with_cash_back(cash_back)?;
+        }
+        if let Some(posted_rate) = parameters.posted_rate {
+            mortgage = mortgage.with_posted_rate(posted_rate)?;
+        }
+        if let Some(payment_number) = parameters.balloon_payment {
+            mortgage = mortgage.with_balloon_payment(payment_number)?;
+        }
+        for payment_number in parameters.skipped_payments {
+            mortgage = mortgage.with_skipped_payment(payment_number)?;
+        }
+        if let Some(deferral) = parameters.deferral {
+            mortgage = mortgage.with_payment_deferral(
+                deferral.first_payment_number,
+                deferral.payments,
+                deferral.outcome,
+            )?;
+        }
+        for lump_sum in parameters.lump_sums {
+            mortgage = mortgage.with_lump_sum(lump_sum.payment_number, lump_sum.amount)?;
+        }
+        for increase in parameters.payment_increases {
+            mortgage =
+                mortgage.with_payment_increase(increase.payment_number, increase.percentage)?;
+        }
+        for payment_number in parameters.double_ups {
+            mortgage = mortgage.with_double_up(payment_number)?;
+        }
+        mortgage = match parameters.payment_override {
+            Some(PaymentOverride::Amount(amount)) => mortgage.with_payment_override(amount)?,
+            Some(PaymentOverride::RoundUpTo(increment)) => {
+                mortgage.with_payment_rounded_up_to(increment)?
+            }
+            None => mortgage,
+        };
+        if parameters.enforce_privileges {
+            mortgage = mortgage.with_enforced_privileges()?;
+        }
+        mortgage.insurance_premium = parameters.insurance_premium;
+
+        Ok(mortgage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ScheduleRow, Summary};
+    use rust_decimal_macros::*;
+
+    #[test]
+    fn mortgage_round_trips() {
+        let mortgage = CanadianMortgage::new(
+            dec!(450000),
+            dec!(4.59),
+            25,
+            PaymentFrequency::AcceleratedBiWeekly,
+        )
+        .unwrap()
+        .with_default_insurance(dec!(500000))
+        .unwrap()
+        .with_term(5)
+        .unwrap()
+        .with_lump_sum(24, dec!(10000))
+        .unwrap();
+
+        let json = serde_json::to_string(&mortgage).unwrap();
+        assert!(json.contains("\"interest_rate\":\"4.59\""));
+        assert!(json.contains("\"payment_frequency\":\"AcceleratedBiWeekly\""));
+
+        let deserialized: CanadianMortgage = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.payment().unwrap(), mortgage.payment().unwrap());
+        assert_eq!(deserialized.term_years(), 5);
+        assert_eq!(
+            deserialized.insurance_premium(),
+            mortgage.insurance_premium()
+        );
+        assert_eq!(deserialized.lump_sums(), mortgage.lump_sums());
+    }
+
+    #[test]
+    fn invalid_mortgage_is_rejected() {
+        let json = r#"{
+            "principal": "100000",
+            "interest_rate": "120",
+            "amortization_period": 25,
+            "term_years": 5,
+            "payment_frequency": "Monthly",
+            "qualifying_benchmark": "5.25",
+            "insurance_premium": "0",
+            "lump_sums": [],
+            "payment_increases": []
+        }"#;
+        assert!(serde_json::from_str::<CanadianMortgage>(json).is_err());
+    }
+
+    #[test]
+    fn schedules_and_summaries_round_trip() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+
+        let schedule = mortgage.amortization_schedule().unwrap();
+        let json = serde_json::to_string(&schedule).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Vec<ScheduleRow>>(&json).unwrap(),
+            schedule
+        );
+
+        let summary = mortgage.summary().unwrap();
+        let json = serde_json::to_string(&summary).unwrap();
+        assert_eq!(serde_json::from_str::<Summary>(&json).unwrap(), summary);
+    }
+}
@@ -0,0 +1,259 @@
+// Monte Carlo simulation of a variable rate mortgage. Prime is stepped at each Bank of Canada
+// rate decision by a RateModel, the schedule is run over each simulated path, and the spread
+// of outcomes is reported as percentiles. Pass a seeded Rng for reproducible results. The paths
+// are drawn in order from the Rng, and the schedules run in parallel with the rayon feature, so
+// results are the same with or without it.
+use crate::{parallel, MortgageError, PrimeRateChange, Result, VariableRateMortgage};
+use alloc::vec::Vec;
+use rand::Rng;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// How prime moves from one rate decision to the next. Implement it for other distributions.
+pub trait RateModel {
+    // The prime rate, as a percentage, after the decision following one at prime_rate.
+    fn next_rate<R: Rng + ?Sized>(&self, prime_rate: Decimal, rng: &mut R) -> Result<Decimal>;
+}
+
+// Each decision moves prime by a normally distributed change with a standard deviation of
+// volatility percentage points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RandomWalk {
+    pub volatility: f64,
+}
+
+impl RateModel for RandomWalk {
+    fn next_rate<R: Rng + ?Sized>(&self, prime_rate: Decimal, rng: &mut R) -> Result<Decimal> {
+        Ok(prime_rate + decimal_from_f64(self.volatility * standard_normal(rng))?)
+    }
+}
+
+// A discrete Vasicek model: each decision closes speed, a fraction, of the gap between prime
+// and long_run_rate, plus a normally distributed shock with a standard deviation of volatility
+// percentage points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeanReverting {
+    pub long_run_rate: f64,
+    pub speed: f64,
+    pub volatility: f64,
+}
+
+impl RateModel for MeanReverting {
+    fn next_rate<R: Rng + ?Sized>(&self, prime_rate: Decimal, rng: &mut R) -> Result<Decimal> {
+        let rate = prime_rate
+            .to_f64()
+            .ok_or(MortgageError::NumericOverflow("prime rate"))?;
+        let change =
+            self.speed * (self.long_run_rate - rate) + self.volatility * standard_normal(rng);
+        Ok(prime_rate + decimal_from_f64(change)?)
+    }
+}
+
+// paths is how many rate paths to simulate. The Bank of Canada makes 8 rate decisions a year.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Simulation {
+    pub paths: usize,
+    pub decisions_per_year: u64,
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Simulation {
+            paths: 1000,
+            decisions_per_year: 8,
+        }
+    }
+}
+
+// Outcomes across the simulated paths, by nearest rank.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Percentiles {
+    pub p5: Decimal,
+    pub p25: Decimal,
+    pub median: Decimal,
+    pub p75: Decimal,
+    pub p95: Decimal,
+}
+
+impl Percentiles {
+    fn from_outcomes(mut outcomes: Vec<Decimal>) -> Percentiles {
+        outcomes.sort();
+        let rank = |percentile: usize| {
+            let index = (outcomes.len() * percentile).div_ceil(100).max(1) - 1;
+            outcomes[index]
+        };
+        Percentiles {
+            p5: rank(5),
+            p25: rank(25),
+            median: rank(50),
+            p75: rank(75),
+            p95: rank(95),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulationReport {
+    pub total_interest: Percentiles,
+    pub payoff_years: Percentiles,
+}
+
+impl VariableRateMortgage {
+    // Prime changes at each decision over the amortization period, starting with the first
+    // payment after the first decision. Prime never goes below zero, nor below what would make
+    // the rate negative. It is an error for any path to reach the trigger point for good with
+    // static payments.
+    pub fn simulate<M: RateModel, R: Rng + ?Sized>(
+        &self,
+        model: &M,
+        simulation: &Simulation,
+        rng: &mut R,
+    ) -> Result<SimulationReport> {
+        if simulation.paths == 0 {
+            return Err(MortgageError::MissingField("paths"));
+        }
+        let payments_per_year = self.payment_frequency().payments_per_year();
+        let decisions = self
+            .amortization_period()
+            .checked_mul(simulation.decisions_per_year)
+            .ok_or(MortgageError::NumericOverflow("number of rate decisions"))?;
+        let payments_per_year_decimal = crate::decimal_from_u64(payments_per_year)?;
+        let floor = dec!(0).max(-self.spread());
+
+        let mut paths = Vec::with_capacity(simulation.paths);
+        for _ in 0..simulation.paths {
+            let mut prime_rate = self.prime_rate();
+            let mut changes = Vec::new();
+            for decision in 1..=decisions {
+                prime_rate = model.next_rate(prime_rate, rng)?.max(floor);
+                let payments_before = decision
+                    .checked_mul(payments_per_year)
+                    .ok_or(MortgageError::NumericOverflow("number of payments"))?
+                    / simulation.decisions_per_year;
+                changes.push(PrimeRateChange {
+                    payment_number: 1 + payments_before,
+                    prime_rate,
+                });
+            }
+
+            paths.push(changes);
+        }
+
+        let outcomes = parallel::try_map(&paths, |changes| {
+            let (total_interest, payments) = self.totals(changes)?;
+            let payoff_years = crate::decimal_from_u64(payments)? / payments_per_year_decimal;
+            Ok((total_interest, payoff_years))
+        })?;
+        let (total_interest, payoff_years) = outcomes.into_iter().unzip();
+
+        Ok(SimulationReport {
+            total_interest: Percentiles::from_outcomes(total_interest),
+            payoff_years: Percentiles::from_outcomes(payoff_years),
+        })
+    }
+}
+
+// Box-Muller transform of two uniform samples.
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * core::f64::consts::PI * u2).cos()
+}
+
+fn decimal_from_f64(x: f64) -> Result<Decimal> {
+    Decimal::from_f64(x)
+        .ok_or_else(|| MortgageError::ConversionFailure(alloc::format!("f64 to Decimal: {}", x)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn mortgage() -> VariableRateMortgage {
+        VariableRateMortgage::new(
+            dec!(430000),
+            dec!(6.45),
+            dec!(-0.90),
+            25,
+            PaymentFrequency::Monthly,
+        )
+        .unwrap()
+    }
+
+    fn simulation() -> Simulation {
+        Simulation {
+            paths: 200,
+            ..Simulation::default()
+        }
+    }
+
+    #[test]
+    fn no_volatility_matches_the_schedule() {
+        let model = MeanReverting {
+            long_run_rate: 6.45,
+            speed: 0.5,
+            volatility: 0.0,
+        };
+        let report = mortgage()
+            .simulate(&model, &simulation(), &mut StdRng::seed_from_u64(1))
+            .unwrap();
+
+        let schedule = mortgage().amortization_schedule(&[]).unwrap();
+        let interest: Decimal = schedule.iter().map(|row| row.interest).sum();
+        assert_eq!(report.total_interest.p5.round_dp(2), interest.round_dp(2));
+        assert_eq!(report.total_interest.p5, report.total_interest.p95);
+        assert_eq!(report.payoff_years.median, dec!(25));
+    }
+
+    #[test]
+    fn outcomes_spread_with_volatility() {
+        let model = RandomWalk { volatility: 0.25 };
+        let report = mortgage()
+            .simulate(&model, &simulation(), &mut StdRng::seed_from_u64(1))
+            .unwrap();
+
+        let total_interest = &report.total_interest;
+        assert!(total_interest.p5 < total_interest.p25);
+        assert!(total_interest.p25 < total_interest.median);
+        assert!(total_interest.median < total_interest.p75);
+        assert!(total_interest.p75 < total_interest.p95);
+        assert_eq!(
+            report.payoff_years.p95,
+            dec!(25),
+            "adjustable payments keep the amortization"
+        );
+
+        let again = mortgage()
+            .simulate(&model, &simulation(), &mut StdRng::seed_from_u64(1))
+            .unwrap();
+        assert_eq!(report, again, "seeded runs are reproducible");
+    }
+
+    #[test]
+    fn too_many_decisions_is_an_error() {
+        let model = RandomWalk { volatility: 0.25 };
+        let simulation = Simulation {
+            decisions_per_year: u64::MAX,
+            ..simulation()
+        };
+        assert_eq!(
+            mortgage().simulate(&model, &simulation, &mut StdRng::seed_from_u64(1)),
+            Err(MortgageError::NumericOverflow("number of rate decisions"))
+        );
+    }
+
+    #[test]
+    fn percentiles_by_nearest_rank() {
+        let outcomes = (1..=100).map(Decimal::from).collect();
+        let percentiles = Percentiles::from_outcomes(outcomes);
+        assert_eq!(percentiles.p5, dec!(5));
+        assert_eq!(percentiles.median, dec!(50));
+        assert_eq!(percentiles.p95, dec!(95));
+    }
+}
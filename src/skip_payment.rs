@@ -0,0 +1,95 @@
+// Most lenders let borrowers skip a payment now and then. Nothing is paid, and the interest for
+// the period is added to the balance, so it is charged interest itself for the rest of the
+// amortization. The schedule shows a skipped payment as a payment of zero with the capitalized
+// interest as negative principal.
+use crate::{CanadianMortgage, MortgageError, Result};
+use rust_decimal::prelude::*;
+
+// How much longer and more expensive the mortgage is for the skipped payments.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SkippedPaymentCost {
+    pub payments_added: u64,
+    pub extra_interest: Decimal,
+}
+
+impl CanadianMortgage {
+    pub fn with_skipped_payment(mut self, payment_number: u64) -> Result<CanadianMortgage> {
+        if payment_number == 0 {
+            return Err(MortgageError::InvalidPaymentNumber(payment_number));
+        }
+
+        if !self.skipped_payments.contains(&payment_number) {
+            self.skipped_payments.push(payment_number);
+        }
+        Ok(self)
+    }
+
+    pub fn skipped_payments(&self) -> &[u64] {
+        &self.skipped_payments
+    }
+
+    // Compare against the same mortgage with every payment made.
+    pub fn skipped_payment_cost(&self) -> Result<SkippedPaymentCost> {
+        let mut baseline = self.clone();
+        baseline.skipped_payments.clear();
+        let baseline = baseline.summary()?;
+        let summary = self.summary()?;
+
+        Ok(SkippedPaymentCost {
+            payments_added: summary.number_of_payments - baseline.number_of_payments,
+            extra_interest: summary.total_interest - baseline.total_interest,
+        })
+    }
+
+    // Skipped on its own or as part of a payment deferral.
+    pub(crate) fn is_skipped(&self, payment_number: u64) -> bool {
+        self.skipped_payments.contains(&payment_number)
+            || self
+                .deferral
+                .is_some_and(|deferral| deferral.defers(payment_number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CanadianMortgage, PaymentFrequency};
+    use rust_decimal_macros::*;
+
+    fn mortgage() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap()
+    }
+
+    #[test]
+    fn skipped_interest_is_capitalized() {
+        let skipped = mortgage().with_skipped_payment(13).unwrap();
+        let schedule = skipped.amortization_schedule().unwrap();
+
+        let row = &schedule[12];
+        assert_eq!(row.payment, dec!(0));
+        assert_eq!(row.principal, -row.interest);
+        assert_eq!(row.balance, schedule[11].balance + row.interest);
+        assert_eq!(skipped.balance_at(13).unwrap(), row.balance);
+        assert_eq!(schedule[13].payment, skipped.payment().unwrap());
+
+        let summary = skipped.summary().unwrap();
+        assert_eq!(summary.capitalized_interest, row.interest);
+    }
+
+    #[test]
+    fn skipping_costs_more_than_the_interest_skipped() {
+        let skipped = mortgage().with_skipped_payment(13).unwrap();
+        let capitalized = skipped.amortization_schedule().unwrap()[12].interest;
+        let cost = skipped.skipped_payment_cost().unwrap();
+
+        assert_eq!(
+            cost.payments_added, 3,
+            "the skipped payment and two more at the end"
+        );
+        assert!(
+            cost.extra_interest > capitalized,
+            "the capitalized interest is charged interest for 24 more years"
+        );
+        assert!(mortgage().with_skipped_payment(0).is_err());
+    }
+}
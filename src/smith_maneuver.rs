@@ -0,0 +1,136 @@
+// The Smith Maneuver converts a mortgage into tax-deductible debt over time. Every dollar of
+// principal repaid is borrowed back from the HELOC of a readvanceable mortgage and invested,
+// so interest on the HELOC is deductible against investment income while mortgage interest is not.
+// HELOC interest is assumed to be paid out of pocket each period, at heloc_rate, an annual
+// percentage charged in equal parts each payment period. Any HELOC balance at the start is
+// counted as already invested.
+use crate::{ReadvanceableMortgage, Result};
+use alloc::vec::Vec;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// One mortgage payment. readvance is borrowed from the HELOC and invested after the payment,
+// bringing the HELOC balance to investment_loan.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmithManeuverRow {
+    pub payment_number: u64,
+    pub readvance: Decimal,
+    pub investment_loan: Decimal,
+    pub deductible_interest: Decimal,
+    pub non_deductible_interest: Decimal,
+}
+
+// Interest for one year of payments, counted from the first payment. Start in January to line
+// the years up with tax years.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnnualInterest {
+    pub year: u64,
+    pub deductible_interest: Decimal,
+    pub non_deductible_interest: Decimal,
+}
+
+impl ReadvanceableMortgage {
+    pub fn smith_maneuver(&self, heloc_rate: Decimal) -> Result<Vec<SmithManeuverRow>> {
+        crate::validate_rate(heloc_rate)?;
+        let payments_per_year =
+            crate::decimal_from_u64(self.mortgage().payment_frequency().payments_per_year())?;
+        let rate = heloc_rate / dec!(100) / payments_per_year;
+
+        let mut investment_loan = self.heloc_balance();
+        let mut rows = Vec::new();
+        for row in self.mortgage().amortization_schedule()? {
+            let deductible_interest = investment_loan * rate;
+            let readvance = (row.principal + row.prepayment)
+                .min(self.credit_limit(row.balance) - investment_loan)
+                .max(dec!(0));
+            investment_loan += readvance;
+
+            rows.push(SmithManeuverRow {
+                payment_number: row.payment_number,
+                readvance,
+                investment_loan,
+                deductible_interest,
+                non_deductible_interest: row.interest,
+            });
+        }
+
+        Ok(rows)
+    }
+
+    // Deductible and non-deductible interest for each year, for tax filing.
+    pub fn annual_interest(&self, heloc_rate: Decimal) -> Result<Vec<AnnualInterest>> {
+        let payments_per_year = self.mortgage().payment_frequency().payments_per_year();
+
+        let mut years: Vec<AnnualInterest> = Vec::new();
+        for row in self.smith_maneuver(heloc_rate)? {
+            let year = (row.payment_number - 1) / payments_per_year + 1;
+            match years.last_mut() {
+                Some(annual) if annual.year == year => {
+                    annual.deductible_interest += row.deductible_interest;
+                    annual.non_deductible_interest += row.non_deductible_interest;
+                }
+                _ => years.push(AnnualInterest {
+                    year,
+                    deductible_interest: row.deductible_interest,
+                    non_deductible_interest: row.non_deductible_interest,
+                }),
+            }
+        }
+
+        Ok(years)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CanadianMortgage, PaymentFrequency};
+
+    fn readvanceable() -> ReadvanceableMortgage {
+        let mortgage =
+            CanadianMortgage::new(dec!(400000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        ReadvanceableMortgage::new(mortgage, dec!(500000)).unwrap()
+    }
+
+    #[test]
+    fn principal_repaid_is_borrowed_to_invest() {
+        let readvanceable = readvanceable();
+        let schedule = readvanceable.mortgage().amortization_schedule().unwrap();
+        let ledger = readvanceable.smith_maneuver(dec!(7)).unwrap();
+
+        assert_eq!(ledger[0].deductible_interest, dec!(0));
+        assert_eq!(
+            ledger[0].readvance.round_dp(20),
+            schedule[0].principal.round_dp(20)
+        );
+        assert_eq!(ledger[0].non_deductible_interest, schedule[0].interest);
+        assert_eq!(
+            ledger[1].deductible_interest.round_dp(20),
+            (schedule[0].principal * dec!(0.07) / dec!(12)).round_dp(20)
+        );
+        assert_eq!(
+            ledger[59].investment_loan.round_dp(20),
+            (dec!(400000) - schedule[59].balance).round_dp(20),
+            "the debt is the same, only its purpose has changed"
+        );
+        assert_eq!(
+            ledger.last().unwrap().investment_loan,
+            dec!(325000),
+            "readvances stop at the revolving limit"
+        );
+    }
+
+    #[test]
+    fn interest_becomes_deductible_over_time() {
+        let years = readvanceable().annual_interest(dec!(7)).unwrap();
+
+        assert_eq!(years.len(), 25);
+        assert_eq!(years[0].year, 1);
+        assert!(years[1].deductible_interest > years[0].deductible_interest);
+        assert!(years.windows(2).all(|years| years[1].deductible_interest
+            >= years[0].deductible_interest
+            && years[1].non_deductible_interest < years[0].non_deductible_interest));
+    }
+}
@@ -0,0 +1,63 @@
+// Under OSFI guideline B-20, borrowers must qualify at the greater of the contract rate plus 2%
+// and a benchmark floor, even though they will pay the contract rate.
+// https://www.osfi-bsif.gc.ca/en/guidance/guidance-library/residential-mortgage-underwriting-practices-procedures-guideline-b-20
+use crate::{CanadianMortgage, Result};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+pub(crate) const DEFAULT_BENCHMARK_RATE: Decimal = dec!(5.25);
+
+const QUALIFYING_BUFFER: Decimal = dec!(2);
+
+impl CanadianMortgage {
+    // benchmark is the minimum qualifying rate as an annual percentage, 5.25% by default.
+    pub fn with_qualifying_benchmark(mut self, benchmark: Decimal) -> Result<CanadianMortgage> {
+        crate::validate_rate(benchmark)?;
+        self.qualifying_benchmark = benchmark;
+        Ok(self)
+    }
+
+    // The minimum qualifying rate: max(contract rate + 2%, benchmark).
+    pub fn qualifying_rate(&self) -> Decimal {
+        (self.annual_rate + QUALIFYING_BUFFER).max(self.qualifying_benchmark)
+    }
+
+    // The payment at the minimum qualifying rate, at the same frequency and amortization.
+    pub fn qualifying_payment(&self) -> Result<Decimal> {
+        self.with_annual_rate(self.qualifying_rate())?.payment()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+
+    #[test]
+    fn qualifying_rate_is_the_greater_of_buffer_and_benchmark() {
+        let low_rate =
+            CanadianMortgage::new(dec!(430000), dec!(2.5), 25, PaymentFrequency::Monthly).unwrap();
+        assert_eq!(low_rate.qualifying_rate(), dec!(5.25));
+
+        let high_rate =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        assert_eq!(high_rate.qualifying_rate(), dec!(6.59));
+
+        let benchmark = low_rate.with_qualifying_benchmark(dec!(4.79)).unwrap();
+        assert_eq!(benchmark.qualifying_rate(), dec!(4.79));
+    }
+
+    #[test]
+    fn qualifying_payment_uses_the_qualifying_rate() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        let stressed =
+            CanadianMortgage::new(dec!(430000), dec!(6.59), 25, PaymentFrequency::Monthly).unwrap();
+
+        assert_eq!(
+            mortgage.qualifying_payment().unwrap(),
+            stressed.payment().unwrap()
+        );
+        assert!(mortgage.qualifying_payment().unwrap() > mortgage.payment().unwrap());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{CanadianMortgage, Result, ScheduleRow};
+use rust_decimal::prelude::*;
+
+// Lifetime totals over the full amortization of a mortgage, including any prepayments.
+// interest_to_principal is the total interest paid per dollar borrowed.
+// capitalized_interest is the interest added to the balance instead of being paid, by skipped payments.
+// premium_interest is the share of total_interest paid on a default insurance premium added to
+// the principal, in proportion to the premium's share of the principal.
+// interest_only is set when the payments only covered interest, so the principal is still owing.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Summary {
+    pub number_of_payments: u64,
+    pub total_payments: Decimal,
+    pub total_interest: Decimal,
+    pub interest_to_principal: Decimal,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub capitalized_interest: Decimal,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub premium_interest: Decimal,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub interest_only: bool,
+}
+
+impl CanadianMortgage {
+    pub fn summary(&self) -> Result<Summary> {
+        Ok(self.summarize(&self.amortization_schedule()?))
+    }
+
+    // The summary of schedule, which must be for this mortgage or one with the same principal.
+    pub(crate) fn summarize(&self, schedule: &[ScheduleRow]) -> Summary {
+        let mut summary = Summary::from_schedule(schedule, self.principal, self.interest_only);
+        summary.premium_interest = summary.total_interest * self.insurance_premium / self.principal;
+        summary
+    }
+}
+
+impl Summary {
+    pub(crate) fn from_schedule(
+        schedule: &[ScheduleRow],
+        principal: Decimal,
+        interest_only: bool,
+    ) -> Summary {
+        let total_payments: Decimal = schedule
+            .iter()
+            .map(|row| row.payment + row.prepayment)
+            .sum();
+        let total_interest: Decimal = schedule.iter().map(|row| row.interest).sum();
+        let capitalized_interest: Decimal = schedule
+            .iter()
+            .filter(|row| row.principal < Decimal::ZERO)
+            .map(|row| -row.principal)
+            .sum();
+
+        Summary {
+            number_of_payments: schedule.len() as u64,
+            total_payments,
+            total_interest,
+            interest_to_principal: total_interest / principal,
+            capitalized_interest,
+            premium_interest: Decimal::ZERO,
+            interest_only,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+    use rust_decimal_macros::*;
+
+    #[test]
+    fn summary_totals_add_up() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        let summary = mortgage.summary().unwrap();
+
+        assert_eq!(summary.number_of_payments, 300);
+        assert_eq!(
+            (summary.total_payments - summary.total_interest).round_dp(10),
+            dec!(430000),
+            "payments are principal plus interest"
+        );
+        assert_eq!(summary.total_interest.round_dp(2), dec!(290448.61));
+        assert_eq!(summary.interest_to_principal.round_dp(4), dec!(0.6755));
+    }
+
+    #[test]
+    fn interest_on_the_premium_is_separate() {
+        let insured =
+            CanadianMortgage::new(dec!(475000), dec!(4.59), 25, PaymentFrequency::Monthly)
+                .unwrap()
+                .with_default_insurance(dec!(500000))
+                .unwrap();
+        let summary = insured.summary().unwrap();
+
+        assert_eq!(insured.principal(), dec!(494000));
+        assert_eq!(
+            summary.premium_interest,
+            summary.total_interest * dec!(19000) / dec!(494000)
+        );
+        assert_eq!(
+            CanadianMortgage::new(dec!(475000), dec!(4.59), 25, PaymentFrequency::Monthly)
+                .unwrap()
+                .summary()
+                .unwrap()
+                .premium_interest,
+            dec!(0)
+        );
+    }
+
+    #[test]
+    fn accelerated_frequencies_cost_less() {
+        let total_interest = |frequency| {
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, frequency)
+                .unwrap()
+                .summary()
+                .unwrap()
+                .total_interest
+        };
+
+        let monthly = total_interest(PaymentFrequency::Monthly);
+        assert!(total_interest(PaymentFrequency::AcceleratedBiWeekly) < monthly);
+        assert!(total_interest(PaymentFrequency::AcceleratedWeekly) < monthly);
+        assert!(
+            total_interest(PaymentFrequency::AcceleratedWeekly)
+                < total_interest(PaymentFrequency::Weekly)
+        );
+    }
+}
@@ -0,0 +1,422 @@
+// Aligned plain-text tables for terminal output, in Canadian English unless a Locale is given.
+// Amounts are shown as currency, rounded half to even to the cent: $2,401.50 or 2 401,50 $.
+use crate::locale::{Label, Localized};
+use crate::{CostOfBorrowingDisclosure, Locale, MortgageError, Result, ScheduleRow, Summary};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use rust_decimal::prelude::*;
+
+// An amount formatted as currency in Canadian English. Width and alignment, as in {:>12}, apply
+// to the whole amount.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Money(pub Decimal);
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&Locale::EnCa.money(self.0))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    PaymentNumber,
+    Payment,
+    Interest,
+    Principal,
+    Prepayment,
+    Balance,
+}
+
+impl Column {
+    pub const ALL: [Column; 6] = [
+        Column::PaymentNumber,
+        Column::Payment,
+        Column::Interest,
+        Column::Principal,
+        Column::Prepayment,
+        Column::Balance,
+    ];
+
+    fn heading(&self, locale: Locale) -> &'static str {
+        match self {
+            Column::PaymentNumber => "#",
+            Column::Payment => locale.label(Label::Payment),
+            Column::Interest => locale.label(Label::Interest),
+            Column::Principal => locale.label(Label::Principal),
+            Column::Prepayment => locale.label(Label::Prepayment),
+            Column::Balance => locale.label(Label::Balance),
+        }
+    }
+
+    fn cell(&self, row: &ScheduleRow, locale: Locale) -> String {
+        match self {
+            Column::PaymentNumber => row.payment_number.to_string(),
+            Column::Payment => locale.money(row.payment),
+            Column::Interest => locale.money(row.interest),
+            Column::Principal => locale.money(row.principal),
+            Column::Prepayment => locale.money(row.prepayment),
+            Column::Balance => locale.money(row.balance),
+        }
+    }
+}
+
+// The payment number column is "number"; the others are their English headings. Case doesn't matter.
+impl FromStr for Column {
+    type Err = MortgageError;
+
+    fn from_str(column: &str) -> Result<Column> {
+        let name = column.trim().to_lowercase();
+        Column::ALL
+            .iter()
+            .find(|c| {
+                c.heading(Locale::EnCa) == name
+                    || (**c == Column::PaymentNumber && name == "number")
+            })
+            .copied()
+            .ok_or_else(|| MortgageError::UnknownColumn(column.into()))
+    }
+}
+
+// A schedule laid out in right-aligned columns under a heading, one line per payment.
+#[derive(Debug, Clone)]
+pub struct ScheduleTable<'a> {
+    rows: &'a [ScheduleRow],
+    columns: Vec<Column>,
+    locale: Locale,
+}
+
+impl<'a> ScheduleTable<'a> {
+    // Every column, in the order of Column::ALL.
+    pub fn new(rows: &'a [ScheduleRow]) -> ScheduleTable<'a> {
+        ScheduleTable {
+            rows,
+            columns: Column::ALL.to_vec(),
+            locale: Locale::default(),
+        }
+    }
+
+    // Only columns, in the order given.
+    pub fn with_columns(mut self, columns: &[Column]) -> ScheduleTable<'a> {
+        self.columns = columns.to_vec();
+        self
+    }
+
+    pub fn with_locale(mut self, locale: Locale) -> ScheduleTable<'a> {
+        self.locale = locale;
+        self
+    }
+}
+
+impl fmt::Display for ScheduleTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| {
+                self.columns
+                    .iter()
+                    .map(|column| column.cell(row, self.locale))
+                    .collect()
+            })
+            .collect();
+        let headings: Vec<&str> = self
+            .columns
+            .iter()
+            .map(|column| column.heading(self.locale))
+            .collect();
+        let widths: Vec<usize> = headings
+            .iter()
+            .enumerate()
+            .map(|(i, heading)| {
+                cells
+                    .iter()
+                    .map(|row| row[i].chars().count())
+                    .fold(heading.chars().count(), usize::max)
+            })
+            .collect();
+
+        write_line(f, &headings, &widths)?;
+        for row in &cells {
+            f.write_str("\n")?;
+            let row: Vec<&str> = row.iter().map(String::as_str).collect();
+            write_line(f, &row, &widths)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_line(f: &mut fmt::Formatter<'_>, cells: &[&str], widths: &[usize]) -> fmt::Result {
+    for (i, (cell, width)) in cells.iter().zip(widths).enumerate() {
+        if i > 0 {
+            f.write_str("  ")?;
+        }
+        write!(f, "{:>width$}", cell, width = width)?;
+    }
+    Ok(())
+}
+
+// Labels on the left, values aligned on the right.
+fn write_labelled(f: &mut fmt::Formatter<'_>, lines: &[(&str, String)]) -> fmt::Result {
+    let label_width = lines
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .max()
+        .unwrap_or(0);
+    let value_width = lines
+        .iter()
+        .map(|(_, value)| value.chars().count())
+        .max()
+        .unwrap_or(0);
+    for (i, (label, value)) in lines.iter().enumerate() {
+        if i > 0 {
+            f.write_str("\n")?;
+        }
+        write!(
+            f,
+            "{:<label_width$}  {:>value_width$}",
+            label,
+            value,
+            label_width = label_width,
+            value_width = value_width
+        )?;
+    }
+    Ok(())
+}
+
+impl Summary {
+    pub fn localized(&self, locale: Locale) -> Localized<'_, Summary> {
+        Localized {
+            value: self,
+            locale,
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.localized(Locale::default()).fmt(f)
+    }
+}
+
+// Capitalized and premium interest are only shown when there is some.
+impl fmt::Display for Localized<'_, Summary> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (summary, locale) = (self.value, self.locale);
+        let mut lines = vec![
+            (
+                locale.label(Label::Payments),
+                summary.number_of_payments.to_string(),
+            ),
+            (
+                locale.label(Label::TotalPayments),
+                locale.money(summary.total_payments),
+            ),
+            (
+                locale.label(Label::TotalInterest),
+                locale.money(summary.total_interest),
+            ),
+            (
+                locale.label(Label::InterestPerDollar),
+                locale.number(summary.interest_to_principal, 2),
+            ),
+        ];
+        if !summary.capitalized_interest.is_zero() {
+            lines.push((
+                locale.label(Label::CapitalizedInterest),
+                locale.money(summary.capitalized_interest),
+            ));
+        }
+        if !summary.premium_interest.is_zero() {
+            lines.push((
+                locale.label(Label::PremiumInterest),
+                locale.money(summary.premium_interest),
+            ));
+        }
+        if summary.interest_only {
+            lines.push((
+                locale.label(Label::InterestOnly),
+                locale.label(Label::Yes).to_string(),
+            ));
+        }
+        write_labelled(f, &lines)
+    }
+}
+
+impl CostOfBorrowingDisclosure {
+    pub fn localized(&self, locale: Locale) -> Localized<'_, CostOfBorrowingDisclosure> {
+        Localized {
+            value: self,
+            locale,
+        }
+    }
+}
+
+impl fmt::Display for CostOfBorrowingDisclosure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.localized(Locale::default()).fmt(f)
+    }
+}
+
+impl fmt::Display for Localized<'_, CostOfBorrowingDisclosure> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (disclosure, locale) = (self.value, self.locale);
+        write_labelled(
+            f,
+            &[
+                (
+                    locale.label(Label::Principal),
+                    locale.money(disclosure.principal),
+                ),
+                (
+                    locale.label(Label::InterestRate),
+                    locale.percent(disclosure.interest_rate),
+                ),
+                (
+                    locale.label(Label::Apr),
+                    locale.percent(disclosure.apr.round_dp(2)),
+                ),
+                (
+                    locale.label(Label::Term),
+                    locale.years(disclosure.term_years),
+                ),
+                (
+                    locale.label(Label::Amortization),
+                    locale.years(disclosure.amortization_period),
+                ),
+                (
+                    locale.label(Label::PaymentFrequency),
+                    locale.frequency(disclosure.payment_frequency).to_string(),
+                ),
+                (
+                    locale.label(Label::Payment),
+                    locale.money(disclosure.payment),
+                ),
+                (
+                    locale.label(Label::Payments),
+                    disclosure.number_of_payments.to_string(),
+                ),
+                (
+                    locale.label(Label::TotalPayments),
+                    locale.money(disclosure.total_payments),
+                ),
+                (
+                    locale.label(Label::TotalInterest),
+                    locale.money(disclosure.total_interest),
+                ),
+                (
+                    locale.label(Label::NonInterestCharges),
+                    locale.money(disclosure.non_interest_charges),
+                ),
+                (
+                    locale.label(Label::CostOfBorrowing),
+                    locale.money(disclosure.total_cost_of_borrowing),
+                ),
+                (
+                    locale.label(Label::BalanceAtTermEnd),
+                    locale.money(disclosure.balance_at_term_end),
+                ),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CanadianMortgage, PaymentFrequency};
+    use alloc::format;
+    use rust_decimal_macros::*;
+
+    fn mortgage() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap()
+    }
+
+    #[test]
+    fn money_is_formatted_as_currency() {
+        assert_eq!(Money(dec!(2401.4953)).to_string(), "$2,401.50");
+        assert_eq!(Money(dec!(1234567.125)).to_string(), "$1,234,567.12");
+        assert_eq!(Money(dec!(999.999)).to_string(), "$1,000.00");
+        assert_eq!(Money(dec!(-12.5)).to_string(), "-$12.50");
+        assert_eq!(Money(dec!(-0.001)).to_string(), "$0.00");
+        assert_eq!(format!("[{:>10}]", Money(dec!(5))), "[     $5.00]");
+    }
+
+    #[test]
+    fn schedule_columns_are_aligned() {
+        let schedule = mortgage().amortization_schedule().unwrap();
+        let table = ScheduleTable::new(&schedule[..2]).to_string();
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(
+            lines,
+            [
+                "#    payment   interest  principal  prepayment      balance",
+                "1  $2,401.50  $1,629.24    $772.26       $0.00  $429,227.74",
+                "2  $2,401.50  $1,626.31    $775.18       $0.00  $428,452.56",
+            ]
+        );
+    }
+
+    #[test]
+    fn columns_are_selectable() {
+        let schedule = mortgage().amortization_schedule().unwrap();
+        let columns: Vec<Column> = ["Number", "balance"]
+            .iter()
+            .map(|c| c.parse().unwrap())
+            .collect();
+        let table = ScheduleTable::new(&schedule[..1])
+            .with_columns(&columns)
+            .to_string();
+
+        assert_eq!(table, "#      balance\n1  $429,227.74");
+        assert_eq!(
+            "fees".parse::<Column>(),
+            Err(MortgageError::UnknownColumn("fees".into()))
+        );
+    }
+
+    #[test]
+    fn summary_is_displayed() {
+        assert_eq!(
+            mortgage().summary().unwrap().to_string(),
+            "payments                     300\n\
+             total payments       $720,448.61\n\
+             total interest       $290,448.61\n\
+             interest per dollar         0.68"
+        );
+    }
+
+    #[test]
+    fn french_output() {
+        let mortgage = mortgage();
+        let schedule = mortgage.amortization_schedule().unwrap();
+        let table = ScheduleTable::new(&schedule[..1])
+            .with_columns(&[Column::PaymentNumber, Column::Balance])
+            .with_locale(Locale::FrCa)
+            .to_string();
+        assert_eq!(table, "#         solde\n1  429\u{a0}227,74\u{a0}$");
+
+        assert_eq!(
+            mortgage
+                .summary()
+                .unwrap()
+                .localized(Locale::FrCa)
+                .to_string(),
+            "versements                     300\n\
+             total des versements  720\u{a0}448,61\u{a0}$\n\
+             total des intérêts    290\u{a0}448,61\u{a0}$\n\
+             intérêts par dollar           0,68"
+        );
+
+        let disclosure = mortgage
+            .cost_of_borrowing_disclosure(&Default::default())
+            .unwrap();
+        let french = disclosure.localized(Locale::FrCa).to_string();
+        assert!(french.contains("taux d'intérêt                    4,59\u{a0}%"));
+        assert!(french.contains("fréquence des versements       mensuelle"));
+        assert!(disclosure.to_string().contains("amortization"));
+    }
+}
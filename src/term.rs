@@ -0,0 +1,101 @@
+use crate::{CanadianMortgage, MortgageError, Result};
+use rust_decimal::prelude::*;
+
+impl CanadianMortgage {
+    // term_years is the length of the mortgage contract, after which the remaining balance is renewed.
+    // It is usually much shorter than the amortization period, e.g. a 5 year term on a 25 year amortization.
+    pub fn with_term(mut self, term_years: u64) -> Result<CanadianMortgage> {
+        if term_years == 0 || term_years > self.amortization_period {
+            return Err(MortgageError::InvalidTerm {
+                term: term_years,
+                amortization: self.amortization_period,
+            });
+        }
+
+        self.term_years = term_years;
+        Ok(self)
+    }
+
+    pub fn term_years(&self) -> u64 {
+        self.term_years
+    }
+
+    // The number of regular payments scheduled during the term.
+    pub fn term_payments(&self) -> u64 {
+        self.term_years * self.payment_frequency.payments_per_year()
+    }
+
+    // The principal owing at renewal.
+    pub fn balance_at_term_end(&self) -> Result<Decimal> {
+        self.balance_at(self.term_payments())
+    }
+
+    // The interest paid over the payments made during the term.
+    pub fn interest_paid_in_term(&self) -> Result<Decimal> {
+        Ok(self
+            .amortization_schedule()?
+            .iter()
+            .take(self.term_payments() as usize)
+            .map(|row| row.interest)
+            .sum())
+    }
+
+    // The number of payments still needed at renewal to pay off the mortgage at the current rate.
+    pub fn payments_remaining_at_renewal(&self) -> Result<u64> {
+        let payments = self.amortization_schedule()?.len() as u64;
+        Ok(payments.saturating_sub(self.term_payments()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+    use rust_decimal_macros::*;
+
+    fn five_year_term() -> CanadianMortgage {
+        CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly)
+            .unwrap()
+            .with_term(5)
+            .unwrap()
+    }
+
+    #[test]
+    fn term_is_validated() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        assert_eq!(mortgage.term_years(), 25, "defaults to the amortization");
+
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        assert!(matches!(
+            mortgage.with_term(0),
+            Err(MortgageError::InvalidTerm { term: 0, .. })
+        ));
+
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+        assert!(mortgage.with_term(30).is_err());
+    }
+
+    #[test]
+    fn end_of_term() {
+        let mortgage = five_year_term();
+        let schedule = mortgage.amortization_schedule().unwrap();
+
+        assert_eq!(mortgage.term_payments(), 60);
+        assert_eq!(mortgage.payments_remaining_at_renewal().unwrap(), 240);
+        assert_eq!(
+            mortgage.balance_at_term_end().unwrap().round_dp(6),
+            schedule[59].balance.round_dp(6)
+        );
+
+        let paid = mortgage.payment().unwrap() * dec!(60);
+        let principal_repaid = dec!(430000) - schedule[59].balance;
+        assert_eq!(
+            mortgage.interest_paid_in_term().unwrap().round_dp(10),
+            (paid - principal_repaid).round_dp(10),
+            "interest is what was paid beyond the principal repaid"
+        );
+    }
+}
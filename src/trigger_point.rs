@@ -0,0 +1,117 @@
+// When a static payment variable mortgage reaches its trigger point, the lender steps in. Most
+// contracts set the trigger at the original principal, some at a percentage above it. The
+// borrower then has to reset the payment so the balance is paid off over what is left of the
+// amortization, or pay a lump sum that brings the balance back down to what the current payment
+// pays off.
+use crate::{PrimeRateChange, Result, ScheduleRow, VariableRateMortgage};
+use alloc::vec::Vec;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TriggerCure {
+    PaymentReset,
+    LumpSum,
+}
+
+impl VariableRateMortgage {
+    // The schedule of a static payment mortgage with prime changing as given, with cure applied
+    // right after every payment that takes the balance over the trigger threshold. A reset
+    // payment applies from the next payment. A lump sum is shown as a prepayment.
+    pub fn cured_schedule(
+        &self,
+        prime_rate_changes: &[PrimeRateChange],
+        cure: TriggerCure,
+    ) -> Result<Vec<ScheduleRow>> {
+        self.schedule_with_cure(prime_rate_changes, Some(cure))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MortgageError, PaymentFrequency};
+    use rust_decimal_macros::*;
+
+    fn mortgage() -> VariableRateMortgage {
+        VariableRateMortgage::new(
+            dec!(430000),
+            dec!(6.45),
+            dec!(-0.90),
+            25,
+            PaymentFrequency::Monthly,
+        )
+        .unwrap()
+        .with_static_payment()
+    }
+
+    fn changes() -> [PrimeRateChange; 1] {
+        [PrimeRateChange {
+            payment_number: 13,
+            prime_rate: dec!(10),
+        }]
+    }
+
+    #[test]
+    fn threshold_is_a_percentage_of_principal() {
+        let mortgage = mortgage().with_trigger_threshold(dec!(105)).unwrap();
+        assert_eq!(mortgage.trigger_threshold(), dec!(451500));
+        assert_eq!(
+            mortgage
+                .clone()
+                .with_trigger_threshold(dec!(99))
+                .unwrap_err(),
+            MortgageError::InvalidTriggerThreshold(dec!(99))
+        );
+
+        let changes = [
+            PrimeRateChange {
+                payment_number: 13,
+                prime_rate: dec!(10),
+            },
+            PrimeRateChange {
+                payment_number: 300,
+                prime_rate: dec!(3),
+            },
+        ];
+        let trigger_point = mortgage.trigger_point(&changes).unwrap().unwrap();
+        let schedule = mortgage.amortization_schedule(&changes).unwrap();
+        assert!(schedule[trigger_point as usize - 1].balance > dec!(451500));
+        assert!(schedule[trigger_point as usize - 2].balance > dec!(430000));
+    }
+
+    #[test]
+    fn payment_reset_restores_the_amortization() {
+        let mortgage = mortgage();
+        let schedule = mortgage
+            .cured_schedule(&changes(), TriggerCure::PaymentReset)
+            .unwrap();
+
+        let trigger_point = schedule
+            .iter()
+            .position(|row| row.balance > dec!(430000))
+            .unwrap();
+        assert_eq!(schedule[trigger_point].payment, mortgage.payment().unwrap());
+        assert!(schedule[trigger_point + 1].payment > schedule[trigger_point].payment);
+        assert!(schedule[trigger_point + 1].principal > dec!(0));
+        assert_eq!(schedule.len(), 300, "paid off over the amortization");
+        assert_eq!(schedule.last().unwrap().balance, dec!(0));
+    }
+
+    #[test]
+    fn lump_sum_keeps_the_payment() {
+        let mortgage = mortgage();
+        let schedule = mortgage
+            .cured_schedule(&changes(), TriggerCure::LumpSum)
+            .unwrap();
+
+        let cure = schedule
+            .iter()
+            .find(|row| row.prepayment > dec!(0))
+            .unwrap();
+        assert!(cure.balance < dec!(430000));
+        assert!(schedule
+            .iter()
+            .all(|row| row.payment == schedule[0].payment || row.balance == dec!(0)));
+        assert_eq!(schedule.len(), 300, "paid off over the amortization");
+    }
+}
@@ -0,0 +1,68 @@
+// American mortgages quote the nominal annual rate compounded monthly, so the same quoted rate
+// costs more in the US than in Canada. These put American quotes on the same footing as
+// Canadian ones.
+use crate::{CanadianMortgage, Compounding, PaymentFrequency, Result};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// A mortgage at a US-style rate, compounded monthly.
+pub fn mortgage(
+    mortgage_amount: Decimal,
+    interest_rate: Decimal,
+    amortization_period: u64,
+    payment_frequency: PaymentFrequency,
+) -> Result<CanadianMortgage> {
+    CanadianMortgage::new(
+        mortgage_amount,
+        interest_rate,
+        amortization_period,
+        payment_frequency,
+    )?
+    .with_compounding(Compounding::Monthly)
+}
+
+// The US-style rate, as a percentage compounded monthly, that costs the same as
+// canadian_rate, a percentage compounded semi-annually.
+pub fn equivalent_us_rate(canadian_rate: Decimal) -> Result<Decimal> {
+    Ok(crate::rates::monthly_compounded_rate(canadian_rate, Compounding::SemiAnnual)? * dec!(100))
+}
+
+// The Canadian rate, as a percentage compounded semi-annually, that costs the same as
+// us_rate, a percentage compounded monthly.
+pub fn equivalent_canadian_rate(us_rate: Decimal) -> Result<Decimal> {
+    crate::validate_rate(us_rate)?;
+    Ok(crate::rates::convert_compounding_basis(us_rate / dec!(100), 12, 2)? * dec!(100))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_quote_costs_more_in_the_us() {
+        let us = mortgage(dec!(430000), dec!(5), 25, PaymentFrequency::Monthly).unwrap();
+        let canadian =
+            CanadianMortgage::new(dec!(430000), dec!(5), 25, PaymentFrequency::Monthly).unwrap();
+        assert!(us.payment().unwrap() > canadian.payment().unwrap());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "fast-math", ignore = "exact to Decimal precision")]
+    fn equivalent_rates_give_the_same_payment() {
+        let us_rate = equivalent_us_rate(dec!(5)).unwrap();
+        assert_eq!(us_rate.round_dp(6), dec!(4.948699));
+
+        let us = mortgage(dec!(430000), us_rate, 25, PaymentFrequency::Monthly).unwrap();
+        let canadian =
+            CanadianMortgage::new(dec!(430000), dec!(5), 25, PaymentFrequency::Monthly).unwrap();
+        assert_eq!(
+            us.payment().unwrap().round_dp(10),
+            canadian.payment().unwrap().round_dp(10)
+        );
+
+        assert_eq!(
+            equivalent_canadian_rate(us_rate).unwrap().round_dp(20),
+            dec!(5)
+        );
+    }
+}
@@ -0,0 +1,214 @@
+// Current rates from the Bank of Canada's Valet API, to start calculations from today's market.
+// https://www.bankofcanada.ca/valet/docs
+//
+// The Bank publishes the chartered banks' prime rate and their conventional five-year posted
+// rate weekly. The five-year posted rate is also the benchmark the minimum qualifying rate was
+// set from for insured mortgages, so benchmark_rate is the same series; since June 2021 the
+// qualifying floor has instead been a fixed 5.25%, and the greater of the two is the benchmark
+// to qualify at.
+use crate::stress_test::DEFAULT_BENCHMARK_RATE;
+use crate::{CanadianMortgage, MortgageError, PaymentFrequency, Result, VariableRateMortgage};
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+use serde_json::Value;
+
+pub const DEFAULT_BASE_URL: &str = "https://www.bankofcanada.ca/valet";
+
+// Valet series names.
+pub const PRIME_RATE: &str = "V80691311";
+pub const FIVE_YEAR_POSTED_RATE: &str = "V80691335";
+
+// A rate as a percentage, on the date the Bank observed it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Observation {
+    pub date: NaiveDate,
+    pub rate: Decimal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarketRates {
+    pub prime_rate: Observation,
+    pub five_year_posted_rate: Observation,
+}
+
+#[derive(Debug, Clone)]
+pub struct Valet {
+    base_url: String,
+    client: reqwest::blocking::Client,
+}
+
+impl Valet {
+    pub fn new() -> Valet {
+        Valet::with_base_url(DEFAULT_BASE_URL)
+    }
+
+    // For a mirror or a test server. base_url is the part before /observations.
+    pub fn with_base_url(base_url: &str) -> Valet {
+        Valet {
+            base_url: base_url.trim_end_matches('/').into(),
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    // The most recent observation of a Valet series.
+    pub fn latest(&self, series: &str) -> Result<Observation> {
+        let url = format!("{}/observations/{}/json?recent=1", self.base_url, series);
+        let body = self
+            .client
+            .get(url)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.text())
+            .map_err(|e| MortgageError::RateUnavailable(e.to_string()))?;
+        latest_observation(&body, series)
+    }
+
+    pub fn prime_rate(&self) -> Result<Observation> {
+        self.latest(PRIME_RATE)
+    }
+
+    pub fn five_year_posted_rate(&self) -> Result<Observation> {
+        self.latest(FIVE_YEAR_POSTED_RATE)
+    }
+
+    pub fn benchmark_rate(&self) -> Result<Observation> {
+        self.five_year_posted_rate()
+    }
+
+    pub fn market_rates(&self) -> Result<MarketRates> {
+        Ok(MarketRates {
+            prime_rate: self.prime_rate()?,
+            five_year_posted_rate: self.five_year_posted_rate()?,
+        })
+    }
+}
+
+impl Default for Valet {
+    fn default() -> Valet {
+        Valet::new()
+    }
+}
+
+impl MarketRates {
+    // The greater of the five-year posted rate and the 5.25% floor.
+    pub fn qualifying_benchmark(&self) -> Decimal {
+        self.five_year_posted_rate.rate.max(DEFAULT_BENCHMARK_RATE)
+    }
+
+    // The mortgage stress tested against today's benchmark.
+    pub fn stress_test(&self, mortgage: CanadianMortgage) -> Result<CanadianMortgage> {
+        mortgage.with_qualifying_benchmark(self.qualifying_benchmark())
+    }
+
+    // A variable rate mortgage at today's prime plus spread.
+    pub fn variable_rate_mortgage(
+        &self,
+        mortgage_amount: Decimal,
+        spread: Decimal,
+        amortization_period: u64,
+        payment_frequency: PaymentFrequency,
+    ) -> Result<VariableRateMortgage> {
+        VariableRateMortgage::new(
+            mortgage_amount,
+            self.prime_rate.rate,
+            spread,
+            amortization_period,
+            payment_frequency,
+        )
+    }
+}
+
+// The last observation in a Valet observations response, which is the most recent.
+// Observations look like {"d": "2025-01-29", "V80691311": {"v": "5.45"}}.
+fn latest_observation(body: &str, series: &str) -> Result<Observation> {
+    let unexpected = || MortgageError::RateUnavailable(format!("no observations of {}", series));
+    let response: Value =
+        serde_json::from_str(body).map_err(|e| MortgageError::RateUnavailable(e.to_string()))?;
+    let observation = response["observations"]
+        .as_array()
+        .and_then(|observations| observations.last())
+        .ok_or_else(unexpected)?;
+
+    let date = observation["d"]
+        .as_str()
+        .and_then(|date| date.parse().ok())
+        .ok_or_else(unexpected)?;
+    let rate = observation[series]["v"]
+        .as_str()
+        .and_then(|rate| rate.parse().ok())
+        .ok_or_else(unexpected)?;
+    crate::validate_rate(rate)?;
+    Ok(Observation { date, rate })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::*;
+
+    const RESPONSE: &str = r#"{
+        "terms": {"url": "https://www.bankofcanada.ca/terms/"},
+        "seriesDetail": {
+            "V80691311": {"label": "Prime rate", "description": "Prime rate"}
+        },
+        "observations": [
+            {"d": "2025-01-22", "V80691311": {"v": "5.45"}},
+            {"d": "2025-01-29", "V80691311": {"v": "5.20"}}
+        ]
+    }"#;
+
+    fn market_rates() -> MarketRates {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 29).unwrap();
+        MarketRates {
+            prime_rate: Observation {
+                date,
+                rate: dec!(5.20),
+            },
+            five_year_posted_rate: Observation {
+                date,
+                rate: dec!(6.09),
+            },
+        }
+    }
+
+    #[test]
+    fn latest_observation_is_read() {
+        assert_eq!(
+            latest_observation(RESPONSE, PRIME_RATE),
+            Ok(Observation {
+                date: NaiveDate::from_ymd_opt(2025, 1, 29).unwrap(),
+                rate: dec!(5.20),
+            })
+        );
+        assert_eq!(
+            latest_observation(RESPONSE, FIVE_YEAR_POSTED_RATE),
+            Err(MortgageError::RateUnavailable(
+                "no observations of V80691335".into()
+            ))
+        );
+        assert!(matches!(
+            latest_observation("<html>", PRIME_RATE),
+            Err(MortgageError::RateUnavailable(_))
+        ));
+    }
+
+    #[test]
+    fn market_rates_feed_calculations() {
+        let rates = market_rates();
+        assert_eq!(rates.qualifying_benchmark(), dec!(6.09));
+
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(3.99), 25, PaymentFrequency::Monthly).unwrap();
+        assert_eq!(
+            rates.stress_test(mortgage).unwrap().qualifying_rate(),
+            dec!(6.09)
+        );
+
+        let variable = rates
+            .variable_rate_mortgage(dec!(430000), dec!(-0.75), 25, PaymentFrequency::Monthly)
+            .unwrap();
+        assert_eq!(variable.interest_rate(), dec!(4.45));
+    }
+}
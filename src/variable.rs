@@ -0,0 +1,571 @@
+// Variable rate mortgages float at a spread above or below the lender's prime rate.
+// Unlike fixed rate mortgages, the rate is compounded monthly rather than semi-annually.
+use crate::schedule::PAID_OFF_TOLERANCE;
+use crate::{MortgageError, PaymentFrequency, Result, ScheduleRow, TriggerCure};
+use alloc::vec::Vec;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::*;
+
+// A new prime rate, as a percentage, that applies from payment_number onwards.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrimeRateChange {
+    pub payment_number: u64,
+    pub prime_rate: Decimal,
+}
+
+// Adjustable payments are recalculated whenever prime changes, so the amortization stays the same.
+// Static payments stay the same, so a higher rate means less of each payment goes to principal
+// and the mortgage takes longer to pay off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VariablePayment {
+    Adjustable,
+    Static,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VariableRateMortgage {
+    principal: Decimal,
+    prime_rate: Decimal,
+    spread: Decimal,
+    amortization_period: u64,
+    payment_frequency: PaymentFrequency,
+    variable_payment: VariablePayment,
+    rate_cap: Option<Decimal>,
+    rate_floor: Option<Decimal>,
+    trigger_threshold: Option<Decimal>,
+}
+
+impl VariableRateMortgage {
+    // prime_rate is the lender's prime rate as a percentage.
+    // spread is added to prime, so a discount of prime - 0.90% is a spread of -0.90.
+    pub fn new(
+        mortgage_amount: Decimal,
+        prime_rate: Decimal,
+        spread: Decimal,
+        amortization_period: u64,
+        payment_frequency: PaymentFrequency,
+    ) -> Result<VariableRateMortgage> {
+        if mortgage_amount <= dec!(0) {
+            return Err(MortgageError::InvalidPrincipal(mortgage_amount));
+        }
+
+        crate::validate_rate(prime_rate + spread)?;
+
+        if amortization_period == 0 {
+            return Err(MortgageError::InvalidAmortization(amortization_period));
+        }
+        if amortization_period
+            .checked_mul(payment_frequency.payments_per_year())
+            .is_none()
+        {
+            return Err(MortgageError::NumericOverflow("number of payments"));
+        }
+
+        Ok(VariableRateMortgage {
+            principal: mortgage_amount,
+            prime_rate,
+            spread,
+            amortization_period,
+            payment_frequency,
+            variable_payment: VariablePayment::Adjustable,
+            rate_cap: None,
+            rate_floor: None,
+            trigger_threshold: None,
+        })
+    }
+
+    pub fn with_static_payment(mut self) -> VariableRateMortgage {
+        self.variable_payment = VariablePayment::Static;
+        self
+    }
+
+    // A capped variable mortgage never charges more than cap, an annual percentage, however
+    // high prime goes.
+    pub fn with_rate_cap(mut self, cap: Decimal) -> Result<VariableRateMortgage> {
+        crate::validate_rate(cap)?;
+        if let Some(floor) = self.rate_floor.filter(|floor| *floor > cap) {
+            return Err(MortgageError::RateFloorAboveCap { floor, cap });
+        }
+        self.rate_cap = Some(cap);
+        Ok(self)
+    }
+
+    // Never charge less than floor, an annual percentage, however low prime goes.
+    pub fn with_rate_floor(mut self, floor: Decimal) -> Result<VariableRateMortgage> {
+        crate::validate_rate(floor)?;
+        if let Some(cap) = self.rate_cap.filter(|cap| floor > *cap) {
+            return Err(MortgageError::RateFloorAboveCap { floor, cap });
+        }
+        self.rate_floor = Some(floor);
+        Ok(self)
+    }
+
+    // The trigger point is reached when the balance is over percentage of the original principal.
+    pub fn with_trigger_threshold(mut self, percentage: Decimal) -> Result<VariableRateMortgage> {
+        if percentage < dec!(100) {
+            return Err(MortgageError::InvalidTriggerThreshold(percentage));
+        }
+        self.trigger_threshold = Some(percentage);
+        Ok(self)
+    }
+
+    pub fn rate_cap(&self) -> Option<Decimal> {
+        self.rate_cap
+    }
+
+    pub fn rate_floor(&self) -> Option<Decimal> {
+        self.rate_floor
+    }
+
+    pub fn principal(&self) -> Decimal {
+        self.principal
+    }
+
+    pub fn payment_frequency(&self) -> PaymentFrequency {
+        self.payment_frequency
+    }
+
+    pub fn amortization_period(&self) -> u64 {
+        self.amortization_period
+    }
+
+    // The initial prime rate as a percentage.
+    pub fn prime_rate(&self) -> Decimal {
+        self.prime_rate
+    }
+
+    pub fn spread(&self) -> Decimal {
+        self.spread
+    }
+
+    // The annual interest rate as a percentage, compounded monthly, within any cap and floor.
+    pub fn interest_rate(&self) -> Decimal {
+        self.rate_at_prime(self.prime_rate)
+    }
+
+    // The payment at the initial prime rate.
+    pub fn payment(&self) -> Result<Decimal> {
+        self.payment_for(self.principal, self.interest_rate(), 0)
+    }
+
+    // Every payment until the mortgage is paid off, with prime changing as given.
+    // With adjustable payments, each time prime changes the payment is recalculated so the
+    // remaining balance is still paid off by the end of the amortization period.
+    // With static payments, interest the payment does not cover is added to the balance;
+    // it is an error if that is still happening after the last change in prime.
+    pub fn amortization_schedule(
+        &self,
+        prime_rate_changes: &[PrimeRateChange],
+    ) -> Result<Vec<ScheduleRow>> {
+        self.schedule_with_cure(prime_rate_changes, None)
+    }
+
+    // The schedule, with cure applied whenever a static payment mortgage reaches its trigger
+    // point. The payment doesn't have to cover the interest until the trigger point is reached.
+    pub(crate) fn schedule_with_cure(
+        &self,
+        prime_rate_changes: &[PrimeRateChange],
+        cure: Option<TriggerCure>,
+    ) -> Result<Vec<ScheduleRow>> {
+        let mut prime_rate_changes = prime_rate_changes.to_vec();
+        prime_rate_changes.sort_by_key(|change| change.payment_number);
+        let mut prime_rate_changes = prime_rate_changes.into_iter().peekable();
+
+        let mut interest_rate = self.interest_rate();
+        let mut rate = self.periodic_rate(interest_rate)?;
+        let mut payment = self.payment()?;
+
+        let mut rows = Vec::new();
+        let mut balance = self.principal;
+        let mut payment_number = 0;
+
+        while balance > dec!(0) {
+            payment_number += 1;
+
+            let mut rate_changed = false;
+            while let Some(change) =
+                prime_rate_changes.next_if(|change| change.payment_number <= payment_number)
+            {
+                crate::validate_rate(change.prime_rate + self.spread)?;
+                interest_rate = self.rate_at_prime(change.prime_rate);
+                rate_changed = true;
+            }
+            if rate_changed {
+                rate = self.periodic_rate(interest_rate)?;
+                if self.variable_payment == VariablePayment::Adjustable {
+                    payment = self.payment_for(balance, interest_rate, payment_number - 1)?;
+                }
+            }
+
+            let interest = balance * rate;
+            let rate_may_fall = prime_rate_changes.peek().is_some()
+                && self.variable_payment == VariablePayment::Static;
+            if payment <= interest && !rate_may_fall && cure.is_none() {
+                return Err(MortgageError::PaymentBelowInterest { payment, interest });
+            }
+
+            let (payment_made, principal) = if balance + interest - payment < PAID_OFF_TOLERANCE {
+                (balance + interest, balance)
+            } else {
+                (payment, payment - interest)
+            };
+            balance -= principal;
+
+            let mut prepayment = dec!(0);
+            if let Some(cure) = cure.filter(|_| balance > self.trigger_threshold()) {
+                let reset_payment = self.payment_for(balance, interest_rate, payment_number)?;
+                match cure {
+                    TriggerCure::PaymentReset => payment = reset_payment,
+                    TriggerCure::LumpSum => {
+                        prepayment = balance - balance * payment / reset_payment;
+                        balance -= prepayment;
+                    }
+                }
+            }
+
+            rows.push(ScheduleRow {
+                payment_number,
+                payment: payment_made,
+                interest,
+                principal,
+                prepayment,
+                balance,
+            });
+        }
+
+        Ok(rows)
+    }
+
+    // The total interest and the number of payments in the schedule with prime changing as given,
+    // for simulations.
+    #[cfg(all(feature = "rand", not(feature = "fast-math")))]
+    pub(crate) fn totals(&self, prime_rate_changes: &[PrimeRateChange]) -> Result<(Decimal, u64)> {
+        let schedule = self.amortization_schedule(prime_rate_changes)?;
+        Ok((
+            schedule.iter().map(|row| row.interest).sum(),
+            schedule.len() as u64,
+        ))
+    }
+
+    // The same totals with the payments made in f64, a segment at a time between changes in prime.
+    #[cfg(all(feature = "rand", feature = "fast-math"))]
+    pub(crate) fn totals(&self, prime_rate_changes: &[PrimeRateChange]) -> Result<(Decimal, u64)> {
+        use crate::fast_math;
+
+        let mut prime_rate_changes = prime_rate_changes.to_vec();
+        prime_rate_changes.sort_by_key(|change| change.payment_number);
+        let mut prime_rate_changes = prime_rate_changes.into_iter().peekable();
+
+        let mut interest_rate = self.interest_rate();
+        let mut rate = self.periodic_rate(interest_rate)?;
+        let mut payment = self.payment()?;
+
+        let mut balance = fast_math::to_f64(self.principal)?;
+        let mut interest = 0.0;
+        let mut payments = 0;
+        while balance > 0.0 {
+            let mut rate_changed = false;
+            while let Some(change) =
+                prime_rate_changes.next_if(|change| change.payment_number <= payments + 1)
+            {
+                crate::validate_rate(change.prime_rate + self.spread)?;
+                interest_rate = self.rate_at_prime(change.prime_rate);
+                rate_changed = true;
+            }
+            if rate_changed {
+                rate = self.periodic_rate(interest_rate)?;
+                if self.variable_payment == VariablePayment::Adjustable {
+                    payment =
+                        self.payment_for(fast_math::from_f64(balance)?, interest_rate, payments)?;
+                }
+            }
+
+            let next_change = prime_rate_changes.peek();
+            let amortization = fast_math::amortize(
+                balance,
+                fast_math::to_f64(rate)?,
+                fast_math::to_f64(payment)?,
+                next_change.map_or(u64::MAX, |change| change.payment_number - payments - 1),
+                next_change.is_some() && self.variable_payment == VariablePayment::Static,
+            )?;
+            balance = amortization.balance;
+            interest += amortization.interest;
+            payments += amortization.payments;
+        }
+
+        Ok((fast_math::from_f64(interest)?, payments))
+    }
+
+    // The same mortgage with shift added to the initial prime rate.
+    pub(crate) fn with_prime_shift(&self, shift: Decimal) -> Result<VariableRateMortgage> {
+        crate::validate_rate(self.prime_rate + self.spread + shift)?;
+        let mut mortgage = self.clone();
+        mortgage.prime_rate += shift;
+        Ok(mortgage)
+    }
+
+    // The annual rate, as a percentage compounded monthly, at which the initial payment
+    // only covers the interest on the original principal. Above it, a static payment
+    // no longer pays down any principal.
+    pub fn trigger_rate(&self) -> Result<Decimal> {
+        let payments_per_year = self.payment_frequency.payments_per_year();
+        let periodic_rate = self.payment()? / self.principal;
+        let n = crate::decimal_from_u64(payments_per_year)?;
+
+        let monthly_rate = if payments_per_year == 12 {
+            periodic_rate * dec!(12)
+        } else {
+            crate::rates::convert_compounding_basis(periodic_rate * n, payments_per_year, 12)?
+        };
+
+        Ok(monthly_rate * dec!(100))
+    }
+
+    // The prime rate at which the trigger rate is reached.
+    pub fn trigger_prime_rate(&self) -> Result<Decimal> {
+        Ok(self.trigger_rate()? - self.spread)
+    }
+
+    // The balance above which the trigger point is reached, the original principal by default.
+    pub fn trigger_threshold(&self) -> Decimal {
+        self.trigger_threshold.map_or(self.principal, |percentage| {
+            self.principal * percentage / dec!(100)
+        })
+    }
+
+    // The first payment after which the balance is higher than the trigger threshold,
+    // if the balance ever gets that high with prime changing as given.
+    pub fn trigger_point(&self, prime_rate_changes: &[PrimeRateChange]) -> Result<Option<u64>> {
+        let threshold = self.trigger_threshold();
+        Ok(self
+            .amortization_schedule(prime_rate_changes)?
+            .iter()
+            .find(|row| row.balance > threshold)
+            .map(|row| row.payment_number))
+    }
+
+    // The payment that pays off balance at interest_rate over what is left of the amortization
+    // once payments_made payments have been made.
+    fn payment_for(
+        &self,
+        balance: Decimal,
+        interest_rate: Decimal,
+        payments_made: u64,
+    ) -> Result<Decimal> {
+        let months_elapsed = payments_made * 12 / self.payment_frequency.payments_per_year();
+        let months_remaining = (self.amortization_period * 12)
+            .saturating_sub(months_elapsed)
+            .max(1);
+
+        let monthly_payment = crate::mortgage_payment(
+            balance,
+            interest_rate / dec!(100) / dec!(12),
+            months_remaining,
+        )?;
+        Ok(self
+            .payment_frequency
+            .scale_monthly_payment(monthly_payment))
+    }
+
+    fn rate_at_prime(&self, prime_rate: Decimal) -> Decimal {
+        let rate = prime_rate + self.spread;
+        let rate = self.rate_cap.map_or(rate, |cap| rate.min(cap));
+        self.rate_floor.map_or(rate, |floor| rate.max(floor))
+    }
+
+    fn periodic_rate(&self, interest_rate: Decimal) -> Result<Decimal> {
+        crate::rates::periodic_rate(interest_rate / dec!(100), self.payment_frequency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CanadianMortgage;
+
+    fn mortgage() -> VariableRateMortgage {
+        VariableRateMortgage::new(
+            dec!(430000),
+            dec!(6.45),
+            dec!(-0.90),
+            25,
+            PaymentFrequency::Monthly,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn rate_is_prime_plus_spread() {
+        assert_eq!(mortgage().interest_rate(), dec!(5.55));
+        assert!(VariableRateMortgage::new(
+            dec!(430000),
+            dec!(0.5),
+            dec!(-1),
+            25,
+            PaymentFrequency::Monthly
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn monthly_compounding_costs_more_than_semi_annual() {
+        let fixed =
+            CanadianMortgage::new(dec!(430000), dec!(5.55), 25, PaymentFrequency::Monthly).unwrap();
+        assert!(mortgage().payment().unwrap() > fixed.payment().unwrap());
+    }
+
+    #[test]
+    fn schedule_without_changes_pays_off_over_amortization() {
+        let schedule = mortgage().amortization_schedule(&[]).unwrap();
+        assert_eq!(schedule.len(), 300);
+        assert_eq!(schedule[0].interest, dec!(430000) * dec!(0.0555) / dec!(12));
+        assert_eq!(schedule.last().unwrap().balance, dec!(0));
+    }
+
+    #[test]
+    fn trigger_rate_is_where_payment_equals_interest() {
+        let mortgage = mortgage().with_static_payment();
+        let trigger_rate = mortgage.trigger_rate().unwrap();
+
+        assert_eq!(
+            (dec!(430000) * trigger_rate / dec!(100) / dec!(12)).round_dp(10),
+            mortgage.payment().unwrap().round_dp(10)
+        );
+        assert_eq!(
+            mortgage.trigger_prime_rate().unwrap(),
+            trigger_rate + dec!(0.90)
+        );
+
+        let weekly = VariableRateMortgage::new(
+            dec!(430000),
+            dec!(6.45),
+            dec!(-0.90),
+            25,
+            PaymentFrequency::Weekly,
+        )
+        .unwrap();
+        let weekly_rate = crate::rates::periodic_rate(
+            weekly.trigger_rate().unwrap() / dec!(100),
+            PaymentFrequency::Weekly,
+        )
+        .unwrap();
+        assert_eq!(
+            (dec!(430000) * weekly_rate).round_dp(6),
+            weekly.payment().unwrap().round_dp(6)
+        );
+    }
+
+    #[test]
+    fn static_payment_balance_grows_past_trigger_rate() {
+        let mortgage = mortgage().with_static_payment();
+        let changes = [
+            PrimeRateChange {
+                payment_number: 13,
+                prime_rate: dec!(10),
+            },
+            PrimeRateChange {
+                payment_number: 60,
+                prime_rate: dec!(5),
+            },
+        ];
+        let schedule = mortgage.amortization_schedule(&changes).unwrap();
+
+        assert!(schedule
+            .iter()
+            .all(|row| row.payment == schedule[0].payment || row.balance == dec!(0)));
+        assert!(schedule[12].principal < dec!(0), "negative amortization");
+        assert!(schedule.len() > 300, "amortization is extended");
+
+        let trigger_point = mortgage.trigger_point(&changes).unwrap().unwrap();
+        assert!(schedule[trigger_point as usize - 1].balance > dec!(430000));
+        assert!(schedule[trigger_point as usize - 2].balance <= dec!(430000));
+
+        assert_eq!(mortgage.trigger_point(&[]).unwrap(), None);
+    }
+
+    #[test]
+    fn static_payment_that_never_covers_interest_is_an_error() {
+        let changes = [PrimeRateChange {
+            payment_number: 13,
+            prime_rate: dec!(10),
+        }];
+        assert!(matches!(
+            mortgage()
+                .with_static_payment()
+                .amortization_schedule(&changes),
+            Err(MortgageError::PaymentBelowInterest { .. })
+        ));
+    }
+
+    #[test]
+    fn prime_rate_changes_reset_the_payment() {
+        let changes = [
+            PrimeRateChange {
+                payment_number: 25,
+                prime_rate: dec!(7.20),
+            },
+            PrimeRateChange {
+                payment_number: 13,
+                prime_rate: dec!(6.95),
+            },
+        ];
+        let schedule = mortgage().amortization_schedule(&changes).unwrap();
+
+        assert_eq!(schedule[11].payment, schedule[0].payment);
+        assert!(schedule[12].payment > schedule[11].payment);
+        assert!(schedule[24].payment > schedule[12].payment);
+        assert_eq!(
+            schedule[24].interest,
+            schedule[23].balance * dec!(0.0630) / dec!(12)
+        );
+        assert_eq!(schedule.len(), 300, "still paid off over the amortization");
+        assert_eq!(schedule.last().unwrap().balance, dec!(0));
+    }
+
+    #[test]
+    fn rate_stays_between_cap_and_floor() {
+        let capped = mortgage()
+            .with_rate_floor(dec!(4))
+            .unwrap()
+            .with_rate_cap(dec!(6))
+            .unwrap();
+        let changes = [
+            PrimeRateChange {
+                payment_number: 13,
+                prime_rate: dec!(7.95),
+            },
+            PrimeRateChange {
+                payment_number: 25,
+                prime_rate: dec!(3.95),
+            },
+        ];
+        let schedule = capped.amortization_schedule(&changes).unwrap();
+
+        assert_eq!(capped.interest_rate(), dec!(5.55));
+        assert_eq!(
+            schedule[12].interest.round_dp(10),
+            (schedule[11].balance * dec!(0.06) / dec!(12)).round_dp(10),
+            "capped at 6% rather than 7.05%"
+        );
+        assert_eq!(
+            schedule[24].interest.round_dp(10),
+            (schedule[23].balance * dec!(0.04) / dec!(12)).round_dp(10),
+            "floored at 4% rather than 3.05%"
+        );
+
+        assert_eq!(
+            mortgage()
+                .with_rate_cap(dec!(4))
+                .unwrap()
+                .with_rate_floor(dec!(5))
+                .unwrap_err(),
+            MortgageError::RateFloorAboveCap {
+                floor: dec!(5),
+                cap: dec!(4)
+            }
+        );
+    }
+}
@@ -0,0 +1,124 @@
+// Browser bindings. Amounts are passed and returned as decimal strings since JavaScript numbers
+// are floating point; schedules are returned in the JSON layout described in json.rs.
+use crate::{insurance, CanadianMortgage, MortgageError, PaymentFrequency, Result, ToJson};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(js_name = payment)]
+pub fn wasm_payment(
+    principal: &str,
+    rate: &str,
+    amortization: u32,
+    frequency: PaymentFrequency,
+) -> std::result::Result<String, JsValue> {
+    to_js(payment(principal, rate, amortization, frequency))
+}
+
+#[wasm_bindgen(js_name = amortizationSchedule)]
+pub fn wasm_amortization_schedule(
+    principal: &str,
+    rate: &str,
+    amortization: u32,
+    frequency: PaymentFrequency,
+) -> std::result::Result<String, JsValue> {
+    to_js(amortization_schedule(
+        principal,
+        rate,
+        amortization,
+        frequency,
+    ))
+}
+
+#[wasm_bindgen(js_name = insurancePremium)]
+pub fn wasm_insurance_premium(
+    loan: &str,
+    property_value: &str,
+    amortization: u32,
+) -> std::result::Result<String, JsValue> {
+    to_js(insurance_premium(loan, property_value, amortization))
+}
+
+fn payment(
+    principal: &str,
+    rate: &str,
+    amortization: u32,
+    frequency: PaymentFrequency,
+) -> Result<String> {
+    Ok(mortgage(principal, rate, amortization, frequency)?
+        .payment()?
+        .to_string())
+}
+
+fn amortization_schedule(
+    principal: &str,
+    rate: &str,
+    amortization: u32,
+    frequency: PaymentFrequency,
+) -> Result<String> {
+    mortgage(principal, rate, amortization, frequency)?
+        .amortization_schedule()?
+        .to_json()
+}
+
+fn insurance_premium(loan: &str, property_value: &str, amortization: u32) -> Result<String> {
+    Ok(insurance::premium(
+        decimal(loan)?,
+        decimal(property_value)?,
+        amortization.into(),
+    )?
+    .to_string())
+}
+
+fn mortgage(
+    principal: &str,
+    rate: &str,
+    amortization: u32,
+    frequency: PaymentFrequency,
+) -> Result<CanadianMortgage> {
+    CanadianMortgage::new(
+        decimal(principal)?,
+        decimal(rate)?,
+        amortization.into(),
+        frequency,
+    )
+}
+
+fn decimal(value: &str) -> Result<Decimal> {
+    Decimal::from_str(value)
+        .map_err(|e| MortgageError::ConversionFailure(format!("{}: {}", value, e)))
+}
+
+fn to_js(result: Result<String>) -> std::result::Result<String, JsValue> {
+    result.map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::*;
+
+    #[test]
+    fn amounts_are_decimal_strings() {
+        let expected =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly)
+                .unwrap()
+                .payment()
+                .unwrap();
+        assert_eq!(
+            payment("430000", "4.59", 25, PaymentFrequency::Monthly).unwrap(),
+            expected.to_string()
+        );
+        assert_eq!(
+            insurance_premium("475000", "500000", 25).unwrap(),
+            "19000.00"
+        );
+        assert!(payment("lots", "4.59", 25, PaymentFrequency::Monthly).is_err());
+    }
+
+    #[test]
+    fn schedule_is_json() {
+        let json = amortization_schedule("430000", "4.59", 25, PaymentFrequency::Monthly).unwrap();
+        assert!(json.starts_with("[{\"payment_number\":1,"));
+    }
+}
@@ -0,0 +1,193 @@
+// Excel export: a workbook with the schedule, the summary, and optionally a prepayment scenario
+// compared with the mortgage as it is, each on its own sheet. Amounts are written as numbers
+// formatted as currency, so they can still be summed and charted in the spreadsheet. They lose
+// precision past about 15 significant digits, as every number in Excel does.
+use crate::{CanadianMortgage, MortgageError, PrepaymentScenario, Result};
+use rust_decimal::prelude::*;
+use rust_xlsxwriter::{Format, Workbook, Worksheet, XlsxError};
+
+const CURRENCY: &str = "$#,##0.00";
+const AMOUNT_WIDTH: f64 = 14.0;
+
+impl CanadianMortgage {
+    // The workbook as the bytes of an .xlsx file.
+    pub fn to_xlsx(&self, scenario: Option<&PrepaymentScenario>) -> Result<Vec<u8>> {
+        let schedule = self.amortization_schedule()?;
+        let summary = self.summarize(&schedule);
+        let report = scenario
+            .map(|scenario| self.compare_scenario(scenario))
+            .transpose()?;
+
+        let currency = Format::new().set_num_format(CURRENCY);
+        let bold = Format::new().set_bold();
+        let mut workbook = Workbook::new();
+
+        let sheet = workbook
+            .add_worksheet()
+            .set_name("Schedule")
+            .map_err(xlsx_error)?;
+        let headings = [
+            "payment_number",
+            "payment",
+            "interest",
+            "principal",
+            "prepayment",
+            "balance",
+        ];
+        for (col, heading) in (0..).zip(headings.iter()) {
+            sheet
+                .write_string_with_format(0, col, *heading, &bold)
+                .map_err(xlsx_error)?;
+            sheet
+                .set_column_width(col, AMOUNT_WIDTH)
+                .map_err(xlsx_error)?;
+        }
+        sheet.set_freeze_panes(1, 0).map_err(xlsx_error)?;
+        for (row, payment) in (1..).zip(schedule.iter()) {
+            sheet
+                .write_number(row, 0, payment.payment_number as f64)
+                .map_err(xlsx_error)?;
+            let amounts = [
+                payment.payment,
+                payment.interest,
+                payment.principal,
+                payment.prepayment,
+                payment.balance,
+            ];
+            for (col, amount) in (1..).zip(amounts.iter()) {
+                write_amount(sheet, row, col, *amount, &currency)?;
+            }
+        }
+
+        let sheet = workbook
+            .add_worksheet()
+            .set_name("Summary")
+            .map_err(xlsx_error)?;
+        sheet.set_column_width(0, 22).map_err(xlsx_error)?;
+        sheet
+            .set_column_width(1, AMOUNT_WIDTH)
+            .map_err(xlsx_error)?;
+        write_label(sheet, 0, "number_of_payments", &bold)?;
+        sheet
+            .write_number(0, 1, summary.number_of_payments as f64)
+            .map_err(xlsx_error)?;
+        let amounts = [
+            ("total_payments", summary.total_payments),
+            ("total_interest", summary.total_interest),
+            ("capitalized_interest", summary.capitalized_interest),
+            ("premium_interest", summary.premium_interest),
+        ];
+        for (row, (label, amount)) in (1..).zip(amounts.iter()) {
+            write_label(sheet, row, label, &bold)?;
+            write_amount(sheet, row, 1, *amount, &currency)?;
+        }
+        write_label(sheet, 5, "interest_to_principal", &bold)?;
+        sheet
+            .write_number(5, 1, to_f64(summary.interest_to_principal))
+            .map_err(xlsx_error)?;
+
+        if let Some(report) = report {
+            let sheet = workbook
+                .add_worksheet()
+                .set_name("Scenario")
+                .map_err(xlsx_error)?;
+            sheet.set_column_width(0, 22).map_err(xlsx_error)?;
+            for (col, heading) in (1..).zip(["baseline", "scenario"].iter()) {
+                sheet
+                    .write_string_with_format(0, col, *heading, &bold)
+                    .map_err(xlsx_error)?;
+                sheet
+                    .set_column_width(col, AMOUNT_WIDTH)
+                    .map_err(xlsx_error)?;
+            }
+            write_label(sheet, 1, "number_of_payments", &bold)?;
+            sheet
+                .write_number(1, 1, report.baseline.number_of_payments as f64)
+                .map_err(xlsx_error)?;
+            sheet
+                .write_number(1, 2, report.scenario.number_of_payments as f64)
+                .map_err(xlsx_error)?;
+            let rows = [
+                (
+                    "total_payments",
+                    report.baseline.total_payments,
+                    report.scenario.total_payments,
+                ),
+                (
+                    "total_interest",
+                    report.baseline.total_interest,
+                    report.scenario.total_interest,
+                ),
+            ];
+            for (row, (label, baseline, scenario)) in (2..).zip(rows.iter()) {
+                write_label(sheet, row, label, &bold)?;
+                write_amount(sheet, row, 1, *baseline, &currency)?;
+                write_amount(sheet, row, 2, *scenario, &currency)?;
+            }
+            write_label(sheet, 4, "payments_eliminated", &bold)?;
+            sheet
+                .write_number(4, 1, report.payments_eliminated as f64)
+                .map_err(xlsx_error)?;
+            write_label(sheet, 5, "interest_saved", &bold)?;
+            write_amount(sheet, 5, 1, report.interest_saved, &currency)?;
+        }
+
+        workbook.save_to_buffer().map_err(xlsx_error)
+    }
+}
+
+fn write_label(sheet: &mut Worksheet, row: u32, label: &str, bold: &Format) -> Result<()> {
+    sheet
+        .write_string_with_format(row, 0, label, bold)
+        .map_err(xlsx_error)?;
+    Ok(())
+}
+
+fn write_amount(
+    sheet: &mut Worksheet,
+    row: u32,
+    col: u16,
+    amount: Decimal,
+    currency: &Format,
+) -> Result<()> {
+    sheet
+        .write_number_with_format(row, col, to_f64(amount), currency)
+        .map_err(xlsx_error)?;
+    Ok(())
+}
+
+fn to_f64(amount: Decimal) -> f64 {
+    amount.to_f64().unwrap_or_default()
+}
+
+fn xlsx_error(e: XlsxError) -> MortgageError {
+    MortgageError::Serialization(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PaymentFrequency;
+    use rust_decimal_macros::*;
+
+    fn contains(bytes: &[u8], name: &str) -> bool {
+        bytes
+            .windows(name.len())
+            .any(|window| window == name.as_bytes())
+    }
+
+    #[test]
+    fn workbook_has_a_sheet_for_each_part() {
+        let mortgage =
+            CanadianMortgage::new(dec!(430000), dec!(4.59), 25, PaymentFrequency::Monthly).unwrap();
+
+        let xlsx = mortgage.to_xlsx(None).unwrap();
+        assert!(xlsx.starts_with(b"PK"), "a zip archive");
+        assert!(contains(&xlsx, "xl/worksheets/sheet2.xml"));
+        assert!(!contains(&xlsx, "xl/worksheets/sheet3.xml"));
+
+        let scenario = PrepaymentScenario::new().with_lump_sum(12, dec!(20000));
+        let xlsx = mortgage.to_xlsx(Some(&scenario)).unwrap();
+        assert!(contains(&xlsx, "xl/worksheets/sheet3.xml"));
+    }
+}